@@ -4,13 +4,25 @@ use crate::rest_model::*;
 use crate::util::*;
 
 use rust_decimal::prelude::*;
+use std::future::Future;
 
 static API_V3_ACCOUNT: &str = "/api/v3/account";
 static API_V3_OPEN_ORDERS: &str = "/api/v3/openOrders";
 static API_V3_ALL_ORDERS: &str = "/api/v3/allOrders";
 static API_V3_MYTRADES: &str = "/api/v3/myTrades";
+static API_V3_MY_PREVENTED_MATCHES: &str = "/api/v3/myPreventedMatches";
 static API_V3_ORDER: &str = "/api/v3/order";
 static API_V3_CANCEL_REPLACE: &str = "/api/v3/order/cancelReplace";
+static API_V3_AMEND_KEEP_PRIORITY: &str = "/api/v3/order/amend/keepPriority";
+static API_V3_SOR_ORDER: &str = "/api/v3/sor/order";
+static API_V3_SOR_ORDER_TEST: &str = "/api/v3/sor/order/test";
+static API_V3_MY_ALLOCATIONS: &str = "/api/v3/myAllocations";
+static API_V3_ORDER_LIST: &str = "/api/v3/orderList";
+static API_V3_ORDER_LIST_OCO: &str = "/api/v3/orderList/oco";
+static API_V3_ORDER_LIST_OTO: &str = "/api/v3/orderList/oto";
+static API_V3_ORDER_LIST_OTOCO: &str = "/api/v3/orderList/otoco";
+static API_V3_ALL_ORDER_LIST: &str = "/api/v3/allOrderList";
+static API_V3_OPEN_ORDER_LIST: &str = "/api/v3/openOrderList";
 /// Endpoint for test orders.
 /// Orders issued to this endpoint are validated, but not sent into the matching engine.
 static API_V3_ORDER_TEST: &str = "/api/v3/order/test";
@@ -41,21 +53,162 @@ pub struct OrderRequest {
     pub stop_price: Option<Decimal>,
     /// Used with limit, stop loss limit and take profit limit to create an iceberg order.
     pub iceberg_qty: Option<Decimal>,
+    /// Used with stop loss, stop loss limit, take profit and take profit limit order types, in BIPS.
+    pub trailing_delta: Option<u32>,
+    /// Arbitrary numeric value identifying the order within an order strategy.
+    pub strategy_id: Option<u64>,
+    /// Arbitrary numeric value identifying the order strategy, values smaller than 1000000 are reserved.
+    pub strategy_type: Option<u64>,
     /// Set the response json, market and limit default to full others to ack.
     pub new_order_resp_type: Option<OrderResponse>,
+    /// Defaults to the symbol's default self-trade prevention mode.
+    pub self_trade_prevention_mode: Option<SelfTradePreventionMode>,
     /// Cannot be greater than 60000
     pub recv_window: Option<u64>,
 }
 
 impl OrderRequest {
-    fn valid(&self) -> Result<()> {
+    /// An order of a kind, which carries the parameters its type requires
+    pub fn new(symbol: impl Into<String>, side: OrderSide, kind: OrderSpec) -> Self {
+        Self {
+            symbol: symbol.into(),
+            side,
+            order_type: kind.order_type(),
+            time_in_force: kind.time_in_force(),
+            quantity: kind.quantity(),
+            quote_order_qty: kind.quote_order_qty(),
+            price: kind.price(),
+            stop_price: kind.stop_price(),
+            ..Self::default()
+        }
+    }
+
+    /// A LIMIT order, good till canceled
+    pub fn limit(symbol: impl Into<String>, side: OrderSide, qty: Decimal, price: Decimal) -> Self {
+        Self::new(symbol, side, OrderSpec::Limit { qty, price })
+    }
+
+    /// A LIMIT_MAKER order, rejected if it would immediately match as a taker
+    pub fn limit_maker(symbol: impl Into<String>, side: OrderSide, qty: Decimal, price: Decimal) -> Self {
+        Self::new(symbol, side, OrderSpec::LimitMaker { qty, price })
+    }
+
+    /// A MARKET order for a quantity of the base asset
+    pub fn market(symbol: impl Into<String>, side: OrderSide, qty: Decimal) -> Self {
+        Self::new(symbol, side, OrderSpec::Market { qty })
+    }
+
+    /// A MARKET order spending (when buying) or receiving (when selling) a quantity of the quote asset
+    pub fn market_quote(symbol: impl Into<String>, side: OrderSide, quote_qty: Decimal) -> Self {
+        Self::new(symbol, side, OrderSpec::MarketQuote { quote_qty })
+    }
+
+    /// A STOP_LOSS order, executing a MARKET order once the stop price is reached
+    pub fn stop_loss(symbol: impl Into<String>, side: OrderSide, qty: Decimal, stop_price: Decimal) -> Self {
+        Self::new(symbol, side, OrderSpec::StopLoss { qty, stop_price })
+    }
+
+    /// A STOP_LOSS_LIMIT order, placing a good till canceled LIMIT order once the stop price is reached
+    pub fn stop_loss_limit(
+        symbol: impl Into<String>,
+        side: OrderSide,
+        qty: Decimal,
+        price: Decimal,
+        stop_price: Decimal,
+    ) -> Self {
+        Self::new(symbol, side, OrderSpec::StopLossLimit { qty, price, stop_price })
+    }
+
+    /// A TAKE_PROFIT order, executing a MARKET order once the stop price is reached
+    pub fn take_profit(symbol: impl Into<String>, side: OrderSide, qty: Decimal, stop_price: Decimal) -> Self {
+        Self::new(symbol, side, OrderSpec::TakeProfit { qty, stop_price })
+    }
+
+    /// A TAKE_PROFIT_LIMIT order, placing a good till canceled LIMIT order once the stop price is reached
+    pub fn take_profit_limit(
+        symbol: impl Into<String>,
+        side: OrderSide,
+        qty: Decimal,
+        price: Decimal,
+        stop_price: Decimal,
+    ) -> Self {
+        Self::new(symbol, side, OrderSpec::TakeProfitLimit { qty, price, stop_price })
+    }
+
+    /// Checks that the order carries the parameters its order type requires
+    pub fn valid(&self) -> Result<()> {
         if self.iceberg_qty.is_some() && self.time_in_force != Some(TimeInForce::GTC) {
             return Err(Error::InvalidOrderError {
                 msg: "Time in force has to be GTC for iceberg orders".to_string(),
             });
         }
-        Ok(())
+        valid_trailing_delta_type(&self.order_type, self.trailing_delta)?;
+        valid_order_quantity(&self.order_type, self.quantity, self.quote_order_qty)?;
+        valid_order_params(
+            "order",
+            &self.order_type,
+            self.price,
+            self.stop_price,
+            self.trailing_delta,
+            self.time_in_force.as_ref(),
+            self.iceberg_qty,
+        )
     }
+
+    /// Checks the trailing delta against the symbol's TRAILING_DELTA filter
+    pub fn valid_trailing_delta(&self, symbol: &Symbol) -> Result<()> {
+        valid_trailing_delta_bounds(&self.side, &self.order_type, self.trailing_delta, symbol)
+    }
+}
+
+/// Checks that the trailing delta is only used with stop loss and take profit order types
+fn valid_trailing_delta_type(order_type: &OrderType, trailing_delta: Option<u32>) -> Result<()> {
+    let allowed = matches!(
+        order_type,
+        OrderType::StopLoss | OrderType::StopLossLimit | OrderType::TakeProfit | OrderType::TakeProfitLimit
+    );
+    if trailing_delta.is_some() && !allowed {
+        return Err(Error::InvalidOrderError {
+            msg: format!("Trailing delta cannot be used with {order_type:?} orders"),
+        });
+    }
+    Ok(())
+}
+
+/// Checks that the trailing delta is within the TRAILING_DELTA filter bounds,
+/// BUY stop losses and SELL take profits trigger above the market, the others below it
+fn valid_trailing_delta_bounds(
+    side: &OrderSide,
+    order_type: &OrderType,
+    trailing_delta: Option<u32>,
+    symbol: &Symbol,
+) -> Result<()> {
+    let (Some(delta), Some(filter)) = (trailing_delta, symbol.trailing_delta()) else {
+        return Ok(());
+    };
+    let Filters::TrailingDelta {
+        min_trailing_above_delta,
+        max_trailing_above_delta,
+        min_trailing_below_delta,
+        max_trailing_below_delta,
+    } = filter
+    else {
+        return Ok(());
+    };
+    let stop_loss = matches!(order_type, OrderType::StopLoss | OrderType::StopLossLimit);
+    let (min, max) = match (side, stop_loss) {
+        (OrderSide::Buy, true) | (OrderSide::Sell, false) => (min_trailing_above_delta, max_trailing_above_delta),
+        (OrderSide::Sell, true) | (OrderSide::Buy, false) => (min_trailing_below_delta, max_trailing_below_delta),
+    };
+    if delta < min || delta > max {
+        return Err(Error::InvalidOrderError {
+            msg: format!(
+                "Trailing delta {delta} is outside of [{min}, {max}] for {}",
+                symbol.symbol
+            ),
+        });
+    }
+    Ok(())
 }
 
 /// Order Cancellation Request
@@ -94,7 +247,11 @@ pub struct CancelReplaceRequest {
     pub new_client_order_id: Option<String>,
     pub stop_price: Option<Decimal>,
     pub iceberg_qty: Option<Decimal>,
+    pub trailing_delta: Option<u32>,
+    pub strategy_id: Option<u64>,
+    pub strategy_type: Option<u64>,
     pub new_order_resp_type: Option<OrderResponse>,
+    pub self_trade_prevention_mode: Option<SelfTradePreventionMode>,
     /// Cannot be greater than 60000
     pub recv_window: Option<u64>,
 }
@@ -106,10 +263,427 @@ impl CancelReplaceRequest {
                 msg: "Time in force has to be GTC for iceberg orders".to_string(),
             });
         }
+        valid_trailing_delta_type(&self.order_type, self.trailing_delta)?;
+        valid_order_quantity(&self.order_type, self.quantity, self.quote_order_qty)?;
+        valid_order_params(
+            "order",
+            &self.order_type,
+            self.price,
+            self.stop_price,
+            self.trailing_delta,
+            self.time_in_force.as_ref(),
+            self.iceberg_qty,
+        )
+    }
+
+    /// Checks the trailing delta against the symbol's TRAILING_DELTA filter
+    pub fn valid_trailing_delta(&self, symbol: &Symbol) -> Result<()> {
+        valid_trailing_delta_bounds(&self.side, &self.order_type, self.trailing_delta, symbol)
+    }
+}
+
+/// Smart Order Routing Order Request
+/// perform an order routed across the symbols sharing the same base asset and an interchangeable quote asset
+/// only LIMIT and MARKET orders are supported, MARKET orders cannot use quote_order_qty
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SorOrderRequest {
+    pub symbol: String,
+    pub side: OrderSide,
+    #[serde(rename = "type")]
+    pub order_type: OrderType,
+    pub time_in_force: Option<TimeInForce>,
+    pub quantity: Decimal,
+    pub price: Option<Decimal>,
+    /// A unique id for the order, automatically generated if not sent.
+    pub new_client_order_id: Option<String>,
+    pub strategy_id: Option<u64>,
+    /// Values smaller than 1000000 are reserved and cannot be used.
+    pub strategy_type: Option<u64>,
+    /// Used with limit to create an iceberg order.
+    pub iceberg_qty: Option<Decimal>,
+    pub new_order_resp_type: Option<OrderResponse>,
+    pub self_trade_prevention_mode: Option<SelfTradePreventionMode>,
+    /// Cannot be greater than 60000
+    pub recv_window: Option<u64>,
+}
+
+impl SorOrderRequest {
+    pub fn valid(&self) -> Result<()> {
+        let msg = match self.order_type {
+            OrderType::Limit if self.price.is_none() => "Price is required for SOR limit orders",
+            OrderType::Limit if self.time_in_force.is_none() => "Time in force is required for SOR limit orders",
+            OrderType::Limit | OrderType::Market => {
+                if self.iceberg_qty.is_some() && self.time_in_force != Some(TimeInForce::GTC) {
+                    "Time in force has to be GTC for iceberg orders"
+                } else {
+                    return Ok(());
+                }
+            }
+            _ => "Only LIMIT and MARKET orders can be routed through SOR",
+        };
+        Err(Error::InvalidOrderError { msg: msg.to_string() })
+    }
+}
+
+/// Allocations Query
+/// perform a query on the allocations resulting from SOR orders
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AllocationsQuery {
+    pub symbol: String,
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    pub from_allocation_id: Option<u64>,
+    /// Default 500 max 1000
+    pub limit: Option<u32>,
+    pub order_id: Option<u64>,
+    /// Cannot be greater than 60000
+    pub recv_window: Option<u64>,
+}
+
+/// Order Amendment Request
+/// reduces the quantity of an existing open order without losing its priority in the book
+/// either order_id or orig_client_order_id must be set
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderAmendRequest {
+    pub symbol: String,
+    pub order_id: Option<u64>,
+    pub orig_client_order_id: Option<String>,
+    /// The new client order id for the amended order, automatically generated if not sent.
+    pub new_client_order_id: Option<String>,
+    /// Must be greater than 0 and less than the order's current quantity
+    pub new_qty: Decimal,
+    /// Cannot be greater than 60000
+    pub recv_window: Option<u64>,
+}
+
+impl OrderAmendRequest {
+    pub fn valid(&self) -> Result<()> {
+        if self.new_qty <= Decimal::ZERO {
+            return Err(Error::InvalidOrderError {
+                msg: "New quantity has to be greater than 0".to_string(),
+            });
+        }
+        if self.order_id.is_none() && self.orig_client_order_id.is_none() {
+            return Err(Error::InvalidOrderError {
+                msg: "Either order id or original client order id is required".to_string(),
+            });
+        }
         Ok(())
     }
 }
 
+/// Order types allowed for the above leg of an OCO
+static OCO_ABOVE_TYPES: [OrderType; 5] = [
+    OrderType::StopLossLimit,
+    OrderType::StopLoss,
+    OrderType::LimitMaker,
+    OrderType::TakeProfit,
+    OrderType::TakeProfitLimit,
+];
+/// Order types allowed for the below leg of an OCO
+static OCO_BELOW_TYPES: [OrderType; 4] = [
+    OrderType::StopLoss,
+    OrderType::StopLossLimit,
+    OrderType::TakeProfit,
+    OrderType::TakeProfitLimit,
+];
+/// Order types allowed for the below leg of the pending OCO in an OTOCO
+static OTOCO_BELOW_TYPES: [OrderType; 5] = [
+    OrderType::LimitMaker,
+    OrderType::StopLoss,
+    OrderType::StopLossLimit,
+    OrderType::TakeProfit,
+    OrderType::TakeProfitLimit,
+];
+/// Order types allowed for the working order of an OTO or OTOCO
+static WORKING_TYPES: [OrderType; 2] = [OrderType::Limit, OrderType::LimitMaker];
+
+/// Checks that an order, or a single leg of an order list, carries the parameters its order type requires
+pub(crate) fn valid_order_params(
+    leg: &str,
+    order_type: &OrderType,
+    price: Option<Decimal>,
+    stop_price: Option<Decimal>,
+    trailing_delta: Option<u32>,
+    time_in_force: Option<&TimeInForce>,
+    iceberg_qty: Option<Decimal>,
+) -> Result<()> {
+    let msg = if order_type.requires_price() && price.is_none() {
+        format!("{leg} price is required for {order_type:?} orders")
+    } else if order_type.requires_stop_price() && stop_price.is_none() && trailing_delta.is_none() {
+        format!("{leg} stop price or trailing delta is required for {order_type:?} orders")
+    } else if order_type.requires_time_in_force() && time_in_force.is_none() {
+        format!("{leg} time in force is required for {order_type:?} orders")
+    } else if iceberg_qty.is_some() && order_type.requires_time_in_force() && time_in_force != Some(&TimeInForce::GTC) {
+        format!("{leg} time in force has to be GTC for iceberg orders")
+    } else {
+        return Ok(());
+    };
+    Err(Error::InvalidOrderError { msg })
+}
+
+/// Checks that market orders set exactly one of quantity or quote order quantity, and that others set a quantity
+pub(crate) fn valid_order_quantity(
+    order_type: &OrderType,
+    quantity: Option<Decimal>,
+    quote_order_qty: Option<Decimal>,
+) -> Result<()> {
+    let msg = match (order_type, quantity, quote_order_qty) {
+        (OrderType::Market, Some(_), Some(_)) => "Market orders cannot set both quantity and quote order quantity",
+        (OrderType::Market, None, None) => "Market orders require either quantity or quote order quantity",
+        (OrderType::Market, _, _) => return Ok(()),
+        (_, _, Some(_)) => "Quote order quantity can only be used with market orders",
+        (_, None, _) => "Quantity is required",
+        _ => return Ok(()),
+    };
+    Err(Error::InvalidOrderError { msg: msg.to_string() })
+}
+
+/// Checks that an order list leg uses one of the allowed order types
+fn valid_list_leg_type(leg: &str, order_type: &OrderType, allowed: &[OrderType]) -> Result<()> {
+    if !allowed.contains(order_type) {
+        return Err(Error::InvalidOrderError {
+            msg: format!("{leg} type must be one of {allowed:?}, got {order_type:?}"),
+        });
+    }
+    Ok(())
+}
+
+/// OCO Order Request
+/// places a one-cancels-the-other pair of orders, the above leg is priced above the market
+/// and the below leg under it. When either leg fills, the other one is canceled.
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OcoOrderRequest {
+    pub symbol: String,
+    /// A unique id for the list, automatically generated if not sent.
+    pub list_client_order_id: Option<String>,
+    pub side: OrderSide,
+    pub quantity: Decimal,
+    /// One of STOP_LOSS_LIMIT, STOP_LOSS, LIMIT_MAKER, TAKE_PROFIT or TAKE_PROFIT_LIMIT
+    pub above_type: OrderType,
+    pub above_client_order_id: Option<String>,
+    pub above_iceberg_qty: Option<Decimal>,
+    pub above_price: Option<Decimal>,
+    pub above_stop_price: Option<Decimal>,
+    pub above_trailing_delta: Option<u32>,
+    pub above_time_in_force: Option<TimeInForce>,
+    /// One of STOP_LOSS, STOP_LOSS_LIMIT, TAKE_PROFIT or TAKE_PROFIT_LIMIT
+    pub below_type: OrderType,
+    pub below_client_order_id: Option<String>,
+    pub below_iceberg_qty: Option<Decimal>,
+    pub below_price: Option<Decimal>,
+    pub below_stop_price: Option<Decimal>,
+    pub below_trailing_delta: Option<u32>,
+    pub below_time_in_force: Option<TimeInForce>,
+    pub new_order_resp_type: Option<OrderResponse>,
+    /// Cannot be greater than 60000
+    pub recv_window: Option<u64>,
+}
+
+impl OcoOrderRequest {
+    pub fn valid(&self) -> Result<()> {
+        valid_list_leg_type("above", &self.above_type, &OCO_ABOVE_TYPES)?;
+        valid_list_leg_type("below", &self.below_type, &OCO_BELOW_TYPES)?;
+        valid_order_params(
+            "above",
+            &self.above_type,
+            self.above_price,
+            self.above_stop_price,
+            self.above_trailing_delta,
+            self.above_time_in_force.as_ref(),
+            self.above_iceberg_qty,
+        )?;
+        valid_order_params(
+            "below",
+            &self.below_type,
+            self.below_price,
+            self.below_stop_price,
+            self.below_trailing_delta,
+            self.below_time_in_force.as_ref(),
+            self.below_iceberg_qty,
+        )
+    }
+}
+
+/// OTO Order Request
+/// places a working LIMIT or LIMIT_MAKER order, and a pending order that is only placed
+/// once the working order is fully filled.
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OtoOrderRequest {
+    pub symbol: String,
+    /// A unique id for the list, automatically generated if not sent.
+    pub list_client_order_id: Option<String>,
+    pub new_order_resp_type: Option<OrderResponse>,
+    /// One of LIMIT or LIMIT_MAKER
+    pub working_type: OrderType,
+    pub working_side: OrderSide,
+    pub working_client_order_id: Option<String>,
+    pub working_price: Decimal,
+    pub working_quantity: Decimal,
+    pub working_iceberg_qty: Option<Decimal>,
+    pub working_time_in_force: Option<TimeInForce>,
+    pub pending_type: OrderType,
+    pub pending_side: OrderSide,
+    pub pending_client_order_id: Option<String>,
+    pub pending_price: Option<Decimal>,
+    pub pending_stop_price: Option<Decimal>,
+    pub pending_trailing_delta: Option<u32>,
+    pub pending_quantity: Decimal,
+    pub pending_iceberg_qty: Option<Decimal>,
+    pub pending_time_in_force: Option<TimeInForce>,
+    /// Cannot be greater than 60000
+    pub recv_window: Option<u64>,
+}
+
+impl OtoOrderRequest {
+    pub fn valid(&self) -> Result<()> {
+        valid_list_leg_type("working", &self.working_type, &WORKING_TYPES)?;
+        valid_order_params(
+            "working",
+            &self.working_type,
+            Some(self.working_price),
+            None,
+            None,
+            self.working_time_in_force.as_ref(),
+            self.working_iceberg_qty,
+        )?;
+        if self.pending_type == OrderType::Other {
+            return Err(Error::InvalidOrderError {
+                msg: "pending type must be a known order type".to_string(),
+            });
+        }
+        valid_order_params(
+            "pending",
+            &self.pending_type,
+            self.pending_price,
+            self.pending_stop_price,
+            self.pending_trailing_delta,
+            self.pending_time_in_force.as_ref(),
+            self.pending_iceberg_qty,
+        )
+    }
+}
+
+/// OTOCO Order Request
+/// places a working LIMIT or LIMIT_MAKER order, and a pending OCO pair that is only placed
+/// once the working order is fully filled.
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OtocoOrderRequest {
+    pub symbol: String,
+    /// A unique id for the list, automatically generated if not sent.
+    pub list_client_order_id: Option<String>,
+    pub new_order_resp_type: Option<OrderResponse>,
+    /// One of LIMIT or LIMIT_MAKER
+    pub working_type: OrderType,
+    pub working_side: OrderSide,
+    pub working_client_order_id: Option<String>,
+    pub working_price: Decimal,
+    pub working_quantity: Decimal,
+    pub working_iceberg_qty: Option<Decimal>,
+    pub working_time_in_force: Option<TimeInForce>,
+    pub pending_side: OrderSide,
+    pub pending_quantity: Decimal,
+    /// One of STOP_LOSS_LIMIT, STOP_LOSS, LIMIT_MAKER, TAKE_PROFIT or TAKE_PROFIT_LIMIT
+    pub pending_above_type: OrderType,
+    pub pending_above_client_order_id: Option<String>,
+    pub pending_above_price: Option<Decimal>,
+    pub pending_above_stop_price: Option<Decimal>,
+    pub pending_above_trailing_delta: Option<u32>,
+    pub pending_above_iceberg_qty: Option<Decimal>,
+    pub pending_above_time_in_force: Option<TimeInForce>,
+    /// One of LIMIT_MAKER, STOP_LOSS, STOP_LOSS_LIMIT, TAKE_PROFIT or TAKE_PROFIT_LIMIT
+    pub pending_below_type: OrderType,
+    pub pending_below_client_order_id: Option<String>,
+    pub pending_below_price: Option<Decimal>,
+    pub pending_below_stop_price: Option<Decimal>,
+    pub pending_below_trailing_delta: Option<u32>,
+    pub pending_below_iceberg_qty: Option<Decimal>,
+    pub pending_below_time_in_force: Option<TimeInForce>,
+    /// Cannot be greater than 60000
+    pub recv_window: Option<u64>,
+}
+
+impl OtocoOrderRequest {
+    pub fn valid(&self) -> Result<()> {
+        valid_list_leg_type("working", &self.working_type, &WORKING_TYPES)?;
+        valid_list_leg_type("pending above", &self.pending_above_type, &OCO_ABOVE_TYPES)?;
+        valid_list_leg_type("pending below", &self.pending_below_type, &OTOCO_BELOW_TYPES)?;
+        valid_order_params(
+            "working",
+            &self.working_type,
+            Some(self.working_price),
+            None,
+            None,
+            self.working_time_in_force.as_ref(),
+            self.working_iceberg_qty,
+        )?;
+        valid_order_params(
+            "pending above",
+            &self.pending_above_type,
+            self.pending_above_price,
+            self.pending_above_stop_price,
+            self.pending_above_trailing_delta,
+            self.pending_above_time_in_force.as_ref(),
+            self.pending_above_iceberg_qty,
+        )?;
+        valid_order_params(
+            "pending below",
+            &self.pending_below_type,
+            self.pending_below_price,
+            self.pending_below_stop_price,
+            self.pending_below_trailing_delta,
+            self.pending_below_time_in_force.as_ref(),
+            self.pending_below_iceberg_qty,
+        )
+    }
+}
+
+/// Order List Cancellation Request
+/// either order_list_id or list_client_order_id must be set
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderListCancellation {
+    pub symbol: String,
+    pub order_list_id: Option<u64>,
+    pub list_client_order_id: Option<String>,
+    /// Used to uniquely identify this cancel. Automatically generated by default.
+    pub new_client_order_id: Option<String>,
+    /// Cannot be greater than 60000
+    pub recv_window: Option<u64>,
+}
+
+/// Order List Status Request
+/// either order_list_id or orig_client_order_id must be set
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderListStatusRequest {
+    pub order_list_id: Option<u64>,
+    pub orig_client_order_id: Option<String>,
+    /// Cannot be greater than 60000
+    pub recv_window: Option<u64>,
+}
+
+/// Order Lists Query
+/// perform a query on all order lists for the account
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderListsQuery {
+    /// If set, start_time and end_time cannot be provided
+    pub from_id: Option<u64>,
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    /// Default 500 max 1000
+    pub limit: Option<u32>,
+    /// Cannot be greater than 60000
+    pub recv_window: Option<u64>,
+}
+
 /// Order Status Request
 /// perform an order status request for the account
 #[derive(Default, Debug, Serialize, Deserialize, Clone)]
@@ -137,6 +711,63 @@ pub struct OrdersQuery {
     pub recv_window: Option<u64>,
 }
 
+/// Prevented Matches Query
+/// perform a query on the orders that expired because of self-trade prevention
+/// either prevented_match_id or order_id must be set
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PreventedMatchesQuery {
+    pub symbol: String,
+    pub prevented_match_id: Option<u64>,
+    pub order_id: Option<u64>,
+    /// Used with order_id to page through prevented matches
+    pub from_prevented_match_id: Option<u64>,
+    /// Default 500 max 1000
+    pub limit: Option<u32>,
+    /// Cannot be greater than 60000
+    pub recv_window: Option<u64>,
+}
+
+/// Trades Query
+/// perform a query on the trades of a symbol for the account
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TradesQuery {
+    pub symbol: String,
+    /// Only the trades of this order
+    pub order_id: Option<u64>,
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    /// Trade id to start from, the latest trades are returned by default
+    pub from_id: Option<u64>,
+    /// Default 500 max 1000
+    pub limit: Option<u32>,
+    /// Cannot be greater than 60000
+    pub recv_window: Option<u64>,
+}
+
+/// Maximum page size of the trades endpoint
+pub const MAX_TRADES_LIMIT: u32 = 1000;
+
+/// Requests pages of trades by id, from the first trade until a page is not full
+pub(crate) async fn paginate_trades<F, Fut>(limit: u32, mut page: F) -> Result<Vec<TradeHistory>>
+where
+    F: FnMut(u64) -> Fut,
+    Fut: Future<Output = Result<Vec<TradeHistory>>>,
+{
+    let mut trades: Vec<TradeHistory> = Vec::new();
+    let mut from_id = 0;
+    loop {
+        let batch = page(from_id).await?;
+        let last_page = batch.len() < limit as usize;
+        trades.extend(batch);
+        match trades.last() {
+            Some(trade) if !last_page => from_id = trade.id + 1,
+            _ => return Ok(trades),
+        }
+    }
+}
+
 impl Account {
     /// General account information
     /// # Examples
@@ -295,15 +926,11 @@ impl Account {
     /// # Examples
     /// ```rust,no_run
     /// use binance::{api::*, account::*, config::*, rest_model::*};
+    /// use rust_decimal_macros::dec;
     /// let account: Account = Binance::new_with_env(&Config::testnet());
     /// let limit_buy = OrderRequest {
-    ///         symbol: "BTCUSDT".to_string(),
-    ///         quantity: Some(10.0),
-    ///         price: Some(0.014000),
-    ///         order_type: OrderType::Limit,
-    ///         side: OrderSide::Buy,
     ///         time_in_force: Some(TimeInForce::FOK),
-    ///         ..OrderRequest::default()
+    ///         ..OrderRequest::limit("BTCUSDT", OrderSide::Buy, dec!(10.0), dec!(0.014000))
     ///     };
     /// let transaction = tokio_test::block_on(account.place_order(limit_buy));
     /// assert!(transaction.is_ok(), "{:?}", transaction);
@@ -322,15 +949,11 @@ impl Account {
     /// # Examples
     /// ```rust,no_run
     /// use binance::{api::*, account::*, config::*, rest_model::*};
+    /// use rust_decimal_macros::dec;
     /// let account: Account = Binance::new_with_env(&Config::testnet());
     /// let limit_buy = OrderRequest {
-    ///         symbol: "BTCUSDT".to_string(),
-    ///         quantity: Some(10.0),
-    ///         price: Some(0.014000),
-    ///         order_type: OrderType::Limit,
-    ///         side: OrderSide::Buy,
     ///         time_in_force: Some(TimeInForce::FOK),
-    ///         ..OrderRequest::default()
+    ///         ..OrderRequest::limit("BTCUSDT", OrderSide::Buy, dec!(10.0), dec!(0.014000))
     ///     };
     /// let resp = tokio_test::block_on(account.place_test_order(limit_buy));
     /// assert!(resp.is_ok(), "{:?}", resp);
@@ -393,6 +1016,191 @@ impl Account {
         self.client.delete_signed(API_V3_ORDER_TEST, &request).await
     }
 
+    /// Place an order using Smart Order Routing
+    /// This methods validates the order request before sending, making sure it complies with SOR rules
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, account::*, config::*, rest_model::*};
+    /// use rust_decimal_macros::dec;
+    /// let account: Account = Binance::new_with_env(&Config::testnet());
+    /// let limit_buy = SorOrderRequest {
+    ///         symbol: "BTCUSDT".to_string(),
+    ///         quantity: dec!(0.01),
+    ///         price: Some(dec!(60000)),
+    ///         order_type: OrderType::Limit,
+    ///         side: OrderSide::Buy,
+    ///         time_in_force: Some(TimeInForce::GTC),
+    ///         ..SorOrderRequest::default()
+    ///     };
+    /// let transaction = tokio_test::block_on(account.place_sor_order(limit_buy));
+    /// assert!(transaction.is_ok(), "{:?}", transaction);
+    /// ```
+    pub async fn place_sor_order(&self, order: SorOrderRequest) -> Result<SorTransaction> {
+        order.valid()?;
+        let recv_window = order.recv_window.unwrap_or(self.recv_window);
+        let request = build_signed_request_p(order, recv_window)?;
+        self.client.post_signed(API_V3_SOR_ORDER, &request).await
+    }
+
+    /// Place a test order using Smart Order Routing
+    ///
+    /// This order is sandboxed: it is validated, but not sent to the matching engine.
+    pub async fn place_sor_test_order(&self, order: SorOrderRequest) -> Result<TestResponse> {
+        order.valid()?;
+        let recv_window = order.recv_window.unwrap_or(self.recv_window);
+        let request = build_signed_request_p(order, recv_window)?;
+        self.client.post_signed(API_V3_SOR_ORDER_TEST, &request).await
+    }
+
+    /// Allocations resulting from SOR order placement
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, account::*, config::*};
+    /// let account: Account = Binance::new_with_env(&Config::testnet());
+    /// let query = AllocationsQuery {
+    ///     symbol: "BTCUSDT".to_string(),
+    ///     ..AllocationsQuery::default()
+    /// };
+    /// let allocations = tokio_test::block_on(account.get_allocations(query));
+    /// assert!(allocations.is_ok(), "{:?}", allocations);
+    /// ```
+    pub async fn get_allocations(&self, query: AllocationsQuery) -> Result<Vec<Allocation>> {
+        let recv_window = query.recv_window.unwrap_or(self.recv_window);
+        let request = build_signed_request_p(query, recv_window)?;
+        self.client.get_signed(API_V3_MY_ALLOCATIONS, &request).await
+    }
+
+    /// Reduce the quantity of an open order, keeping its priority in the order book
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, account::*, config::*};
+    /// use rust_decimal_macros::dec;
+    /// let account: Account = Binance::new_with_env(&Config::testnet());
+    /// let amend = OrderAmendRequest {
+    ///     symbol: "BTCUSDT".to_string(),
+    ///     order_id: Some(1),
+    ///     new_qty: dec!(0.005),
+    ///     ..OrderAmendRequest::default()
+    /// };
+    /// let amendment = tokio_test::block_on(account.amend_order_keep_priority(amend));
+    /// assert!(amendment.is_ok(), "{:?}", amendment);
+    /// ```
+    pub async fn amend_order_keep_priority(&self, amend: OrderAmendRequest) -> Result<OrderAmendment> {
+        amend.valid()?;
+        let recv_window = amend.recv_window.unwrap_or(self.recv_window);
+        let request = build_signed_request_p(amend, recv_window)?;
+        self.client.put_signed(API_V3_AMEND_KEEP_PRIORITY, &request).await
+    }
+
+    /// Place an OCO order list
+    /// This methods validates the order list before sending, making sure each leg has the parameters its type requires
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, account::*, config::*, rest_model::*};
+    /// use rust_decimal_macros::dec;
+    /// let account: Account = Binance::new_with_env(&Config::testnet());
+    /// let oco = OcoOrderRequest {
+    ///         symbol: "BTCUSDT".to_string(),
+    ///         side: OrderSide::Sell,
+    ///         quantity: dec!(0.01),
+    ///         above_type: OrderType::LimitMaker,
+    ///         above_price: Some(dec!(70000)),
+    ///         below_type: OrderType::StopLoss,
+    ///         below_stop_price: Some(dec!(55000)),
+    ///         ..OcoOrderRequest::default()
+    ///     };
+    /// let order_list = tokio_test::block_on(account.place_oco_order(oco));
+    /// assert!(order_list.is_ok(), "{:?}", order_list);
+    /// ```
+    pub async fn place_oco_order(&self, order: OcoOrderRequest) -> Result<OrderList> {
+        order.valid()?;
+        let recv_window = order.recv_window.unwrap_or(self.recv_window);
+        let request = build_signed_request_p(order, recv_window)?;
+        self.client.post_signed(API_V3_ORDER_LIST_OCO, &request).await
+    }
+
+    /// Place an OTO order list
+    /// This methods validates the order list before sending, making sure each leg has the parameters its type requires
+    pub async fn place_oto_order(&self, order: OtoOrderRequest) -> Result<OrderList> {
+        order.valid()?;
+        let recv_window = order.recv_window.unwrap_or(self.recv_window);
+        let request = build_signed_request_p(order, recv_window)?;
+        self.client.post_signed(API_V3_ORDER_LIST_OTO, &request).await
+    }
+
+    /// Place an OTOCO order list
+    /// This methods validates the order list before sending, making sure each leg has the parameters its type requires
+    pub async fn place_otoco_order(&self, order: OtocoOrderRequest) -> Result<OrderList> {
+        order.valid()?;
+        let recv_window = order.recv_window.unwrap_or(self.recv_window);
+        let request = build_signed_request_p(order, recv_window)?;
+        self.client.post_signed(API_V3_ORDER_LIST_OTOCO, &request).await
+    }
+
+    /// Cancel an entire order list
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, account::*, config::*};
+    /// let account: Account = Binance::new_with_env(&Config::testnet());
+    /// let query = OrderListCancellation {
+    ///     symbol: "BTCUSDT".to_string(),
+    ///     order_list_id: Some(1),
+    ///     ..OrderListCancellation::default()
+    /// };
+    /// let canceled = tokio_test::block_on(account.cancel_order_list(query));
+    /// assert!(canceled.is_ok(), "{:?}", canceled);
+    /// ```
+    pub async fn cancel_order_list(&self, o: OrderListCancellation) -> Result<OrderList> {
+        let recv_window = o.recv_window.unwrap_or(self.recv_window);
+        let request = build_signed_request_p(o, recv_window)?;
+        self.client.delete_signed(API_V3_ORDER_LIST, &request).await
+    }
+
+    /// Check an order list's status
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, account::*, config::*};
+    /// let account: Account = Binance::new_with_env(&Config::testnet());
+    /// let query = OrderListStatusRequest {
+    ///     order_list_id: Some(1),
+    ///     ..OrderListStatusRequest::default()
+    /// };
+    /// let order_list = tokio_test::block_on(account.order_list_status(query));
+    /// assert!(order_list.is_ok(), "{:?}", order_list);
+    /// ```
+    pub async fn order_list_status(&self, query: OrderListStatusRequest) -> Result<OrderList> {
+        let recv_window = query.recv_window.unwrap_or(self.recv_window);
+        let request = build_signed_request_p(query, recv_window)?;
+        self.client.get_signed(API_V3_ORDER_LIST, &request).await
+    }
+
+    /// All order lists for the account
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, account::*, config::*};
+    /// let account: Account = Binance::new_with_env(&Config::testnet());
+    /// let order_lists = tokio_test::block_on(account.get_all_order_lists(OrderListsQuery::default()));
+    /// assert!(order_lists.is_ok(), "{:?}", order_lists);
+    /// ```
+    pub async fn get_all_order_lists(&self, query: OrderListsQuery) -> Result<Vec<OrderList>> {
+        let recv_window = query.recv_window.unwrap_or(self.recv_window);
+        let request = build_signed_request_p(query, recv_window)?;
+        self.client.get_signed(API_V3_ALL_ORDER_LIST, &request).await
+    }
+
+    /// All currently open order lists for the account
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, account::*, config::*};
+    /// let account: Account = Binance::new_with_env(&Config::testnet());
+    /// let order_lists = tokio_test::block_on(account.get_open_order_lists());
+    /// assert!(order_lists.is_ok(), "{:?}", order_lists);
+    /// ```
+    pub async fn get_open_order_lists(&self) -> Result<Vec<OrderList>> {
+        let request = build_signed_request([("", "")], self.recv_window)?;
+        self.client.get_signed(API_V3_OPEN_ORDER_LIST, &request).await
+    }
+
     /// Trade history
     /// # Examples
     /// ```rust,no_run
@@ -409,4 +1217,238 @@ impl Account {
         let request = build_signed_request(parameters, self.recv_window)?;
         self.client.get_signed(API_V3_MYTRADES, &request).await
     }
+
+    /// One page of trades of a symbol
+    pub async fn trades(&self, query: TradesQuery) -> Result<Vec<TradeHistory>> {
+        let recv_window = query.recv_window.unwrap_or(self.recv_window);
+        let request = build_signed_request_p(query, recv_window)?;
+        self.client.get_signed(API_V3_MYTRADES, &request).await
+    }
+
+    /// All the trades of a symbol, requesting pages by trade id from the first trade, whereas
+    /// [`Account::trade_history`] only returns the latest 500
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, account::*, config::*};
+    /// let account: Account = Binance::new_with_env(&Config::testnet());
+    /// let trade_history = tokio_test::block_on(account.trade_history_paginated("BTCUSDT"));
+    /// assert!(trade_history.is_ok(), "{:?}", trade_history);
+    /// ```
+    pub async fn trade_history_paginated<S>(&self, symbol: S) -> Result<Vec<TradeHistory>>
+    where
+        S: AsRef<str>,
+    {
+        paginate_trades(MAX_TRADES_LIMIT, |from_id| {
+            self.trades(TradesQuery {
+                symbol: symbol.as_ref().to_string(),
+                from_id: Some(from_id),
+                limit: Some(MAX_TRADES_LIMIT),
+                ..TradesQuery::default()
+            })
+        })
+        .await
+    }
+
+    /// Orders that expired because of self-trade prevention
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, account::*, config::*};
+    /// let account: Account = Binance::new_with_env(&Config::testnet());
+    /// let query = PreventedMatchesQuery {
+    ///     symbol: "BTCUSDT".to_string(),
+    ///     order_id: Some(1),
+    ///     ..PreventedMatchesQuery::default()
+    /// };
+    /// let prevented_matches = tokio_test::block_on(account.get_prevented_matches(query));
+    /// assert!(prevented_matches.is_ok(), "{:?}", prevented_matches);
+    /// ```
+    pub async fn get_prevented_matches(&self, query: PreventedMatchesQuery) -> Result<Vec<PreventedMatch>> {
+        let recv_window = query.recv_window.unwrap_or(self.recv_window);
+        let request = build_signed_request_p(query, recv_window)?;
+        self.client.get_signed(API_V3_MY_PREVENTED_MATCHES, &request).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn oco() -> OcoOrderRequest {
+        OcoOrderRequest {
+            symbol: "BTCUSDT".to_string(),
+            side: OrderSide::Sell,
+            quantity: dec!(1),
+            above_type: OrderType::LimitMaker,
+            above_price: Some(dec!(21000)),
+            below_type: OrderType::StopLossLimit,
+            below_price: Some(dec!(18900)),
+            below_stop_price: Some(dec!(19000)),
+            below_time_in_force: Some(TimeInForce::GTC),
+            ..OcoOrderRequest::default()
+        }
+    }
+
+    fn oto() -> OtoOrderRequest {
+        OtoOrderRequest {
+            symbol: "BTCUSDT".to_string(),
+            working_type: OrderType::Limit,
+            working_side: OrderSide::Buy,
+            working_price: dec!(20000),
+            working_quantity: dec!(1),
+            working_time_in_force: Some(TimeInForce::GTC),
+            pending_type: OrderType::StopLoss,
+            pending_side: OrderSide::Sell,
+            pending_trailing_delta: Some(100),
+            pending_quantity: dec!(1),
+            ..OtoOrderRequest::default()
+        }
+    }
+
+    fn otoco() -> OtocoOrderRequest {
+        OtocoOrderRequest {
+            symbol: "BTCUSDT".to_string(),
+            working_type: OrderType::LimitMaker,
+            working_side: OrderSide::Buy,
+            working_price: dec!(20000),
+            working_quantity: dec!(1),
+            pending_side: OrderSide::Sell,
+            pending_quantity: dec!(1),
+            pending_above_type: OrderType::LimitMaker,
+            pending_above_price: Some(dec!(21000)),
+            pending_below_type: OrderType::StopLoss,
+            pending_below_stop_price: Some(dec!(19000)),
+            ..OtocoOrderRequest::default()
+        }
+    }
+
+    #[test]
+    fn order_specs_build_valid_orders() {
+        let specs = [
+            (
+                OrderSpec::Limit {
+                    qty: dec!(1),
+                    price: dec!(20000),
+                },
+                OrderType::Limit,
+            ),
+            (
+                OrderSpec::LimitMaker {
+                    qty: dec!(1),
+                    price: dec!(20000),
+                },
+                OrderType::LimitMaker,
+            ),
+            (OrderSpec::Market { qty: dec!(1) }, OrderType::Market),
+            (OrderSpec::MarketQuote { quote_qty: dec!(100) }, OrderType::Market),
+            (
+                OrderSpec::StopLoss {
+                    qty: dec!(1),
+                    stop_price: dec!(19000),
+                },
+                OrderType::StopLoss,
+            ),
+            (
+                OrderSpec::StopLossLimit {
+                    qty: dec!(1),
+                    price: dec!(18900),
+                    stop_price: dec!(19000),
+                },
+                OrderType::StopLossLimit,
+            ),
+            (
+                OrderSpec::TakeProfit {
+                    qty: dec!(1),
+                    stop_price: dec!(21000),
+                },
+                OrderType::TakeProfit,
+            ),
+            (
+                OrderSpec::TakeProfitLimit {
+                    qty: dec!(1),
+                    price: dec!(21100),
+                    stop_price: dec!(21000),
+                },
+                OrderType::TakeProfitLimit,
+            ),
+        ];
+        for (spec, order_type) in specs {
+            let order = OrderRequest::new("BTCUSDT", OrderSide::Sell, spec);
+            assert_eq!(order.order_type, order_type);
+            assert!(order.valid().is_ok(), "{order:?}");
+        }
+        let limit = OrderRequest::limit("BTCUSDT", OrderSide::Buy, dec!(1), dec!(20000));
+        assert_eq!(limit.time_in_force, Some(TimeInForce::GTC));
+        assert_eq!(limit.price, Some(dec!(20000)));
+    }
+
+    #[test]
+    fn oco_order_validation() {
+        assert!(oco().valid().is_ok());
+        let below_limit_maker = OcoOrderRequest {
+            below_type: OrderType::LimitMaker,
+            ..oco()
+        };
+        assert!(matches!(
+            below_limit_maker.valid(),
+            Err(Error::InvalidOrderError { .. })
+        ));
+        let no_time_in_force = OcoOrderRequest {
+            below_time_in_force: None,
+            ..oco()
+        };
+        assert!(no_time_in_force.valid().is_err());
+        let iceberg_ioc = OcoOrderRequest {
+            below_iceberg_qty: Some(dec!(0.1)),
+            below_time_in_force: Some(TimeInForce::IOC),
+            ..oco()
+        };
+        assert!(iceberg_ioc.valid().is_err());
+    }
+
+    #[test]
+    fn oto_order_validation() {
+        assert!(oto().valid().is_ok());
+        let market_working = OtoOrderRequest {
+            working_type: OrderType::Market,
+            ..oto()
+        };
+        assert!(market_working.valid().is_err());
+        let no_time_in_force = OtoOrderRequest {
+            working_time_in_force: None,
+            ..oto()
+        };
+        assert!(no_time_in_force.valid().is_err());
+        let unknown_pending = OtoOrderRequest {
+            pending_type: OrderType::Other,
+            ..oto()
+        };
+        assert!(unknown_pending.valid().is_err());
+        let no_stop = OtoOrderRequest {
+            pending_trailing_delta: None,
+            ..oto()
+        };
+        assert!(no_stop.valid().is_err());
+    }
+
+    #[test]
+    fn otoco_order_validation() {
+        assert!(otoco().valid().is_ok());
+        let below_limit_maker = OtocoOrderRequest {
+            pending_below_type: OrderType::LimitMaker,
+            pending_below_price: Some(dec!(19000)),
+            ..otoco()
+        };
+        assert!(below_limit_maker.valid().is_ok());
+        let no_stop = OtocoOrderRequest {
+            pending_below_stop_price: None,
+            ..otoco()
+        };
+        assert!(no_stop.valid().is_err());
+        let above_market = OtocoOrderRequest {
+            pending_above_type: OrderType::Market,
+            ..otoco()
+        };
+        assert!(above_market.valid().is_err());
+    }
 }
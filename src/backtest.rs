@@ -0,0 +1,591 @@
+//! Backtesting.
+//!
+//! A [`Strategy`] receives the same [`WebsocketEvent`]s as the live websockets and answers with
+//! [`Actions`], the orders to place or cancel. [`Backtest`] replays historical klines, aggregated
+//! trades and recorded events of several symbols in timestamp order and routes the actions to a
+//! [`PaperAccount`], while [`LiveRunner`] routes them to an [`Account`], so that the strategy code
+//! is the same in both modes.
+//!
+//! Klines are replayed as a price path through the open, the low and high in the likely order, and
+//! the close, then delivered as a final kline event at their close time. Market orders placed on a
+//! kline event fill around its close, resting orders can only fill during the following klines.
+//! Around every replayed price the simulated book is reset with a configurable spread and depth, by
+//! default only as deep as the quantity replayed at that price.
+
+use std::collections::{HashMap, VecDeque};
+
+use rust_decimal::prelude::*;
+
+use crate::account::{Account, OrderCancellation, OrderRequest};
+use crate::errors::*;
+use crate::paper::{Fees, PaperAccount};
+use crate::rest_model::{AggTrade, Asks, Balance, Bids, ExecutionType, KlineSummary, OrderBook, OrderSide, Symbol};
+use crate::ws_model::{Kline, KlineEvent, TradeEvent, TradesEvent, WebsocketEvent};
+
+/// An order to place or cancel
+#[derive(Debug, Clone)]
+pub enum Action {
+    Place(OrderRequest),
+    Cancel(OrderCancellation),
+}
+
+/// Actions requested by a strategy while handling an event
+#[derive(Debug, Default)]
+pub struct Actions {
+    actions: Vec<Action>,
+}
+
+impl Actions {
+    pub fn place(&mut self, order: OrderRequest) {
+        self.actions.push(Action::Place(order));
+    }
+
+    pub fn cancel(&mut self, cancellation: OrderCancellation) {
+        self.actions.push(Action::Cancel(cancellation));
+    }
+
+    pub fn is_empty(&self) -> bool { self.actions.is_empty() }
+
+    fn take(&mut self) -> Vec<Action> { std::mem::take(&mut self.actions) }
+}
+
+/// Trading logic driven by market and user stream events
+pub trait Strategy {
+    /// Called for every market event and for the account events caused by the strategy's orders
+    fn on_event(&mut self, event: &WebsocketEvent, actions: &mut Actions);
+
+    /// Called when an action is rejected
+    fn on_rejected(&mut self, _action: &Action, _error: &Error) {}
+}
+
+/// A fill of one of the strategy's orders
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TradeRecord {
+    pub time: u64,
+    pub symbol: String,
+    pub order_id: u64,
+    pub side: OrderSide,
+    pub price: Decimal,
+    pub qty: Decimal,
+    pub commission: Decimal,
+    pub commission_asset: Option<String>,
+    pub is_maker: bool,
+}
+
+/// Value of the account at a point in time, in the valuation asset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EquityPoint {
+    pub time: u64,
+    pub equity: Decimal,
+}
+
+/// Outcome of a backtest
+#[derive(Debug, Clone, Default)]
+pub struct BacktestReport {
+    pub equity_curve: Vec<EquityPoint>,
+    pub trades: Vec<TradeRecord>,
+    pub final_balances: Vec<Balance>,
+    /// Number of actions rejected by the simulated exchange
+    pub rejected: usize,
+}
+
+impl BacktestReport {
+    /// Relative change of the equity from the first to the last point
+    pub fn total_return(&self) -> Option<Decimal> {
+        let first = self.equity_curve.first()?.equity;
+        let last = self.equity_curve.last()?.equity;
+        (!first.is_zero()).then(|| last / first - Decimal::ONE)
+    }
+
+    /// Largest relative fall of the equity from a previous peak
+    pub fn max_drawdown(&self) -> Decimal {
+        let mut peak = Decimal::ZERO;
+        let mut drawdown = Decimal::ZERO;
+        for point in &self.equity_curve {
+            peak = peak.max(point.equity);
+            if !peak.is_zero() {
+                drawdown = drawdown.max((peak - point.equity) / peak);
+            }
+        }
+        drawdown
+    }
+}
+
+enum ReplayItem {
+    /// A price the market went through, which the strategy does not see
+    Price {
+        symbol: String,
+        time: u64,
+        price: Decimal,
+        qty: Decimal,
+    },
+    Event {
+        time: u64,
+        event: WebsocketEvent,
+    },
+}
+
+impl ReplayItem {
+    fn time(&self) -> u64 {
+        match self {
+            ReplayItem::Price { time, .. } | ReplayItem::Event { time, .. } => *time,
+        }
+    }
+}
+
+/// Deterministic replay of historical data through a strategy
+pub struct Backtest {
+    paper: PaperAccount,
+    symbols: HashMap<String, Symbol>,
+    valuation_asset: String,
+    spread: Decimal,
+    book_qty: Option<Decimal>,
+    equity_interval: u64,
+    items: Vec<ReplayItem>,
+}
+
+impl Backtest {
+    /// A backtest trading the symbols, valuing the account in the valuation asset, such as USDT
+    pub fn new<S: Into<String>>(symbols: Vec<Symbol>, valuation_asset: S) -> Self {
+        Self {
+            paper: PaperAccount::new(symbols.clone()),
+            symbols: symbols.into_iter().map(|s| (s.symbol.clone(), s)).collect(),
+            valuation_asset: valuation_asset.into(),
+            spread: Decimal::ZERO,
+            book_qty: None,
+            equity_interval: 0,
+            items: vec![],
+        }
+    }
+
+    pub fn with_fees(mut self, fees: Fees) -> Self {
+        self.paper = self.paper.with_fees(fees);
+        self
+    }
+
+    /// Credits an asset to the simulated account
+    pub fn deposit<S: Into<String>>(self, asset: S, amount: Decimal) -> Self {
+        self.paper.deposit(asset, amount);
+        self
+    }
+
+    /// Sets the relative spread of the simulated book, 0.001 puts the best bid and ask 0.05% away
+    /// from the replayed price
+    pub fn set_spread(mut self, spread: Decimal) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    /// Sets the quantity of the best bid and ask of the simulated book. By default it is the quantity
+    /// replayed at each price, the trade quantity or a quarter of the kline volume
+    pub fn set_book_qty(mut self, book_qty: Decimal) -> Self {
+        self.book_qty = Some(book_qty);
+        self
+    }
+
+    /// Sets the minimum time between two points of the equity curve in milliseconds, by default
+    /// every replayed timestamp is a point
+    pub fn set_equity_interval(mut self, equity_interval: u64) -> Self {
+        self.equity_interval = equity_interval;
+        self
+    }
+
+    /// Adds the klines of a symbol, such as from `Market::get_klines`
+    pub fn add_klines(&mut self, symbol: &str, interval: &str, klines: &[KlineSummary]) {
+        for k in klines {
+            let open_time = k.open_time.max(0) as u64;
+            let close_time = k.close_time.max(0) as u64;
+            let step = close_time.saturating_sub(open_time) / 3;
+            let (first, second) = if k.close >= k.open {
+                (k.low, k.high)
+            } else {
+                (k.high, k.low)
+            };
+            let qty = k.volume / Decimal::from(4);
+            for (time, price) in [
+                (open_time, k.open),
+                (open_time + step, first),
+                (open_time + 2 * step, second),
+                (close_time, k.close),
+            ] {
+                self.items.push(ReplayItem::Price {
+                    symbol: symbol.to_string(),
+                    time,
+                    price,
+                    qty,
+                });
+            }
+            self.items.push(ReplayItem::Event {
+                time: close_time,
+                event: WebsocketEvent::Kline(Box::new(KlineEvent {
+                    event_time: close_time,
+                    symbol: symbol.to_string(),
+                    kline: Kline {
+                        start_time: k.open_time,
+                        end_time: k.close_time,
+                        symbol: symbol.to_string(),
+                        interval: interval.to_string(),
+                        first_trade_id: -1,
+                        last_trade_id: -1,
+                        open: k.open,
+                        close: k.close,
+                        high: k.high,
+                        low: k.low,
+                        volume: k.volume,
+                        number_of_trades: k.number_of_trades,
+                        is_final_bar: true,
+                        quote_volume: k.quote_asset_volume,
+                        active_buy_volume: k.taker_buy_base_asset_volume,
+                        active_volume_buy_quote: k.taker_buy_quote_asset_volume,
+                        ignore_me: String::new(),
+                    },
+                })),
+            });
+        }
+    }
+
+    /// Adds the aggregated trades of a symbol, such as from `Market::get_agg_trades`
+    pub fn add_agg_trades(&mut self, symbol: &str, trades: &[AggTrade]) {
+        for t in trades {
+            self.items.push(ReplayItem::Event {
+                time: t.time,
+                event: WebsocketEvent::AggTrade(Box::new(TradesEvent {
+                    event_time: t.time,
+                    symbol: symbol.to_string(),
+                    aggregated_trade_id: t.agg_id,
+                    price: t.price.to_string(),
+                    qty: t.qty.to_string(),
+                    first_break_trade_id: t.first_id,
+                    last_break_trade_id: t.last_id,
+                    trade_order_time: t.time,
+                    is_buyer_maker: t.maker,
+                    m_ignore: t.best_match,
+                })),
+            });
+        }
+    }
+
+    /// Adds a recorded event, trades and depth updates also move the simulated market
+    pub fn add_event(&mut self, time: u64, event: WebsocketEvent) {
+        self.items.push(ReplayItem::Event { time, event });
+    }
+
+    /// Replays the data through the strategy
+    pub async fn run<S: Strategy>(mut self, strategy: &mut S) -> Result<BacktestReport> {
+        let mut items = std::mem::take(&mut self.items);
+        items.sort_by_key(ReplayItem::time);
+        let mut report = BacktestReport::default();
+        let mut prices: HashMap<String, Decimal> = HashMap::new();
+        self.deliver(strategy, VecDeque::new(), &mut report).await?;
+        for item in items {
+            let time = item.time();
+            match item {
+                ReplayItem::Price {
+                    symbol,
+                    time,
+                    price,
+                    qty,
+                } => {
+                    self.trade(&symbol, time, price, qty)?;
+                    prices.insert(symbol, price);
+                    self.deliver(strategy, VecDeque::new(), &mut report).await?;
+                }
+                ReplayItem::Event { event, .. } => {
+                    match &event {
+                        WebsocketEvent::AggTrade(t) => {
+                            let price = parse_decimal(&t.price)?;
+                            self.trade(&t.symbol, t.trade_order_time, price, parse_decimal(&t.qty)?)?;
+                            prices.insert(t.symbol.clone(), price);
+                        }
+                        WebsocketEvent::Trade(t) => {
+                            let price = parse_decimal(&t.price)?;
+                            self.trade(&t.symbol, t.trade_order_time, price, parse_decimal(&t.qty)?)?;
+                            prices.insert(t.symbol.clone(), price);
+                        }
+                        WebsocketEvent::DepthOrderBook(depth) => self.paper.apply_depth(depth),
+                        WebsocketEvent::Kline(k) => {
+                            prices.insert(k.symbol.clone(), k.kline.close);
+                        }
+                        _ => {}
+                    }
+                    self.deliver(strategy, VecDeque::from([event]), &mut report).await?;
+                }
+            }
+            match report.equity_curve.last() {
+                Some(last) if last.time != time && time < last.time + self.equity_interval => {}
+                _ => {
+                    let point = EquityPoint {
+                        time,
+                        equity: self.equity(&prices).await?,
+                    };
+                    match report.equity_curve.last_mut() {
+                        Some(last) if last.time == time => *last = point,
+                        _ => report.equity_curve.push(point),
+                    }
+                }
+            }
+        }
+        report.final_balances = self.paper.get_account().await?.balances;
+        Ok(report)
+    }
+
+    /// Resets the simulated book around the price and replays a trade at that price, the book is
+    /// empty when nothing was traded and no depth is configured
+    fn trade(&self, symbol: &str, time: u64, price: Decimal, qty: Decimal) -> Result<()> {
+        let half_spread = price * self.spread / Decimal::TWO;
+        let book_qty = self.book_qty.unwrap_or(qty);
+        let (bids, asks) = if book_qty.is_zero() {
+            (vec![], vec![])
+        } else {
+            (
+                vec![Bids {
+                    price: price - half_spread,
+                    qty: book_qty,
+                }],
+                vec![Asks {
+                    price: price + half_spread,
+                    qty: book_qty,
+                }],
+            )
+        };
+        self.paper.apply_order_book(
+            symbol,
+            &OrderBook {
+                last_update_id: 0,
+                bids,
+                asks,
+            },
+        );
+        self.paper.apply_trade(&TradeEvent {
+            event_time: time,
+            symbol: symbol.to_string(),
+            trade_id: 0,
+            price: price.to_string(),
+            qty: qty.to_string(),
+            buyer_order_id: 0,
+            seller_order_id: 0,
+            trade_order_time: time,
+            is_buyer_maker: false,
+            m_ignore: false,
+        })
+    }
+
+    /// Passes the events and the account events they cause to the strategy, executing its actions
+    async fn deliver<S: Strategy>(
+        &self,
+        strategy: &mut S,
+        mut pending: VecDeque<WebsocketEvent>,
+        report: &mut BacktestReport,
+    ) -> Result<()> {
+        pending.extend(self.paper.take_events());
+        while let Some(event) = pending.pop_front() {
+            if let WebsocketEvent::OrderUpdate(update) = &event {
+                if let ExecutionType::Trade = update.execution_type {
+                    report.trades.push(TradeRecord {
+                        time: update.event_time,
+                        symbol: update.symbol.clone(),
+                        order_id: update.order_id,
+                        side: update.side.clone(),
+                        price: update.last_executed_price,
+                        qty: update.qty_last_executed,
+                        commission: update.commission,
+                        commission_asset: update.commission_asset.clone(),
+                        is_maker: update.is_buyer_maker,
+                    });
+                }
+            }
+            let mut actions = Actions::default();
+            strategy.on_event(&event, &mut actions);
+            for action in actions.take() {
+                let result = match &action {
+                    Action::Place(order) => self.paper.place_order(order.clone()).await.map(|_| ()),
+                    Action::Cancel(cancellation) => self.paper.cancel_order(cancellation.clone()).await.map(|_| ()),
+                };
+                if let Err(e) = result {
+                    report.rejected += 1;
+                    strategy.on_rejected(&action, &e);
+                }
+            }
+            pending.extend(self.paper.take_events());
+        }
+        Ok(())
+    }
+
+    /// Value of the account in the valuation asset, assets without a price are not counted
+    async fn equity(&self, prices: &HashMap<String, Decimal>) -> Result<Decimal> {
+        let mut equity = Decimal::ZERO;
+        for balance in self.paper.get_account().await?.balances {
+            let amount = balance.free + balance.locked;
+            if balance.asset == self.valuation_asset {
+                equity += amount;
+            } else if let Some(price) = self.price(&balance.asset, &self.valuation_asset, prices) {
+                equity += amount * price;
+            } else if let Some(price) = self.price(&self.valuation_asset, &balance.asset, prices) {
+                if !price.is_zero() {
+                    equity += amount / price;
+                }
+            }
+        }
+        Ok(equity)
+    }
+
+    fn price(&self, base_asset: &str, quote_asset: &str, prices: &HashMap<String, Decimal>) -> Option<Decimal> {
+        self.symbols
+            .values()
+            .find(|s| s.base_asset == base_asset && s.quote_asset == quote_asset)
+            .and_then(|s| prices.get(&s.symbol).copied())
+    }
+}
+
+/// Runs a strategy against the exchange
+pub struct LiveRunner {
+    account: Account,
+}
+
+impl LiveRunner {
+    pub fn new(account: Account) -> Self { Self { account } }
+
+    /// Passes an event, such as from `WebSockets`, to the strategy and sends the orders it asks
+    /// for, reporting the rejected ones to the strategy
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, account::*, backtest::*, config::*, ws_model::*};
+    /// struct Noop;
+    /// impl Strategy for Noop {
+    ///     fn on_event(&mut self, _event: &WebsocketEvent, _actions: &mut Actions) {}
+    /// }
+    /// let account: Account = Binance::new_with_env(&Config::testnet());
+    /// let runner = LiveRunner::new(account);
+    /// let event: WebsocketEvent = serde_json::from_str(r#"{"e":"trade","E":1,"s":"BTCUSDT","t":1,"p":"1","q":"1","b":1,"a":2,"T":1,"m":true,"M":true}"#).unwrap();
+    /// tokio_test::block_on(runner.on_event(&mut Noop, &event));
+    /// ```
+    pub async fn on_event<S: Strategy>(&self, strategy: &mut S, event: &WebsocketEvent) {
+        let mut actions = Actions::default();
+        strategy.on_event(event, &mut actions);
+        for action in actions.take() {
+            let result = match &action {
+                Action::Place(order) => self.account.place_order(order.clone()).await.map(|_| ()),
+                Action::Cancel(cancellation) => self.account.cancel_order(cancellation.clone()).await.map(|_| ()),
+            };
+            if let Err(e) = result {
+                strategy.on_rejected(&action, &e);
+            }
+        }
+    }
+}
+
+fn parse_decimal(value: &str) -> Result<Decimal> {
+    Decimal::from_str(value).map_err(|e| Error::Msg(format!("invalid decimal {value}: {e}")))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    const SYMBOL: &str = "BTCUSDT";
+
+    fn backtest() -> Backtest {
+        let symbol: Symbol = serde_json::from_str(
+            r#"{
+                "symbol": "BTCUSDT", "status": "TRADING", "baseAsset": "BTC", "baseAssetPrecision": 8,
+                "quoteAsset": "USDT", "quotePrecision": 8, "quoteAssetPrecision": 8,
+                "baseCommissionPrecision": 8, "quoteCommissionPrecision": 8, "orderTypes": ["LIMIT", "MARKET"],
+                "icebergAllowed": true, "ocoAllowed": true, "quoteOrderQtyMarketAllowed": true,
+                "isSpotTradingAllowed": true, "isMarginTradingAllowed": false, "filters": [],
+                "permissions": ["SPOT"], "defaultSelfTradePreventionMode": "NONE"
+            }"#,
+        )
+        .unwrap();
+        Backtest::new(vec![symbol], "USDT")
+            .with_fees(Fees {
+                maker: dec!(0),
+                taker: dec!(0),
+            })
+            .deposit("USDT", dec!(1000))
+    }
+
+    fn kline(open_time: i64, open: Decimal, high: Decimal, low: Decimal, close: Decimal) -> KlineSummary {
+        KlineSummary {
+            open_time,
+            open,
+            high,
+            low,
+            close,
+            volume: dec!(40),
+            close_time: open_time + 2999,
+            quote_asset_volume: dec!(4000),
+            number_of_trades: 10,
+            taker_buy_base_asset_volume: dec!(20),
+            taker_buy_quote_asset_volume: dec!(2000),
+        }
+    }
+
+    /// Buys with a market order on the first kline
+    struct Buyer {
+        qty: Decimal,
+        placed: bool,
+    }
+
+    impl Strategy for Buyer {
+        fn on_event(&mut self, event: &WebsocketEvent, actions: &mut Actions) {
+            if let (WebsocketEvent::Kline(_), false) = (event, self.placed) {
+                self.placed = true;
+                actions.place(OrderRequest::market(SYMBOL, OrderSide::Buy, self.qty));
+            }
+        }
+    }
+
+    fn run(mut backtest: Backtest, qty: Decimal) -> BacktestReport {
+        backtest.add_klines(
+            SYMBOL,
+            "1s",
+            &[
+                kline(0, dec!(100), dec!(110), dec!(90), dec!(100)),
+                kline(3000, dec!(100), dec!(120), dec!(80), dec!(120)),
+            ],
+        );
+        tokio_test::block_on(backtest.run(&mut Buyer { qty, placed: false })).unwrap()
+    }
+
+    #[test]
+    fn book_depth_follows_the_replayed_volume() {
+        // A quarter of the kline volume is available at its close
+        let report = run(backtest().deposit("USDT", dec!(1000)), dec!(15));
+        assert_eq!(report.trades.len(), 1);
+        assert_eq!(report.trades[0].qty, dec!(10));
+        assert_eq!(report.trades[0].price, dec!(100));
+
+        let report = run(backtest().deposit("USDT", dec!(1000)).set_book_qty(dec!(100)), dec!(15));
+        assert_eq!(report.trades.len(), 1);
+        assert_eq!(report.trades[0].qty, dec!(15));
+        let btc = report.final_balances.iter().find(|b| b.asset == "BTC").unwrap();
+        assert_eq!(btc.free, dec!(15));
+    }
+
+    #[test]
+    fn equity_curve_values_the_account_at_the_replayed_prices() {
+        let report = run(backtest(), dec!(1));
+        let curve: Vec<(u64, Decimal)> = report.equity_curve.iter().map(|p| (p.time, p.equity)).collect();
+        assert_eq!(
+            curve,
+            vec![
+                (0, dec!(1000)),
+                (999, dec!(1000)),
+                (1998, dec!(1000)),
+                (2999, dec!(1000)),
+                (3000, dec!(1000)),
+                (3999, dec!(980)),
+                (4998, dec!(1020)),
+                (5999, dec!(1020)),
+            ]
+        );
+        assert_eq!(report.total_return(), Some(dec!(0.02)));
+        assert_eq!(report.max_drawdown(), dec!(0.02));
+        assert_eq!(report.rejected, 0);
+
+        let report = run(backtest().set_equity_interval(3000), dec!(1));
+        let times: Vec<u64> = report.equity_curve.iter().map(|p| p.time).collect();
+        assert_eq!(times, vec![0, 3000]);
+    }
+}
@@ -0,0 +1,303 @@
+//! Live spot balances.
+//!
+//! [`BalanceBook`] is seeded from [`Account::get_account`] and kept current with the
+//! `outboundAccountPosition` and `balanceUpdate` events of the user stream, so that strategies can
+//! check what they can trade without querying the account on every decision.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use rust_decimal::Decimal;
+
+use crate::account::Account;
+use crate::errors::*;
+use crate::rest_model::{AccountInformation, OrderSide};
+use crate::util::get_timestamp;
+use crate::ws_model::{AccountPositionUpdate, BalanceUpdate, WebsocketEvent};
+
+/// Balance of a single asset
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AssetBalance {
+    pub free: Decimal,
+    pub locked: Decimal,
+    /// Time of the account change this balance reflects, in milliseconds
+    pub update_time: u64,
+}
+
+impl AssetBalance {
+    pub fn total(&self) -> Decimal { self.free + self.locked }
+}
+
+/// Free and locked balances per asset
+#[derive(Debug, Clone)]
+pub struct BalanceBook {
+    balances: HashMap<String, AssetBalance>,
+    /// Event time and delta of the balance updates applied at the current update time of an asset,
+    /// to tell a repeated event from another one cleared at the same time
+    cleared_updates: HashMap<String, Vec<(u64, Decimal)>>,
+    reserved: HashMap<String, Decimal>,
+    resync_interval: Duration,
+    last_sync: u64,
+}
+
+impl Default for BalanceBook {
+    fn default() -> Self {
+        Self {
+            balances: HashMap::new(),
+            cleared_updates: HashMap::new(),
+            reserved: HashMap::new(),
+            resync_interval: Duration::from_secs(300),
+            last_sync: 0,
+        }
+    }
+}
+
+impl BalanceBook {
+    /// Creates a book from the account balances
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, account::*, balance_book::*, config::*};
+    /// let account: Account = Binance::new_with_env(&Config::testnet());
+    /// let book = tokio_test::block_on(BalanceBook::from_account(&account));
+    /// assert!(book.is_ok(), "{:?}", book);
+    /// ```
+    pub async fn from_account(account: &Account) -> Result<Self> {
+        let mut book = Self::default();
+        book.resync(account).await?;
+        Ok(book)
+    }
+
+    /// Sets how often [`BalanceBook::resync_if_due`] refreshes the book from the REST API
+    pub fn set_resync_interval(mut self, resync_interval: Duration) -> Self {
+        self.resync_interval = resync_interval;
+        self
+    }
+
+    /// Replaces the balances which did not change since the snapshot was taken, and removes the
+    /// assets absent from the snapshot
+    pub fn apply_snapshot(&mut self, information: &AccountInformation) {
+        let update_time = information.update_time.max(0) as u64;
+        for balance in &information.balances {
+            self.set_balance(&balance.asset, balance.free, balance.locked, update_time);
+        }
+        let absent: Vec<String> = self
+            .balances
+            .iter()
+            .filter(|(asset, balance)| {
+                balance.update_time <= update_time && !information.balances.iter().any(|b| &b.asset == *asset)
+            })
+            .map(|(asset, _)| asset.clone())
+            .collect();
+        for asset in absent {
+            self.balances.remove(&asset);
+            self.cleared_updates.remove(&asset);
+        }
+    }
+
+    /// Applies an `outboundAccountPosition` event, events older than the known balances are ignored
+    pub fn apply_position_update(&mut self, update: &AccountPositionUpdate) {
+        for balance in &update.balances {
+            self.set_balance(&balance.asset, balance.free, balance.locked, update.last_update_time);
+        }
+    }
+
+    /// Applies a `balanceUpdate` event, such as a deposit, withdrawal or transfer, events received
+    /// twice are ignored
+    pub fn apply_balance_update(&mut self, update: &BalanceUpdate) {
+        let balance = self.balances.entry(update.asset.clone()).or_default();
+        let cleared = self.cleared_updates.entry(update.asset.clone()).or_default();
+        let identity = (update.event_time, update.delta);
+        // A position update or snapshot at or after the clear time already includes the delta, while
+        // other balance updates cleared at the same time do not
+        let is_new = if update.clear_time > balance.update_time {
+            cleared.clear();
+            true
+        } else {
+            update.clear_time == balance.update_time && !cleared.is_empty() && !cleared.contains(&identity)
+        };
+        if is_new {
+            balance.free += update.delta;
+            balance.update_time = update.clear_time;
+            cleared.push(identity);
+        }
+    }
+
+    /// Applies the user stream events which change balances, ignoring the others
+    pub fn apply_event(&mut self, event: &WebsocketEvent) {
+        match event {
+            WebsocketEvent::AccountPositionUpdate(update) => self.apply_position_update(update),
+            WebsocketEvent::BalanceUpdate(update) => self.apply_balance_update(update),
+            _ => {}
+        }
+    }
+
+    /// Refreshes the book from the REST API
+    pub async fn resync(&mut self, account: &Account) -> Result<()> {
+        let information = account.get_account().await?;
+        self.apply_snapshot(&information);
+        self.last_sync = get_timestamp()?;
+        Ok(())
+    }
+
+    /// Refreshes the book from the REST API if the resync interval elapsed, returns whether it did
+    pub async fn resync_if_due(&mut self, account: &Account) -> Result<bool> {
+        if get_timestamp()?.saturating_sub(self.last_sync) < self.resync_interval.as_millis() as u64 {
+            return Ok(false);
+        }
+        self.resync(account).await?;
+        Ok(true)
+    }
+
+    pub fn balance(&self, asset: &str) -> Option<&AssetBalance> { self.balances.get(asset) }
+
+    pub fn balances(&self) -> &HashMap<String, AssetBalance> { &self.balances }
+
+    pub fn free(&self, asset: &str) -> Decimal { self.balances.get(asset).map(|b| b.free).unwrap_or_default() }
+
+    pub fn locked(&self, asset: &str) -> Decimal { self.balances.get(asset).map(|b| b.locked).unwrap_or_default() }
+
+    /// Sets aside an amount of an asset, such as for an order which is being placed and not yet
+    /// reflected in the balances
+    pub fn reserve(&mut self, asset: &str, amount: Decimal) {
+        *self.reserved.entry(asset.to_string()).or_default() += amount;
+    }
+
+    /// Releases an amount previously reserved
+    pub fn release(&mut self, asset: &str, amount: Decimal) {
+        if let Some(reserved) = self.reserved.get_mut(asset) {
+            *reserved = (*reserved - amount).max(Decimal::ZERO);
+            if reserved.is_zero() {
+                self.reserved.remove(asset);
+            }
+        }
+    }
+
+    /// Free amount of an asset which is not reserved
+    pub fn available(&self, asset: &str) -> Decimal {
+        let reserved = self.reserved.get(asset).copied().unwrap_or_default();
+        (self.free(asset) - reserved).max(Decimal::ZERO)
+    }
+
+    /// Amount available for an order on a pair, in quote asset when buying and in base asset when
+    /// selling
+    pub fn available_to_trade(&self, side: OrderSide, base_asset: &str, quote_asset: &str) -> Decimal {
+        match side {
+            OrderSide::Buy => self.available(quote_asset),
+            OrderSide::Sell => self.available(base_asset),
+        }
+    }
+
+    /// Whether an order for this quantity at this price can be funded
+    pub fn can_afford(
+        &self,
+        side: OrderSide,
+        base_asset: &str,
+        quote_asset: &str,
+        qty: Decimal,
+        price: Decimal,
+    ) -> bool {
+        let required = match side {
+            OrderSide::Buy => qty * price,
+            OrderSide::Sell => qty,
+        };
+        self.available_to_trade(side, base_asset, quote_asset) >= required
+    }
+
+    fn set_balance(&mut self, asset: &str, free: Decimal, locked: Decimal, update_time: u64) {
+        let balance = self.balances.entry(asset.to_string()).or_default();
+        if update_time >= balance.update_time {
+            self.cleared_updates.remove(asset);
+            *balance = AssetBalance {
+                free,
+                locked,
+                update_time,
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ws_model::EventBalance;
+    use rust_decimal_macros::dec;
+
+    fn snapshot(update_time: i64, balances: &[(&str, &str)]) -> AccountInformation {
+        let balances: Vec<String> = balances
+            .iter()
+            .map(|(asset, free)| format!(r#"{{"asset":"{asset}","free":"{free}","locked":"0"}}"#))
+            .collect();
+        serde_json::from_str(&format!(
+            r#"{{"makerCommission":10,"takerCommission":10,"buyerCommission":0,"sellerCommission":0,
+            "canTrade":true,"canWithdraw":true,"canDeposit":true,"accountType":"SPOT",
+            "balances":[{}],"permissions":["SPOT"],"updateTime":{update_time}}}"#,
+            balances.join(",")
+        ))
+        .unwrap()
+    }
+
+    fn balance_update(event_time: u64, delta: Decimal, clear_time: u64) -> BalanceUpdate {
+        BalanceUpdate {
+            event_time,
+            asset: "USDT".to_string(),
+            delta,
+            clear_time,
+        }
+    }
+
+    #[test]
+    fn balance_updates_cleared_at_the_same_time() {
+        let mut book = BalanceBook::default();
+        book.apply_snapshot(&snapshot(1000, &[("USDT", "100")]));
+        // Already included in the snapshot
+        book.apply_balance_update(&balance_update(1001, dec!(50), 1000));
+        assert_eq!(book.free("USDT"), dec!(100));
+
+        book.apply_balance_update(&balance_update(1011, dec!(20), 1010));
+        book.apply_balance_update(&balance_update(1012, dec!(5), 1010));
+        // Received twice
+        book.apply_balance_update(&balance_update(1012, dec!(5), 1010));
+        assert_eq!(book.free("USDT"), dec!(125));
+
+        book.apply_position_update(&AccountPositionUpdate {
+            event_time: 1021,
+            last_update_time: 1020,
+            balances: vec![EventBalance {
+                asset: "USDT".to_string(),
+                free: dec!(120),
+                locked: dec!(5),
+            }],
+        });
+        book.apply_balance_update(&balance_update(1022, dec!(1), 1020));
+        assert_eq!(book.free("USDT"), dec!(120));
+        assert_eq!(book.balance("USDT").unwrap().total(), dec!(125));
+    }
+
+    #[test]
+    fn snapshot_removes_absent_assets() {
+        let mut book = BalanceBook::default();
+        book.apply_snapshot(&snapshot(1000, &[("USDT", "100"), ("BTC", "1")]));
+        book.apply_balance_update(&BalanceUpdate {
+            asset: "ETH".to_string(),
+            ..balance_update(1031, dec!(2), 1030)
+        });
+        book.apply_snapshot(&snapshot(1020, &[("USDT", "90")]));
+        assert!(book.balance("BTC").is_none());
+        // Changed after the snapshot was taken
+        assert_eq!(book.free("ETH"), dec!(2));
+        assert_eq!(book.free("USDT"), dec!(90));
+    }
+
+    #[test]
+    fn reservations_reduce_the_available_balance() {
+        let mut book = BalanceBook::default();
+        book.apply_snapshot(&snapshot(1000, &[("USDT", "100"), ("BTC", "1")]));
+        book.reserve("USDT", dec!(60));
+        assert_eq!(book.available("USDT"), dec!(40));
+        assert!(book.can_afford(OrderSide::Buy, "BTC", "USDT", dec!(0.002), dec!(20000)));
+        assert!(!book.can_afford(OrderSide::Buy, "BTC", "USDT", dec!(0.003), dec!(20000)));
+        assert_eq!(book.available_to_trade(OrderSide::Sell, "BTC", "USDT"), dec!(1));
+        book.release("USDT", dec!(100));
+        assert_eq!(book.available("USDT"), dec!(100));
+    }
+}
@@ -0,0 +1,92 @@
+//! Encoding of the batch order endpoints shared by the futures and options APIs.
+
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+use crate::errors::*;
+
+/// Encodes between 1 and `max_orders` orders as the JSON array of a batch parameter, in which every
+/// value is a string
+pub(crate) fn encode_orders<T: serde::Serialize>(orders: &[T], max_orders: usize) -> Result<String> {
+    if orders.is_empty() || orders.len() > max_orders {
+        return Err(Error::InvalidOrderError {
+            msg: format!("a batch must have between 1 and {max_orders} orders"),
+        });
+    }
+    let orders = orders
+        .iter()
+        .map(|order| {
+            let Value::Object(fields) = serde_json::to_value(order)? else {
+                return Err(Error::Msg("batch orders must serialize to objects".to_string()));
+            };
+            Ok(fields
+                .into_iter()
+                .filter_map(|(key, value)| match value {
+                    Value::Null => None,
+                    Value::String(s) => Some((key, Value::String(s))),
+                    value => Some((key, Value::String(value.to_string()))),
+                })
+                .collect::<Map<String, Value>>())
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(serde_json::to_string(&orders)?)
+}
+
+/// Splits the entries of a batch response into orders and errors
+pub(crate) fn batch_results<T: DeserializeOwned>(
+    entries: Vec<Value>,
+) -> Result<Vec<std::result::Result<T, BinanceContentError>>> {
+    entries
+        .into_iter()
+        .map(|entry| {
+            if entry.get("code").is_some() {
+                Ok(Err(serde_json::from_value(entry)?))
+            } else {
+                Ok(Ok(serde_json::from_value(entry)?))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct TestOrder {
+        symbol: String,
+        quantity: rust_decimal::Decimal,
+        reduce_only: Option<bool>,
+        price: Option<u64>,
+    }
+
+    #[test]
+    fn encodes_every_value_as_a_string() {
+        let order = TestOrder {
+            symbol: "BTCUSDT".to_string(),
+            quantity: rust_decimal_macros::dec!(0.01),
+            reduce_only: Some(true),
+            price: None,
+        };
+        assert_eq!(
+            encode_orders(&[order], 5).unwrap(),
+            r#"[{"quantity":"0.01","reduceOnly":"true","symbol":"BTCUSDT"}]"#
+        );
+        assert!(matches!(
+            encode_orders::<TestOrder>(&[], 5),
+            Err(Error::InvalidOrderError { .. })
+        ));
+        assert!(matches!(encode_orders(&[1, 2], 5), Err(Error::Msg(_))));
+    }
+
+    #[test]
+    fn splits_orders_and_errors() {
+        let entries: Vec<Value> =
+            serde_json::from_str(r#"[{"symbol": "BTCUSDT"}, {"code": -2022, "msg": "ReduceOnly Order is rejected."}]"#)
+                .unwrap();
+        let results: Vec<std::result::Result<Value, BinanceContentError>> = batch_results(entries).unwrap();
+        assert_eq!(results[0].as_ref().unwrap()["symbol"], "BTCUSDT");
+        assert_eq!(results[1].as_ref().unwrap_err().code, -2022);
+    }
+}
@@ -81,6 +81,23 @@ impl Client {
         self.post_signed(endpoint, &request).await
     }
 
+    pub async fn put_signed<T: DeserializeOwned>(&self, endpoint: &str, request: &str) -> Result<T> {
+        let url = self.sign_request(endpoint, request);
+        let response = self.inner.put(&url).headers(self.build_headers(true)?).send().await?;
+
+        self.handler(response).await
+    }
+
+    pub async fn put_signed_p<T: de::DeserializeOwned, P: serde::Serialize>(
+        &self,
+        endpoint: &str,
+        payload: P,
+        recv_window: u64,
+    ) -> Result<T> {
+        let request = build_signed_request_p(payload, recv_window)?;
+        self.put_signed(endpoint, &request).await
+    }
+
     pub async fn delete_signed_p<T: de::DeserializeOwned, P: serde::Serialize>(
         &self,
         endpoint: &str,
@@ -0,0 +1,156 @@
+use std::collections::BTreeMap;
+
+use super::rest_model::{
+    AccountBalance, ChangeLeverageResponse, MarginType, Order, Position, PositionMode, StatusResponse,
+};
+use crate::account::OrderCancellation;
+use crate::client::Client;
+use crate::errors::*;
+use crate::futures::account::{ChangeMarginTypeRequest, ChangePositionModeRequest, GetOrderRequest, OrderRequest};
+use crate::rest_model::PairQuery;
+use crate::util::*;
+
+#[derive(Clone)]
+pub struct CoinFuturesAccount {
+    pub client: Client,
+    pub recv_window: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PositionQuery {
+    pub pair: Option<String>,
+}
+
+impl CoinFuturesAccount {
+    /// Get an order
+    pub async fn get_order(&self, order: GetOrderRequest) -> Result<Order> {
+        self.client
+            .get_signed_p("/dapi/v1/order", Some(order), self.recv_window)
+            .await
+    }
+
+    /// Place an order, the quantity is in contracts
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, config::*, coin_futures::account::*, futures::account::OrderRequest, rest_model::OrderSide};
+    /// use rust_decimal_macros::dec;
+    /// let account: CoinFuturesAccount = Binance::new_with_env(&Config::testnet());
+    /// let order = OrderRequest::limit("BTCUSD_PERP", OrderSide::Buy, dec!(1), dec!(20000));
+    /// let placed = tokio_test::block_on(account.place_order(order));
+    /// assert!(placed.is_ok(), "{:?}", placed);
+    /// ```
+    pub async fn place_order(&self, order: OrderRequest) -> Result<Order> {
+        order.valid()?;
+        self.client
+            .post_signed_p("/dapi/v1/order", order, self.recv_window)
+            .await
+    }
+
+    /// Place a cancellation order
+    pub async fn cancel_order(&self, o: OrderCancellation) -> Result<Order> {
+        let recv_window = o.recv_window.unwrap_or(self.recv_window);
+        self.client.delete_signed_p("/dapi/v1/order", &o, recv_window).await
+    }
+
+    /// Cancel all open orders on this symbol
+    pub async fn cancel_all_open_orders<S>(&self, symbol: S) -> Result<StatusResponse>
+    where
+        S: Into<String>,
+    {
+        self.client
+            .delete_signed_p(
+                "/dapi/v1/allOpenOrders",
+                PairQuery { symbol: symbol.into() },
+                self.recv_window,
+            )
+            .await
+    }
+
+    /// Get currently open orders
+    pub async fn get_open_orders(&self, symbol: impl Into<String>) -> Result<Vec<Order>> {
+        let payload = build_signed_request_p([("symbol", symbol.into())], self.recv_window)?;
+        self.client.get_signed("/dapi/v1/openOrders", &payload).await
+    }
+
+    /// Return the balances of the margin assets
+    pub async fn account_balance(&self) -> Result<Vec<AccountBalance>> {
+        let request = build_signed_request(BTreeMap::<String, String>::new(), self.recv_window)?;
+        self.client.get_signed_d("/dapi/v1/balance", request.as_str()).await
+    }
+
+    /// Get current position risk for the contracts of a pair, such as "BTCUSD", or of all pairs
+    pub async fn position_information(&self, pair: Option<String>) -> Result<Vec<Position>> {
+        self.client
+            .get_signed_p("/dapi/v1/positionRisk", Some(PositionQuery { pair }), self.recv_window)
+            .await
+    }
+
+    /// Change the initial leverage for the symbol
+    pub async fn change_initial_leverage<S>(&self, symbol: S, leverage: u8) -> Result<ChangeLeverageResponse>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("leverage".into(), leverage.to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client.post_signed_d("/dapi/v1/leverage", request.as_str()).await
+    }
+
+    /// Change the margin type of the symbol
+    pub async fn change_margin_type<S>(&self, symbol: S, margin_type: MarginType) -> Result<StatusResponse>
+    where
+        S: Into<String>,
+    {
+        let request = ChangeMarginTypeRequest::new(symbol.into(), margin_type);
+        self.client
+            .post_signed_p("/dapi/v1/marginType", request, self.recv_window)
+            .await
+    }
+
+    /// Change the dual position side
+    pub async fn change_position_mode(&self, dual_side_position: bool) -> Result<StatusResponse> {
+        self.client
+            .post_signed_p(
+                "/dapi/v1/positionSide/dual",
+                ChangePositionModeRequest { dual_side_position },
+                self.recv_window,
+            )
+            .await
+    }
+
+    /// Whether the account is in hedge mode
+    pub async fn position_mode(&self) -> Result<PositionMode> {
+        self.client
+            .get_signed_p("/dapi/v1/positionSide/dual", Option::<String>::None, self.recv_window)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::api::Binance;
+    use crate::rest_model::OrderSide;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn orders_are_validated_before_sending() {
+        // The client has no credentials, so only a rejection by `valid` can come back as an order error
+        let account: CoinFuturesAccount = Binance::new(None, None);
+        let missing_price = OrderRequest {
+            price: None,
+            ..OrderRequest::limit("BTCUSD_PERP", OrderSide::Buy, dec!(1), dec!(20000))
+        };
+        let result = tokio_test::block_on(account.place_order(missing_price));
+        assert!(matches!(result, Err(Error::InvalidOrderError { .. })), "{result:?}");
+        let close_position = OrderRequest {
+            close_position: Some(true),
+            ..OrderRequest::stop_market("BTCUSD_PERP", OrderSide::Sell, dec!(1), dec!(19000))
+        };
+        let result = tokio_test::block_on(account.place_order(close_position));
+        assert!(matches!(result, Err(Error::InvalidOrderError { .. })), "{result:?}");
+    }
+}
@@ -0,0 +1,193 @@
+use crate::client::*;
+use crate::coin_futures::rest_model::*;
+use crate::errors::*;
+use crate::futures::rest_model::HistoryQuery;
+use crate::rest_model::PairQuery;
+use crate::util::*;
+use serde_json::Value;
+
+#[derive(Clone)]
+pub struct CoinFuturesMarket {
+    pub client: Client,
+    pub recv_window: u64,
+}
+
+#[derive(Serialize)]
+struct OptionalPairQuery {
+    pub pair: Option<String>,
+}
+
+impl CoinFuturesMarket {
+    /// Order book (Default 500; max 1000)
+    pub async fn get_depth<S>(&self, symbol: S) -> Result<OrderBook>
+    where
+        S: Into<String>,
+    {
+        self.client
+            .get_d("/dapi/v1/depth", Some(PairQuery { symbol: symbol.into() }))
+            .await
+    }
+
+    /// Get recent trades for a symbol
+    pub async fn get_trades<S>(&self, symbol: S) -> Result<Vec<Trade>>
+    where
+        S: Into<String>,
+    {
+        self.client
+            .get_d("/dapi/v1/trades", Some(PairQuery { symbol: symbol.into() }))
+            .await
+    }
+
+    /// Get compressed, aggregate trades
+    pub async fn get_agg_trades<S1, S2, S3, S4>(
+        &self,
+        symbol: S1,
+        from_id: S2,
+        start_time: S3,
+        end_time: S4,
+        limit: u16,
+    ) -> Result<AggTrades>
+    where
+        S1: Into<String>,
+        S2: Into<Option<u64>>,
+        S3: Into<Option<u64>>,
+        S4: Into<Option<u64>>,
+    {
+        let query = HistoryQuery {
+            start_time: start_time.into(),
+            end_time: end_time.into(),
+            from_id: from_id.into(),
+            limit,
+            symbol: symbol.into(),
+            interval: None,
+            period: None,
+        };
+        self.client.get_d("/dapi/v1/aggTrades", Some(query)).await
+    }
+
+    /// Returns up to 'limit' klines for given symbol and interval ("1m", "5m", ...), the volume is
+    /// in contracts and the quote asset volume in base asset
+    pub async fn get_klines<S1, S2, S3, S4, S5>(
+        &self,
+        symbol: S1,
+        interval: S2,
+        limit: S3,
+        start_time: S4,
+        end_time: S5,
+    ) -> Result<KlineSummaries>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<u16>,
+        S4: Into<Option<u64>>,
+        S5: Into<Option<u64>>,
+    {
+        let query = HistoryQuery {
+            start_time: start_time.into(),
+            end_time: end_time.into(),
+            limit: limit.into(),
+            symbol: symbol.into(),
+            interval: Some(interval.into()),
+            from_id: None,
+            period: None,
+        };
+        let data: Vec<Vec<Value>> = self.client.get_d("/dapi/v1/klines", Some(query)).await?;
+
+        let klines = KlineSummaries::AllKlineSummaries(
+            data.iter()
+                .map(|row| KlineSummary {
+                    open_time: to_i64(&row[0]),
+                    open: to_decimal(&row[1]),
+                    high: to_decimal(&row[2]),
+                    low: to_decimal(&row[3]),
+                    close: to_decimal(&row[4]),
+                    volume: to_decimal(&row[5]),
+                    close_time: to_i64(&row[6]),
+                    quote_asset_volume: to_decimal(&row[7]),
+                    number_of_trades: to_i64(&row[8]),
+                    taker_buy_base_asset_volume: to_decimal(&row[9]),
+                    taker_buy_quote_asset_volume: to_decimal(&row[10]),
+                })
+                .collect(),
+        );
+        Ok(klines)
+    }
+
+    /// Mark and index prices of the contracts of a pair, such as "BTCUSD", or of all contracts
+    pub async fn get_mark_prices(&self, pair: Option<String>) -> Result<Vec<MarkPrice>> {
+        self.client
+            .get_d("/dapi/v1/premiumIndex", Some(OptionalPairQuery { pair }))
+            .await
+    }
+
+    /// Funding rate history of a perpetual contract
+    pub async fn get_funding_rate<S1, S3, S4, S5>(
+        &self,
+        symbol: S1,
+        start_time: S3,
+        end_time: S4,
+        limit: S5,
+    ) -> Result<Vec<FundingRate>>
+    where
+        S1: Into<String>,
+        S3: Into<Option<u64>>,
+        S4: Into<Option<u64>>,
+        S5: Into<u16>,
+    {
+        let query = HistoryQuery {
+            start_time: start_time.into(),
+            end_time: end_time.into(),
+            limit: limit.into(),
+            symbol: symbol.into(),
+            from_id: None,
+            interval: None,
+            period: None,
+        };
+        self.client.get_d("/dapi/v1/fundingRate", Some(query)).await
+    }
+
+    /// 24hr ticker price change statistics of a symbol
+    pub async fn get_24h_price_stats<S>(&self, symbol: S) -> Result<Vec<PriceStats>>
+    where
+        S: Into<String>,
+    {
+        self.client
+            .get_d("/dapi/v1/ticker/24hr", Some(PairQuery { symbol: symbol.into() }))
+            .await
+    }
+
+    /// 24hr ticker price change statistics of all symbols
+    pub async fn get_all_24h_price_stats(&self) -> Result<Vec<PriceStats>> {
+        self.client.get_p("/dapi/v1/ticker/24hr", None).await
+    }
+
+    /// Latest price of a symbol
+    pub async fn get_price<S>(&self, symbol: S) -> Result<Vec<SymbolPrice>>
+    where
+        S: Into<String>,
+    {
+        self.client
+            .get_d("/dapi/v1/ticker/price", Some(PairQuery { symbol: symbol.into() }))
+            .await
+    }
+
+    /// Latest prices of all symbols
+    pub async fn get_all_prices(&self) -> Result<Vec<SymbolPrice>> {
+        self.client.get_p("/dapi/v1/ticker/price", None).await
+    }
+
+    /// Best price/qty on the order book for all symbols
+    pub async fn get_all_book_tickers(&self) -> Result<BookTickers> {
+        self.client.get_p("/dapi/v1/ticker/bookTicker", None).await
+    }
+
+    /// Open interest of a symbol, in contracts
+    pub async fn open_interest<S>(&self, symbol: S) -> Result<OpenInterest>
+    where
+        S: Into<String>,
+    {
+        self.client
+            .get_d("/dapi/v1/openInterest", Some(PairQuery { symbol: symbol.into() }))
+            .await
+    }
+}
@@ -0,0 +1,12 @@
+//! Coin-margined (COIN-M) perpetual and delivery futures, settled in the base asset, on `/dapi`.
+//!
+//! Quantities are in contracts of a fixed quote value, see [`rest_model::Symbol::contract_size`].
+//! Requests and payloads which are the same as the USDⓈ-M ones are shared with [`crate::futures`].
+
+pub mod account;
+pub mod general;
+pub mod market;
+pub mod rest_model;
+pub mod userstream;
+pub mod websockets;
+pub mod ws_model;
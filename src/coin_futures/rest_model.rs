@@ -0,0 +1,408 @@
+//! Models of the coin-margined futures API. Payloads which are the same as the USDⓈ-M ones are
+//! re-exported from [`crate::futures::rest_model`].
+
+pub use crate::futures::rest_model::{
+    AggTrades, ContractType, Filters, FundingRate, MarginType, OpenInterest, OrderBook, OrderType, PositionMode,
+    PositionSide, StatusResponse, WorkingType,
+};
+use crate::rest_model::{string_or_bool, string_or_float_opt};
+pub use crate::rest_model::{
+    string_or_float, string_or_u64, BookTickers, KlineSummaries, KlineSummary, OrderSide, OrderStatus, RateLimit,
+    ServerTime, TimeInForce,
+};
+use rust_decimal::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExchangeInformation {
+    pub timezone: String,
+    pub server_time: u64,
+    pub rate_limits: Vec<RateLimit>,
+    pub exchange_filters: Vec<Filters>,
+    pub symbols: Vec<Symbol>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ContractStatus {
+    PendingTrading,
+    Trading,
+    PreDelivering,
+    Delivering,
+    Delivered,
+    PreSettle,
+    Settling,
+    Close,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Symbol {
+    pub symbol: String,
+    pub pair: String,
+    pub contract_type: ContractType,
+    pub delivery_date: u64,
+    pub onboard_date: u64,
+    pub contract_status: ContractStatus,
+    /// Value of one contract in quote asset, such as 100 USD for BTCUSD
+    #[serde(with = "string_or_float")]
+    pub contract_size: Decimal,
+    pub margin_asset: String,
+    #[serde(with = "string_or_float")]
+    pub maint_margin_percent: Decimal,
+    #[serde(with = "string_or_float")]
+    pub required_margin_percent: Decimal,
+    pub base_asset: String,
+    pub quote_asset: String,
+    pub price_precision: u16,
+    pub quantity_precision: u16,
+    pub base_asset_precision: u64,
+    pub quote_precision: u64,
+    pub equal_qty_precision: u16,
+    #[serde(with = "string_or_float")]
+    pub trigger_protect: Decimal,
+    pub underlying_type: String,
+    pub underlying_sub_type: Vec<String>,
+    pub filters: Vec<Filters>,
+    pub order_types: Vec<OrderType>,
+    pub time_in_force: Vec<TimeInForce>,
+}
+
+impl Symbol {
+    /// Notional value in quote asset of a number of contracts
+    pub fn notional(&self, contracts: Decimal) -> Decimal { contracts * self.contract_size }
+
+    /// Whole number of contracts worth at most a notional value in quote asset
+    pub fn contracts_for_notional(&self, notional: Decimal) -> Decimal { (notional / self.contract_size).floor() }
+
+    /// Quantity of base asset, which is also the margin asset, that a number of contracts is worth at a price
+    pub fn base_qty(&self, contracts: Decimal, price: Decimal) -> Decimal { self.notional(contracts) / price }
+
+    /// Whole number of contracts worth at most a quantity of base asset at a price
+    pub fn contracts_for_base_qty(&self, base_qty: Decimal, price: Decimal) -> Decimal {
+        self.contracts_for_notional(base_qty * price)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceStats {
+    pub symbol: String,
+    pub pair: String,
+    #[serde(with = "string_or_float")]
+    pub price_change: Decimal,
+    #[serde(with = "string_or_float")]
+    pub price_change_percent: Decimal,
+    #[serde(with = "string_or_float")]
+    pub weighted_avg_price: Decimal,
+    #[serde(with = "string_or_float")]
+    pub last_price: Decimal,
+    #[serde(with = "string_or_float")]
+    pub last_qty: Decimal,
+    #[serde(with = "string_or_float")]
+    pub open_price: Decimal,
+    #[serde(with = "string_or_float")]
+    pub high_price: Decimal,
+    #[serde(with = "string_or_float")]
+    pub low_price: Decimal,
+    /// Volume in contracts
+    #[serde(with = "string_or_float")]
+    pub volume: Decimal,
+    #[serde(with = "string_or_float")]
+    pub base_volume: Decimal,
+    pub open_time: u64,
+    pub close_time: u64,
+    pub first_id: u64,
+    pub last_id: u64,
+    pub count: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolPrice {
+    pub symbol: String,
+    #[serde(rename = "ps")]
+    pub pair: String,
+    #[serde(with = "string_or_float")]
+    pub price: Decimal,
+    pub time: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Trade {
+    pub id: u64,
+    pub is_buyer_maker: bool,
+    #[serde(with = "string_or_float")]
+    pub price: Decimal,
+    /// Quantity in contracts
+    #[serde(with = "string_or_float")]
+    pub qty: Decimal,
+    #[serde(with = "string_or_float")]
+    pub base_qty: Decimal,
+    pub time: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarkPrice {
+    pub symbol: String,
+    pub pair: String,
+    #[serde(with = "string_or_float")]
+    pub mark_price: Decimal,
+    #[serde(with = "string_or_float")]
+    pub index_price: Decimal,
+    /// Only meaningful in the last hour before settlement
+    #[serde(with = "string_or_float")]
+    pub estimated_settle_price: Decimal,
+    /// None for delivery contracts
+    #[serde(with = "empty_or_float")]
+    pub last_funding_rate: Option<Decimal>,
+    /// None for delivery contracts
+    #[serde(with = "empty_or_float")]
+    pub interest_rate: Option<Decimal>,
+    /// 0 for delivery contracts
+    pub next_funding_time: u64,
+    pub time: u64,
+}
+
+/// An order, as returned by queries, placements and cancellations
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Order {
+    pub order_id: u64,
+    pub symbol: String,
+    pub pair: String,
+    pub status: OrderStatus,
+    pub client_order_id: String,
+    #[serde(with = "string_or_float")]
+    pub price: Decimal,
+    #[serde(default, with = "string_or_float")]
+    pub avg_price: Decimal,
+    /// Quantity in contracts
+    #[serde(with = "string_or_float")]
+    pub orig_qty: Decimal,
+    #[serde(with = "string_or_float")]
+    pub executed_qty: Decimal,
+    /// Executed quantity in base asset
+    #[serde(default, with = "string_or_float")]
+    pub cum_base: Decimal,
+    pub time_in_force: TimeInForce,
+    #[serde(rename = "type")]
+    pub order_type: OrderType,
+    pub orig_type: OrderType,
+    pub reduce_only: bool,
+    pub close_position: bool,
+    pub side: OrderSide,
+    pub position_side: PositionSide,
+    #[serde(default, with = "string_or_float")]
+    pub stop_price: Decimal,
+    pub working_type: WorkingType,
+    #[serde(default)]
+    pub price_protect: bool,
+    #[serde(default, with = "string_or_float_opt")]
+    pub activate_price: Option<Decimal>,
+    #[serde(default, with = "string_or_float_opt")]
+    pub price_rate: Option<Decimal>,
+    /// Creation time, not included in placement and cancellation responses
+    #[serde(default)]
+    pub time: u64,
+    pub update_time: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountBalance {
+    pub account_alias: String,
+    pub asset: String,
+    #[serde(with = "string_or_float")]
+    pub balance: Decimal,
+    #[serde(with = "string_or_float")]
+    pub withdraw_available: Decimal,
+    #[serde(with = "string_or_float")]
+    pub cross_wallet_balance: Decimal,
+    #[serde(with = "string_or_float", rename = "crossUnPnl")]
+    pub cross_unrealized_pnl: Decimal,
+    #[serde(with = "string_or_float")]
+    pub available_balance: Decimal,
+    pub update_time: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Position {
+    pub symbol: String,
+    /// Position in contracts, negative when short
+    #[serde(with = "string_or_float", rename = "positionAmt")]
+    pub position_amount: Decimal,
+    #[serde(with = "string_or_float")]
+    pub entry_price: Decimal,
+    #[serde(default, with = "string_or_float_opt")]
+    pub break_even_price: Option<Decimal>,
+    #[serde(with = "string_or_float")]
+    pub mark_price: Decimal,
+    /// In margin asset
+    #[serde(with = "string_or_float", rename = "unRealizedProfit")]
+    pub unrealized_profit: Decimal,
+    #[serde(with = "string_or_float")]
+    pub liquidation_price: Decimal,
+    #[serde(with = "string_or_u64")]
+    pub leverage: u64,
+    #[serde(with = "string_or_float")]
+    pub max_qty: Decimal,
+    pub margin_type: MarginType,
+    #[serde(with = "string_or_float")]
+    pub isolated_margin: Decimal,
+    #[serde(with = "string_or_bool")]
+    pub is_auto_add_margin: bool,
+    pub position_side: PositionSide,
+    /// In margin asset
+    #[serde(with = "string_or_float")]
+    pub notional_value: Decimal,
+    #[serde(with = "string_or_float")]
+    pub isolated_wallet: Decimal,
+    pub update_time: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeLeverageResponse {
+    pub leverage: u8,
+    /// Maximum position in contracts
+    #[serde(with = "string_or_float")]
+    pub max_qty: Decimal,
+    pub symbol: String,
+}
+
+/// Fields which are empty strings when they do not apply
+mod empty_or_float {
+    use rust_decimal::Decimal;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(value: &Option<Decimal>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match value {
+            Some(v) => serializer.collect_str(v),
+            None => serializer.serialize_str(""),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Decimal>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        if s.is_empty() {
+            return Ok(None);
+        }
+        s.parse().map(Some).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn btcusd_perp() -> Symbol {
+        serde_json::from_str(
+            r#"{
+                "symbol": "BTCUSD_PERP", "pair": "BTCUSD", "contractType": "PERPETUAL", "deliveryDate": 4133404800000,
+                "onboardDate": 1597042800000, "contractStatus": "TRADING", "contractSize": 100, "marginAsset": "BTC",
+                "maintMarginPercent": "2.5000", "requiredMarginPercent": "5.0000", "baseAsset": "BTC",
+                "quoteAsset": "USD", "pricePrecision": 1, "quantityPrecision": 0, "baseAssetPrecision": 8,
+                "quotePrecision": 8, "equalQtyPrecision": 4, "maxMoveOrderLimit": 10000, "triggerProtect": "0.0500",
+                "underlyingType": "COIN", "underlyingSubType": [], "liquidationFee": "0.015000",
+                "marketTakeBound": "0.05",
+                "filters": [
+                    {"filterType": "PRICE_FILTER", "minPrice": "1000", "maxPrice": "4520958", "tickSize": "0.1"},
+                    {"filterType": "LOT_SIZE", "minQty": "1", "maxQty": "1000000", "stepSize": "1"},
+                    {"filterType": "MARKET_LOT_SIZE", "minQty": "1", "maxQty": "60000", "stepSize": "1"},
+                    {"filterType": "MAX_NUM_ORDERS", "limit": 200},
+                    {"filterType": "PERCENT_PRICE", "multiplierUp": "1.0500", "multiplierDown": "0.9500",
+                     "multiplierDecimal": "4"}
+                ],
+                "orderTypes": ["LIMIT", "MARKET", "STOP", "STOP_MARKET", "TAKE_PROFIT", "TAKE_PROFIT_MARKET",
+                               "TRAILING_STOP_MARKET"],
+                "timeInForce": ["GTC", "IOC", "FOK", "GTX"]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn symbol_serde() {
+        let symbol = btcusd_perp();
+        assert!(matches!(symbol.contract_type, ContractType::Perpetual));
+        assert_eq!(symbol.contract_status, ContractStatus::Trading);
+        assert_eq!(symbol.contract_size, dec!(100));
+        assert_eq!(symbol.margin_asset, "BTC");
+        let status: ContractStatus = serde_json::from_str("\"UNKNOWN_STATUS\"").unwrap();
+        assert_eq!(status, ContractStatus::Other);
+    }
+
+    #[test]
+    fn contract_size() {
+        let symbol = btcusd_perp();
+        assert_eq!(symbol.notional(dec!(3)), dec!(300));
+        assert_eq!(symbol.contracts_for_notional(dec!(399.99)), dec!(3));
+        assert_eq!(symbol.base_qty(dec!(5), dec!(25000)), dec!(0.02));
+        assert_eq!(symbol.contracts_for_base_qty(dec!(0.02), dec!(25000)), dec!(5));
+        assert_eq!(symbol.contracts_for_base_qty(dec!(0.0199), dec!(25000)), dec!(4));
+    }
+
+    #[test]
+    fn mark_price_serde() {
+        let prices: Vec<MarkPrice> = serde_json::from_str(
+            r#"[
+                {"symbol": "BTCUSD_PERP", "pair": "BTCUSD", "markPrice": "11029.69574559",
+                 "indexPrice": "10979.14437500", "estimatedSettlePrice": "10981.74168236",
+                 "lastFundingRate": "0.00071003", "interestRate": "0.00010000", "nextFundingTime": 1596096000000,
+                 "time": 1596094042000},
+                {"symbol": "BTCUSD_200925", "pair": "BTCUSD", "markPrice": "12077.01343750",
+                 "indexPrice": "11986.95543750", "estimatedSettlePrice": "11985.70037267", "lastFundingRate": "",
+                 "interestRate": "", "nextFundingTime": 0, "time": 1596094042000}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(prices[0].last_funding_rate, Some(dec!(0.00071003)));
+        assert_eq!(prices[0].interest_rate, Some(dec!(0.0001)));
+        assert_eq!(prices[1].last_funding_rate, None);
+        assert_eq!(prices[1].interest_rate, None);
+        let serialized = serde_json::to_value(&prices[1]).unwrap();
+        assert_eq!(serialized["lastFundingRate"], "");
+    }
+
+    #[test]
+    fn order_and_position_serde() {
+        let order: Order = serde_json::from_str(
+            r#"{"avgPrice": "0.0", "clientOrderId": "abc", "cumBase": "0", "executedQty": "0", "orderId": 1917641,
+                "origQty": "0.40", "origType": "TRAILING_STOP_MARKET", "price": "0", "reduceOnly": false,
+                "side": "BUY", "status": "NEW", "stopPrice": "9300", "closePosition": false, "symbol": "BTCUSD_200925",
+                "pair": "BTCUSD", "time": 1579276756075, "timeInForce": "GTC", "type": "TRAILING_STOP_MARKET",
+                "activatePrice": "9020", "priceRate": "0.3", "updateTime": 1579276756075,
+                "workingType": "CONTRACT_PRICE", "priceProtect": false, "positionSide": "SHORT"}"#,
+        )
+        .unwrap();
+        assert!(matches!(order.order_type, OrderType::TrailingStopMarket));
+        assert_eq!(order.activate_price, Some(dec!(9020)));
+        assert_eq!(order.price_rate, Some(dec!(0.3)));
+
+        let position: Position = serde_json::from_str(
+            r#"{"symbol": "BTCUSD_201225", "positionAmt": "-2", "entryPrice": "0.00000000",
+                "breakEvenPrice": "0.0", "markPrice": "0.00000000", "unRealizedProfit": "0.00000000",
+                "liquidationPrice": "0", "leverage": "125", "maxQty": "50", "marginType": "cross",
+                "isolatedMargin": "0.00000000", "isAutoAddMargin": "false", "positionSide": "BOTH",
+                "notionalValue": "0", "isolatedWallet": "0", "updateTime": 0}"#,
+        )
+        .unwrap();
+        assert_eq!(position.position_amount, dec!(-2));
+        assert_eq!(position.leverage, 125);
+        assert!(!position.is_auto_add_margin);
+    }
+}
@@ -0,0 +1,42 @@
+//! Market and user data streams of `dstream`, which share their format and stream names with the
+//! USDⓈ-M streams, see [`crate::futures::websockets`].
+
+pub use crate::futures::websockets::{
+    agg_trade_stream, all_book_ticker_stream, all_mini_ticker_stream, all_ticker_stream, book_ticker_stream,
+    diff_book_depth_stream, kline_stream, mini_ticker_stream, partial_book_depth_stream, ticker_stream, WebSockets,
+};
+
+use crate::config::Config;
+use crate::errors::*;
+
+/// Mark price stream of a symbol, or of all the contracts of a pair such as "btcusd", update speed
+/// is 1000 or 3000
+pub fn mark_price_stream(symbol: &str, update_speed: u16) -> String {
+    if update_speed == 1000 {
+        format!("{symbol}@markPrice@1s")
+    } else {
+        format!("{symbol}@markPrice")
+    }
+}
+
+/// New websocket holder connecting to the coin-margined futures endpoint of the configuration
+/// # Examples
+/// ```rust,no_run
+/// use binance::{coin_futures::{websockets::*, ws_model::WebsocketEvent}, config::*};
+/// use std::sync::atomic::AtomicBool;
+/// let mut ws = new_with_options(|event: WebsocketEvent| { println!("{event:?}"); Ok(()) }, Config::default());
+/// tokio_test::block_on(ws.connect("my_listen_key")).unwrap();
+/// tokio_test::block_on(ws.event_loop(&AtomicBool::new(true))).unwrap();
+/// ```
+pub fn new_with_options<'a, WE, Callback>(handler: Callback, conf: Config) -> WebSockets<'a, WE>
+where
+    WE: serde::de::DeserializeOwned,
+    Callback: FnMut(WE) -> Result<()> + 'a + Send,
+{
+    let conf = Config {
+        ws_endpoint: conf.coin_futures_ws_endpoint.clone(),
+        futures_ws_endpoint: conf.coin_futures_ws_endpoint.clone(),
+        ..conf
+    };
+    WebSockets::new_with_options(handler, conf)
+}
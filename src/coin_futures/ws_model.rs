@@ -0,0 +1,137 @@
+use crate::futures::rest_model::{MarginType, OrderType, PositionSide, WorkingType};
+pub use crate::futures::ws_model::{Balance, ReasonType};
+use crate::rest_model::{string_or_float, string_or_float_opt, ExecutionType, OrderSide, OrderStatus, TimeInForce};
+use rust_decimal::Decimal;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE", tag = "e")]
+pub enum WebsocketEvent {
+    AccountUpdate(Box<AccountUpdate>),
+    OrderTradeUpdate(Box<OrderTradeUpdate>),
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AccountUpdate {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+    #[serde(rename = "i")]
+    pub account_alias: String,
+    #[serde(rename = "a")]
+    pub account: Account,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Account {
+    #[serde(rename = "m")]
+    pub reason_type: ReasonType,
+    #[serde(rename = "B")]
+    pub balances: Vec<Balance>,
+    #[serde(rename = "P")]
+    pub positions: Vec<Position>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Position {
+    #[serde(rename = "s")]
+    pub symbol: String,
+    /// Position in contracts, negative when short
+    #[serde(rename = "pa", with = "string_or_float")]
+    pub position_amount: Decimal,
+    #[serde(rename = "ep", with = "string_or_float")]
+    pub entry_price: Decimal,
+    #[serde(default, rename = "bep", with = "string_or_float_opt")]
+    pub breakeven_price: Option<Decimal>,
+    #[serde(rename = "cr", with = "string_or_float")]
+    pub accumulated_realized: Decimal,
+    #[serde(rename = "up", with = "string_or_float")]
+    pub unrealized_profit: Decimal,
+    #[serde(rename = "mt")]
+    pub margin_type: MarginType,
+    #[serde(rename = "iw", with = "string_or_float")]
+    pub isolated_wallet: Decimal,
+    #[serde(rename = "ps")]
+    pub position_side: PositionSide,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct OrderTradeUpdate {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+    #[serde(rename = "i")]
+    pub account_alias: String,
+    #[serde(rename = "o")]
+    pub order: Order,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Order {
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "c")]
+    pub client_order_id: String,
+    #[serde(rename = "S")]
+    pub side: OrderSide,
+    #[serde(rename = "o")]
+    pub order_type: OrderType,
+    #[serde(rename = "f")]
+    pub time_in_force: TimeInForce,
+    /// Quantity in contracts
+    #[serde(rename = "q", with = "string_or_float")]
+    pub quantity: Decimal,
+    #[serde(rename = "p", with = "string_or_float")]
+    pub price: Decimal,
+    #[serde(rename = "ap", with = "string_or_float")]
+    pub average_price: Decimal,
+    #[serde(rename = "sp", with = "string_or_float")]
+    pub stop_price: Decimal,
+    #[serde(rename = "x")]
+    pub execution_type: ExecutionType,
+    #[serde(rename = "X")]
+    pub order_status: OrderStatus,
+    #[serde(rename = "i")]
+    pub order_id: u64,
+    #[serde(rename = "l", with = "string_or_float")]
+    pub order_last_filled_quantity: Decimal,
+    #[serde(rename = "z", with = "string_or_float")]
+    pub order_filled_accumulated_quantity: Decimal,
+    #[serde(rename = "L", with = "string_or_float")]
+    pub last_filled_price: Decimal,
+    #[serde(rename = "ma")]
+    pub margin_asset: String,
+    #[serde(default, rename = "n", with = "string_or_float_opt")]
+    pub commission: Option<Decimal>,
+    #[serde(rename = "N")]
+    pub commission_asset: Option<String>,
+    #[serde(rename = "T")]
+    pub order_trade_time: u64,
+    #[serde(rename = "t")]
+    pub trade_id: u64,
+    #[serde(rename = "rp", with = "string_or_float")]
+    pub realized_profit: Decimal,
+    #[serde(rename = "b", with = "string_or_float")]
+    pub bid_notional: Decimal,
+    #[serde(rename = "a", with = "string_or_float")]
+    pub ask_notional: Decimal,
+    #[serde(rename = "m")]
+    pub is_maker: bool,
+    #[serde(rename = "R")]
+    pub is_reduce: bool,
+    #[serde(rename = "wt")]
+    pub working_type: WorkingType,
+    #[serde(rename = "ot")]
+    pub original_order_type: OrderType,
+    #[serde(rename = "ps")]
+    pub position_side: PositionSide,
+    #[serde(rename = "cp")]
+    pub close_position: bool,
+    #[serde(default, rename = "AP", with = "string_or_float_opt")]
+    pub activation_price: Option<Decimal>,
+    #[serde(default, rename = "cr", with = "string_or_float_opt")]
+    pub callback_rate: Option<Decimal>,
+    #[serde(default, rename = "pP")]
+    pub price_protect: bool,
+}
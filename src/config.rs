@@ -8,6 +8,15 @@ pub struct Config {
     pub futures_rest_api_endpoint: String,
     pub futures_ws_endpoint: String,
 
+    pub coin_futures_rest_api_endpoint: String,
+    pub coin_futures_ws_endpoint: String,
+
+    pub options_rest_api_endpoint: String,
+    pub options_ws_endpoint: String,
+
+    pub portfolio_margin_rest_api_endpoint: String,
+    pub portfolio_margin_ws_endpoint: String,
+
     pub recv_window: u64,
 
     pub binance_us_api: bool,
@@ -28,6 +37,8 @@ impl Config {
             .set_ws_endpoint("wss://testnet.binance.vision")
             .set_futures_rest_api_endpoint("https://testnet.binancefuture.us")
             .set_futures_ws_endpoint("wss://fstream.binancefuture.us")
+            .set_coin_futures_rest_api_endpoint("https://testnet.binancefuture.com")
+            .set_coin_futures_ws_endpoint("wss://dstream.binancefuture.com")
     }
 
     /// Sets the rest api endpoint. Defaults to <https://api.binance.us>.
@@ -110,6 +121,129 @@ impl Config {
         self
     }
 
+    /// Sets the coin-margined futures rest api endpoint. Defaults to <https://dapi.binance.com>.
+    ///
+    /// # Arguments
+    ///
+    /// * `coin_futures_rest_api_endpoint`:
+    ///
+    /// returns: Config
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binance::config::Config;
+    /// let config = Config::default();
+    /// config.set_coin_futures_rest_api_endpoint("http://myendpoint:8080");
+    /// ```
+    pub fn set_coin_futures_rest_api_endpoint<T: Into<String>>(mut self, coin_futures_rest_api_endpoint: T) -> Self {
+        self.coin_futures_rest_api_endpoint = coin_futures_rest_api_endpoint.into();
+        self
+    }
+
+    /// Sets the coin-margined futures websocket endpoint. Defaults to "wss://dstream.binance.com".
+    ///
+    /// # Arguments
+    ///
+    /// * `coin_futures_ws_endpoint`:
+    ///
+    /// returns: Config
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binance::config::Config;
+    /// let config = Config::default();
+    /// config.set_coin_futures_ws_endpoint("ws://myendpoint:8080");
+    /// ```
+    pub fn set_coin_futures_ws_endpoint<T: Into<String>>(mut self, coin_futures_ws_endpoint: T) -> Self {
+        self.coin_futures_ws_endpoint = coin_futures_ws_endpoint.into();
+        self
+    }
+
+    /// Sets the options rest api endpoint. Defaults to <https://eapi.binance.com>.
+    ///
+    /// # Arguments
+    ///
+    /// * `options_rest_api_endpoint`:
+    ///
+    /// returns: Config
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binance::config::Config;
+    /// let config = Config::default();
+    /// config.set_options_rest_api_endpoint("http://myendpoint:8080");
+    /// ```
+    pub fn set_options_rest_api_endpoint<T: Into<String>>(mut self, options_rest_api_endpoint: T) -> Self {
+        self.options_rest_api_endpoint = options_rest_api_endpoint.into();
+        self
+    }
+
+    /// Sets the options websocket endpoint. Defaults to "wss://nbstream.binance.com/eoptions".
+    ///
+    /// # Arguments
+    ///
+    /// * `options_ws_endpoint`:
+    ///
+    /// returns: Config
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binance::config::Config;
+    /// let config = Config::default();
+    /// config.set_options_ws_endpoint("ws://myendpoint:8080");
+    /// ```
+    pub fn set_options_ws_endpoint<T: Into<String>>(mut self, options_ws_endpoint: T) -> Self {
+        self.options_ws_endpoint = options_ws_endpoint.into();
+        self
+    }
+
+    /// Sets the portfolio margin rest api endpoint. Defaults to <https://papi.binance.com>.
+    ///
+    /// # Arguments
+    ///
+    /// * `portfolio_margin_rest_api_endpoint`:
+    ///
+    /// returns: Config
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binance::config::Config;
+    /// let config = Config::default();
+    /// config.set_portfolio_margin_rest_api_endpoint("http://myendpoint:8080");
+    /// ```
+    pub fn set_portfolio_margin_rest_api_endpoint<T: Into<String>>(
+        mut self,
+        portfolio_margin_rest_api_endpoint: T,
+    ) -> Self {
+        self.portfolio_margin_rest_api_endpoint = portfolio_margin_rest_api_endpoint.into();
+        self
+    }
+
+    /// Sets the portfolio margin websocket endpoint. Defaults to "wss://fstream.binance.com/pm".
+    ///
+    /// # Arguments
+    ///
+    /// * `portfolio_margin_ws_endpoint`:
+    ///
+    /// returns: Config
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binance::config::Config;
+    /// let config = Config::default();
+    /// config.set_portfolio_margin_ws_endpoint("ws://myendpoint:8080");
+    /// ```
+    pub fn set_portfolio_margin_ws_endpoint<T: Into<String>>(mut self, portfolio_margin_ws_endpoint: T) -> Self {
+        self.portfolio_margin_ws_endpoint = portfolio_margin_ws_endpoint.into();
+        self
+    }
+
     /// Sets the 'receive window'. The receive window is the number of milliseconds after timestamp
     /// the request is valid for.
     ///
@@ -167,6 +301,15 @@ impl Default for Config {
             futures_rest_api_endpoint: "https://fapi.binance.us".into(),
             futures_ws_endpoint: "wss://fstream.binance.us".into(),
 
+            coin_futures_rest_api_endpoint: "https://dapi.binance.com".into(),
+            coin_futures_ws_endpoint: "wss://dstream.binance.com".into(),
+
+            options_rest_api_endpoint: "https://eapi.binance.com".into(),
+            options_ws_endpoint: "wss://nbstream.binance.com/eoptions".into(),
+
+            portfolio_margin_rest_api_endpoint: "https://papi.binance.com".into(),
+            portfolio_margin_ws_endpoint: "wss://fstream.binance.com/pm".into(),
+
             recv_window: 5000,
             binance_us_api: false,
 
@@ -12,6 +12,16 @@ pub struct BinanceContentError {
     extra: HashMap<String, Value>,
 }
 
+impl BinanceContentError {
+    pub fn new<S: Into<String>>(code: i32, msg: S) -> Self {
+        Self {
+            code,
+            msg: msg.into(),
+            extra: HashMap::new(),
+        }
+    }
+}
+
 /// First errors are technical errors
 /// All unhandled binance content errors are BinanceError
 /// The rest are binance content errors that are properly handled
@@ -53,6 +63,10 @@ pub enum Error {
     InvalidPrice,
     #[error("invalid period {0}")]
     InvalidPeriod(String),
+    #[error("invalid time range, start time {start_time} is after end time {end_time}")]
+    InvalidTimeRange { start_time: u64, end_time: u64 },
+    #[error("client order ids with prefix {prefix} can reach {max_len} characters, over the limit of 36")]
+    ClientOrderIdTooLong { prefix: String, max_len: usize },
     #[error("internal server error")]
     InternalServerError,
     #[error("service unavailable")]
@@ -1,13 +1,17 @@
 use std::collections::BTreeMap;
 
 use super::rest_model::{
-    AccountBalance, AccountInformation, CanceledOrder, ChangeLeverageResponse, Order, OrderType, Position,
-    PositionSide, Transaction, WorkingType,
+    AccountBalance, AccountInformation, AdlQuantile, AutoCloseType, CanceledOrder, ChangeLeverageResponse,
+    CommissionRate, CountdownCancelAll, Income, IncomeType, MarginType, MultiAssetsMode, Order, OrderType, Position,
+    PositionMarginChange, PositionMarginResponse, PositionMarginType, PositionMode, PositionSide, StatusResponse,
+    Transaction, UserTrade, WorkingType,
 };
+use super::ws_model::{PriceMatch, SelfTradePreventionMode};
 use crate::account::OrderCancellation;
+use crate::batch::{batch_results, encode_orders};
 use crate::client::Client;
 use crate::errors::*;
-use crate::rest_model::{OrderSide, TimeInForce};
+use crate::rest_model::{OrderResponse, OrderSide, TimeInForce};
 use crate::rest_model::{PairAndWindowQuery, PairQuery};
 use crate::util::*;
 use rust_decimal::Decimal;
@@ -51,7 +55,54 @@ pub struct GetOrderRequest {
     pub orig_client_order_id: Option<String>,
 }
 
-#[derive(Serialize, Default)]
+/// Type of a futures order with the parameters it requires, the limit orders are good till
+/// canceled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderSpec {
+    Limit {
+        qty: Decimal,
+        price: Decimal,
+    },
+    /// A LIMIT order which expires at a time in ms
+    GoodTillDate {
+        qty: Decimal,
+        price: Decimal,
+        good_till_date: u64,
+    },
+    Market {
+        qty: Decimal,
+    },
+    /// Places a LIMIT order once the stop price is reached
+    Stop {
+        qty: Decimal,
+        price: Decimal,
+        stop_price: Decimal,
+    },
+    /// Places a MARKET order once the stop price is reached
+    StopMarket {
+        qty: Decimal,
+        stop_price: Decimal,
+    },
+    /// Places a LIMIT order once the stop price is reached
+    TakeProfit {
+        qty: Decimal,
+        price: Decimal,
+        stop_price: Decimal,
+    },
+    /// Places a MARKET order once the stop price is reached
+    TakeProfitMarket {
+        qty: Decimal,
+        stop_price: Decimal,
+    },
+    /// The callback rate is a percentage between 0.1 and 10
+    TrailingStopMarket {
+        qty: Decimal,
+        callback_rate: Decimal,
+    },
+}
+
+/// An order to place on USD-M futures
+#[derive(Serialize, Default, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct OrderRequest {
     pub symbol: String,
@@ -72,15 +123,399 @@ pub struct OrderRequest {
     #[serde(serialize_with = "serialize_opt_as_uppercase")]
     pub price_protect: Option<bool>,
     pub new_client_order_id: Option<String>,
+    pub self_trade_prevention_mode: Option<SelfTradePreventionMode>,
+    /// Prices a LIMIT, STOP or TAKE_PROFIT order from the order book instead of the price
+    pub price_match: Option<PriceMatch>,
+    /// Expiry time in ms of a GTD order
+    pub good_till_date: Option<u64>,
+    /// ACK or RESULT, FULL is not supported by futures
+    pub new_order_resp_type: Option<OrderResponse>,
 }
 
+impl OrderRequest {
+    /// An order of a kind, which carries the parameters its type requires
+    pub fn new(symbol: impl Into<String>, side: OrderSide, spec: OrderSpec) -> Self {
+        let order = Self {
+            symbol: symbol.into(),
+            side,
+            ..Self::default()
+        };
+        match spec {
+            OrderSpec::Limit { qty, price } => Self {
+                order_type: OrderType::Limit,
+                time_in_force: Some(TimeInForce::GTC),
+                quantity: Some(qty),
+                price: Some(price),
+                ..order
+            },
+            OrderSpec::GoodTillDate {
+                qty,
+                price,
+                good_till_date,
+            } => Self {
+                order_type: OrderType::Limit,
+                time_in_force: Some(TimeInForce::GTD),
+                quantity: Some(qty),
+                price: Some(price),
+                good_till_date: Some(good_till_date),
+                ..order
+            },
+            OrderSpec::Market { qty } => Self {
+                order_type: OrderType::Market,
+                quantity: Some(qty),
+                ..order
+            },
+            OrderSpec::Stop { qty, price, stop_price } => Self {
+                order_type: OrderType::Stop,
+                quantity: Some(qty),
+                price: Some(price),
+                stop_price: Some(stop_price),
+                ..order
+            },
+            OrderSpec::StopMarket { qty, stop_price } => Self {
+                order_type: OrderType::StopMarket,
+                quantity: Some(qty),
+                stop_price: Some(stop_price),
+                ..order
+            },
+            OrderSpec::TakeProfit { qty, price, stop_price } => Self {
+                order_type: OrderType::TakeProfit,
+                quantity: Some(qty),
+                price: Some(price),
+                stop_price: Some(stop_price),
+                ..order
+            },
+            OrderSpec::TakeProfitMarket { qty, stop_price } => Self {
+                order_type: OrderType::TakeProfitMarket,
+                quantity: Some(qty),
+                stop_price: Some(stop_price),
+                ..order
+            },
+            OrderSpec::TrailingStopMarket { qty, callback_rate } => Self {
+                order_type: OrderType::TrailingStopMarket,
+                quantity: Some(qty),
+                callback_rate: Some(callback_rate),
+                ..order
+            },
+        }
+    }
+
+    /// A LIMIT order, good till canceled
+    pub fn limit(symbol: impl Into<String>, side: OrderSide, qty: Decimal, price: Decimal) -> Self {
+        Self::new(symbol, side, OrderSpec::Limit { qty, price })
+    }
+
+    /// A MARKET order
+    pub fn market(symbol: impl Into<String>, side: OrderSide, qty: Decimal) -> Self {
+        Self::new(symbol, side, OrderSpec::Market { qty })
+    }
+
+    /// A STOP order, placing a LIMIT order once the stop price is reached
+    pub fn stop(symbol: impl Into<String>, side: OrderSide, qty: Decimal, price: Decimal, stop_price: Decimal) -> Self {
+        Self::new(symbol, side, OrderSpec::Stop { qty, price, stop_price })
+    }
+
+    /// A STOP_MARKET order, placing a MARKET order once the stop price is reached
+    pub fn stop_market(symbol: impl Into<String>, side: OrderSide, qty: Decimal, stop_price: Decimal) -> Self {
+        Self::new(symbol, side, OrderSpec::StopMarket { qty, stop_price })
+    }
+
+    /// A TAKE_PROFIT order, placing a LIMIT order once the stop price is reached
+    pub fn take_profit(
+        symbol: impl Into<String>,
+        side: OrderSide,
+        qty: Decimal,
+        price: Decimal,
+        stop_price: Decimal,
+    ) -> Self {
+        Self::new(symbol, side, OrderSpec::TakeProfit { qty, price, stop_price })
+    }
+
+    /// A TAKE_PROFIT_MARKET order, placing a MARKET order once the stop price is reached
+    pub fn take_profit_market(symbol: impl Into<String>, side: OrderSide, qty: Decimal, stop_price: Decimal) -> Self {
+        Self::new(symbol, side, OrderSpec::TakeProfitMarket { qty, stop_price })
+    }
+
+    /// A TRAILING_STOP_MARKET order, the callback rate is a percentage between 0.1 and 10
+    pub fn trailing_stop_market(
+        symbol: impl Into<String>,
+        side: OrderSide,
+        qty: Decimal,
+        callback_rate: Decimal,
+    ) -> Self {
+        Self::new(symbol, side, OrderSpec::TrailingStopMarket { qty, callback_rate })
+    }
+
+    /// A LIMIT order which expires at a time in ms
+    pub fn good_till_date(
+        symbol: impl Into<String>,
+        side: OrderSide,
+        qty: Decimal,
+        price: Decimal,
+        good_till_date: u64,
+    ) -> Self {
+        Self::new(
+            symbol,
+            side,
+            OrderSpec::GoodTillDate {
+                qty,
+                price,
+                good_till_date,
+            },
+        )
+    }
+
+    /// Checks that the order carries the parameters its order type requires, and no illegal
+    /// combination of them
+    pub fn valid(&self) -> Result<()> {
+        let error = |msg: &str| {
+            Err(Error::InvalidOrderError {
+                msg: format!("{msg} for a {:?} order on {}", self.order_type, self.symbol),
+            })
+        };
+        let close_position = self.close_position.unwrap_or(false);
+        let reduce_only = self.reduce_only.unwrap_or(false);
+        if close_position {
+            if !matches!(self.order_type, OrderType::StopMarket | OrderType::TakeProfitMarket) {
+                return error("close position is only supported by STOP_MARKET and TAKE_PROFIT_MARKET orders");
+            }
+            if self.quantity.is_some() {
+                return error("close position cannot be sent with a quantity");
+            }
+            if reduce_only {
+                return error("close position cannot be sent with reduce only");
+            }
+        } else if self.quantity.unwrap_or_default() <= Decimal::ZERO {
+            return error("quantity must be positive");
+        }
+        if reduce_only && matches!(self.position_side, Some(PositionSide::Long | PositionSide::Short)) {
+            return error("reduce only cannot be sent in hedge mode");
+        }
+
+        let requires_price = matches!(
+            self.order_type,
+            OrderType::Limit | OrderType::Stop | OrderType::TakeProfit
+        );
+        match (&self.price_match, self.price) {
+            (Some(_), _) if !requires_price => return error("price match is only supported by priced orders"),
+            (Some(_), Some(_)) => return error("price match cannot be sent with a price"),
+            (None, None) if requires_price => return error("missing price"),
+            (None, Some(_)) if !requires_price => return error("unexpected price"),
+            _ => {}
+        }
+        let requires_stop_price = matches!(
+            self.order_type,
+            OrderType::Stop | OrderType::StopMarket | OrderType::TakeProfit | OrderType::TakeProfitMarket
+        );
+        if requires_stop_price != self.stop_price.is_some() {
+            return error(if requires_stop_price {
+                "missing stop price"
+            } else {
+                "unexpected stop price"
+            });
+        }
+        let trailing = self.order_type == OrderType::TrailingStopMarket;
+        match self.callback_rate {
+            None if trailing => return error("missing callback rate"),
+            Some(_) if !trailing => return error("unexpected callback rate"),
+            Some(rate) if rate < Decimal::new(1, 1) || rate > Decimal::TEN => {
+                return error("callback rate must be between 0.1 and 10")
+            }
+            _ => {}
+        }
+        if self.activation_price.is_some() && !trailing {
+            return error("unexpected activation price");
+        }
+
+        let gtd = self.time_in_force == Some(TimeInForce::GTD);
+        if gtd != self.good_till_date.is_some() {
+            return error(if gtd {
+                "missing good till date"
+            } else {
+                "good till date requires the GTD time in force"
+            });
+        }
+        if matches!(
+            self.new_order_resp_type,
+            Some(OrderResponse::Full | OrderResponse::Other)
+        ) {
+            return error("the response type must be ACK or RESULT");
+        }
+        Ok(())
+    }
+}
+
+/// Modifies the price or quantity of a LIMIT order
+/// either order_id or orig_client_order_id must be set
+#[derive(Serialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModifyOrderRequest {
+    pub order_id: Option<u64>,
+    pub orig_client_order_id: Option<String>,
+    pub symbol: String,
+    /// Must be the side of the order
+    pub side: OrderSide,
+    pub quantity: Decimal,
+    pub price: Decimal,
+}
+
+impl ModifyOrderRequest {
+    pub fn new(symbol: impl Into<String>, order_id: u64, side: OrderSide, qty: Decimal, price: Decimal) -> Self {
+        Self {
+            order_id: Some(order_id),
+            symbol: symbol.into(),
+            side,
+            quantity: qty,
+            price,
+            ..Self::default()
+        }
+    }
+}
+
+/// Outcome of one order of a batch, the exchange accepts or rejects each order on its own
+pub type BatchOrderResult = std::result::Result<Transaction, BinanceContentError>;
+
+/// Maximum number of orders placed or modified in one batch
+pub const MAX_BATCH_ORDERS: usize = 5;
+/// Maximum number of orders canceled in one batch
+pub const MAX_BATCH_CANCELLATIONS: usize = 10;
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
-struct ChangePositionModeRequest {
+struct BatchOrdersRequest {
+    batch_orders: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BatchCancellationRequest {
+    symbol: String,
+    order_id_list: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ChangePositionModeRequest {
     #[serde(serialize_with = "serialize_as_str")]
     pub dual_side_position: bool,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ChangeMultiAssetsModeRequest {
+    #[serde(serialize_with = "serialize_as_str")]
+    pub multi_assets_margin: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ChangeMarginTypeRequest {
+    pub symbol: String,
+    pub margin_type: &'static str,
+}
+
+impl ChangeMarginTypeRequest {
+    pub(crate) fn new(symbol: String, margin_type: MarginType) -> Self {
+        Self {
+            symbol,
+            margin_type: match margin_type {
+                MarginType::Isolated => "ISOLATED",
+                MarginType::Cross => "CROSSED",
+            },
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PositionMarginRequest {
+    pub symbol: String,
+    pub position_side: Option<PositionSide>,
+    pub amount: Decimal,
+    #[serde(rename = "type")]
+    pub margin_type: PositionMarginType,
+}
+
+#[derive(Serialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionMarginHistoryQuery {
+    pub symbol: String,
+    #[serde(rename = "type")]
+    pub margin_type: Option<PositionMarginType>,
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    /// Default 500
+    pub limit: Option<u16>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CountdownCancelAllRequest {
+    pub symbol: String,
+    pub countdown_time: u64,
+}
+
+#[derive(Serialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomeQuery {
+    pub symbol: Option<String>,
+    pub income_type: Option<IncomeType>,
+    /// Only the last 7 days are returned when neither start nor end time are set
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    pub page: Option<u32>,
+    /// Default 100, max 1000
+    pub limit: Option<u16>,
+}
+
+#[derive(Serialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UserTradesQuery {
+    pub symbol: String,
+    /// Can only be used with the symbol
+    pub order_id: Option<u64>,
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    /// Cannot be sent with the start or end time
+    pub from_id: Option<u64>,
+    /// Default 500, max 1000
+    pub limit: Option<u16>,
+}
+
+#[derive(Serialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AllOrdersQuery {
+    pub symbol: String,
+    /// Returns orders from this id on, the most recent orders otherwise. Either this or the start
+    /// time is required by [`FuturesAccount::all_orders_paginated`]
+    pub order_id: Option<u64>,
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    /// Default 500, max 1000
+    pub limit: Option<u16>,
+}
+
+#[derive(Serialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ForceOrdersQuery {
+    pub symbol: Option<String>,
+    /// Both liquidations and auto-deleveraging orders are returned by default
+    pub auto_close_type: Option<AutoCloseType>,
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    /// Default 50, max 100
+    pub limit: Option<u16>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OptionalSymbolQuery {
+    pub symbol: Option<String>,
+}
+
+/// Maximum page size of the income, user trades and all orders endpoints
+pub const MAX_HISTORY_LIMIT: u16 = 1000;
+
 impl FuturesAccount {
     /// Get an order
     pub async fn get_order(&self, order: Option<GetOrderRequest>) -> Result<Transaction> {
@@ -91,6 +526,7 @@ impl FuturesAccount {
 
     /// Place an order
     pub async fn place_order(&self, order: OrderRequest) -> Result<Transaction> {
+        order.valid()?;
         self.client
             .post_signed_p("/fapi/v1/order", order, self.recv_window)
             .await
@@ -104,6 +540,7 @@ impl FuturesAccount {
 
     /// Place a test order    
     pub async fn place_order_test(&self, order: OrderRequest) -> Result<Transaction> {
+        order.valid()?;
         self.client
             .post_signed_p("/fapi/v1/order/test", order, self.recv_window)
             .await
@@ -118,21 +555,9 @@ impl FuturesAccount {
         time_in_force: TimeInForce,
     ) -> Result<Transaction> {
         let order = OrderRequest {
-            symbol: symbol.into(),
-            side: OrderSide::Buy,
-            position_side: None,
-            order_type: OrderType::Limit,
             time_in_force: Some(time_in_force),
-            quantity: Some(qty.into()),
-            reduce_only: None,
-            price: Some(price),
-            stop_price: None,
-            close_position: None,
-            activation_price: None,
-            callback_rate: None,
-            working_type: None,
-            price_protect: None,
-            new_client_order_id: None,
+            new_order_resp_type: Some(OrderResponse::Result),
+            ..OrderRequest::limit(symbol, OrderSide::Buy, qty.into(), price)
         };
         self.place_order(order).await
     }
@@ -146,21 +571,9 @@ impl FuturesAccount {
         time_in_force: TimeInForce,
     ) -> Result<Transaction> {
         let order = OrderRequest {
-            symbol: symbol.into(),
-            side: OrderSide::Sell,
-            position_side: None,
-            order_type: OrderType::Limit,
             time_in_force: Some(time_in_force),
-            quantity: Some(qty.into()),
-            reduce_only: None,
-            price: Some(price),
-            stop_price: None,
-            close_position: None,
-            activation_price: None,
-            callback_rate: None,
-            working_type: None,
-            price_protect: None,
-            new_client_order_id: None,
+            new_order_resp_type: Some(OrderResponse::Result),
+            ..OrderRequest::limit(symbol, OrderSide::Sell, qty.into(), price)
         };
         self.place_order(order).await
     }
@@ -172,21 +585,8 @@ impl FuturesAccount {
         F: Into<Decimal>,
     {
         let order = OrderRequest {
-            symbol: symbol.into(),
-            side: OrderSide::Buy,
-            position_side: None,
-            order_type: OrderType::Market,
-            time_in_force: None,
-            quantity: Some(qty.into()),
-            reduce_only: None,
-            price: None,
-            stop_price: None,
-            close_position: None,
-            activation_price: None,
-            callback_rate: None,
-            working_type: None,
-            price_protect: None,
-            new_client_order_id: None,
+            new_order_resp_type: Some(OrderResponse::Result),
+            ..OrderRequest::market(symbol, OrderSide::Buy, qty.into())
         };
         self.place_order(order).await
     }
@@ -197,22 +597,9 @@ impl FuturesAccount {
         S: Into<String>,
         F: Into<Decimal>,
     {
-        let order: OrderRequest = OrderRequest {
-            symbol: symbol.into(),
-            side: OrderSide::Sell,
-            position_side: None,
-            order_type: OrderType::Market,
-            time_in_force: None,
-            quantity: Some(qty.into()),
-            reduce_only: None,
-            price: None,
-            stop_price: None,
-            close_position: None,
-            activation_price: None,
-            callback_rate: None,
-            working_type: None,
-            price_protect: None,
-            new_client_order_id: None,
+        let order = OrderRequest {
+            new_order_resp_type: Some(OrderResponse::Result),
+            ..OrderRequest::market(symbol, OrderSide::Sell, qty.into())
         };
         self.place_order(order).await
     }
@@ -223,6 +610,73 @@ impl FuturesAccount {
         self.client.delete_signed_p("/fapi/v1/order", &o, recv_window).await
     }
 
+    /// Modify the price or quantity of an open LIMIT order
+    pub async fn modify_order(&self, order: ModifyOrderRequest) -> Result<Transaction> {
+        self.client
+            .put_signed_p("/fapi/v1/order", order, self.recv_window)
+            .await
+    }
+
+    /// Place up to [`MAX_BATCH_ORDERS`] orders in one request
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, config::*, futures::account::*, rest_model::OrderSide};
+    /// use rust_decimal_macros::dec;
+    /// let account: FuturesAccount = Binance::new_with_env(&Config::testnet());
+    /// let orders = vec![
+    ///     OrderRequest::limit("BTCUSDT", OrderSide::Buy, dec!(0.001), dec!(20000)),
+    ///     OrderRequest::limit("BTCUSDT", OrderSide::Buy, dec!(0.001), dec!(19900)),
+    /// ];
+    /// let results = tokio_test::block_on(account.place_batch_orders(orders));
+    /// assert!(results.is_ok(), "{:?}", results);
+    /// ```
+    pub async fn place_batch_orders(&self, orders: Vec<OrderRequest>) -> Result<Vec<BatchOrderResult>> {
+        for order in &orders {
+            order.valid()?;
+        }
+        let request = BatchOrdersRequest {
+            batch_orders: encode_orders(&orders, MAX_BATCH_ORDERS)?,
+        };
+        let entries = self
+            .client
+            .post_signed_p("/fapi/v1/batchOrders", request, self.recv_window)
+            .await?;
+        batch_results(entries)
+    }
+
+    /// Modify up to [`MAX_BATCH_ORDERS`] orders in one request
+    pub async fn modify_batch_orders(&self, orders: Vec<ModifyOrderRequest>) -> Result<Vec<BatchOrderResult>> {
+        let request = BatchOrdersRequest {
+            batch_orders: encode_orders(&orders, MAX_BATCH_ORDERS)?,
+        };
+        let entries = self
+            .client
+            .put_signed_p("/fapi/v1/batchOrders", request, self.recv_window)
+            .await?;
+        batch_results(entries)
+    }
+
+    /// Cancel up to [`MAX_BATCH_CANCELLATIONS`] orders of a symbol in one request
+    pub async fn cancel_batch_orders<S>(&self, symbol: S, order_ids: Vec<u64>) -> Result<Vec<BatchOrderResult>>
+    where
+        S: Into<String>,
+    {
+        if order_ids.is_empty() || order_ids.len() > MAX_BATCH_CANCELLATIONS {
+            return Err(Error::InvalidOrderError {
+                msg: format!("a batch must have between 1 and {MAX_BATCH_CANCELLATIONS} orders"),
+            });
+        }
+        let request = BatchCancellationRequest {
+            symbol: symbol.into(),
+            order_id_list: serde_json::to_string(&order_ids)?,
+        };
+        let entries = self
+            .client
+            .delete_signed_p("/fapi/v1/batchOrders", request, self.recv_window)
+            .await?;
+        batch_results(entries)
+    }
+
     /// Get current position risk for the symbol
     pub async fn position_information<S>(&self, symbol: S) -> Result<Vec<Position>>
     where
@@ -279,6 +733,221 @@ impl FuturesAccount {
         Ok(())
     }
 
+    /// Whether the account is in hedge mode, see [`FuturesAccount::change_position_mode`]
+    pub async fn position_mode(&self) -> Result<PositionMode> {
+        self.client
+            .get_signed_p("/fapi/v1/positionSide/dual", Option::<String>::None, self.recv_window)
+            .await
+    }
+
+    /// Change the multi-assets mode, in which margin is shared across assets
+    pub async fn change_multi_assets_mode(&self, multi_assets_margin: bool) -> Result<StatusResponse> {
+        self.client
+            .post_signed_p(
+                "/fapi/v1/multiAssetsMargin",
+                ChangeMultiAssetsModeRequest { multi_assets_margin },
+                self.recv_window,
+            )
+            .await
+    }
+
+    /// Whether the account is in multi-assets mode
+    pub async fn multi_assets_mode(&self) -> Result<MultiAssetsMode> {
+        self.client
+            .get_signed_p("/fapi/v1/multiAssetsMargin", Option::<String>::None, self.recv_window)
+            .await
+    }
+
+    /// Change the margin type of the symbol
+    pub async fn change_margin_type<S>(&self, symbol: S, margin_type: MarginType) -> Result<StatusResponse>
+    where
+        S: Into<String>,
+    {
+        let request = ChangeMarginTypeRequest::new(symbol.into(), margin_type);
+        self.client
+            .post_signed_p("/fapi/v1/marginType", request, self.recv_window)
+            .await
+    }
+
+    /// Add margin to or reduce margin of an isolated position, the position side is required in hedge mode
+    pub async fn modify_position_margin<S>(
+        &self,
+        symbol: S,
+        amount: Decimal,
+        margin_type: PositionMarginType,
+        position_side: Option<PositionSide>,
+    ) -> Result<PositionMarginResponse>
+    where
+        S: Into<String>,
+    {
+        let request = PositionMarginRequest {
+            symbol: symbol.into(),
+            position_side,
+            amount,
+            margin_type,
+        };
+        self.client
+            .post_signed_p("/fapi/v1/positionMargin", request, self.recv_window)
+            .await
+    }
+
+    /// Get the margin changes of isolated positions
+    pub async fn position_margin_history(
+        &self,
+        query: PositionMarginHistoryQuery,
+    ) -> Result<Vec<PositionMarginChange>> {
+        self.client
+            .get_signed_p("/fapi/v1/positionMargin/history", Some(query), self.recv_window)
+            .await
+    }
+
+    /// Cancel all open orders of the symbol once the countdown expires, unless it is renewed by
+    /// calling this again, a countdown of 0 stops it
+    pub async fn countdown_cancel_all<S>(&self, symbol: S, countdown_time: u64) -> Result<CountdownCancelAll>
+    where
+        S: Into<String>,
+    {
+        let request = CountdownCancelAllRequest {
+            symbol: symbol.into(),
+            countdown_time,
+        };
+        self.client
+            .post_signed_p("/fapi/v1/countdownCancelAll", request, self.recv_window)
+            .await
+    }
+
+    /// Get current position risk for all symbols
+    pub async fn all_position_information(&self) -> Result<Vec<Position>> {
+        self.client
+            .get_signed_p("/fapi/v2/positionRisk", Option::<String>::None, self.recv_window)
+            .await
+    }
+
+    /// Get one page of income history, such as realized PnL, funding fees and commissions
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, config::*, futures::account::*, futures::rest_model::IncomeType};
+    /// let account: FuturesAccount = Binance::new_with_env(&Config::testnet());
+    /// let query = IncomeQuery {
+    ///     income_type: Some(IncomeType::FundingFee),
+    ///     ..IncomeQuery::default()
+    /// };
+    /// let incomes = tokio_test::block_on(account.income(query));
+    /// assert!(incomes.is_ok(), "{:?}", incomes);
+    /// ```
+    pub async fn income(&self, query: IncomeQuery) -> Result<Vec<Income>> {
+        self.client
+            .get_signed_p("/fapi/v1/income", Some(query), self.recv_window)
+            .await
+    }
+
+    /// Get the whole income history matching the query, requesting one page after the other
+    pub async fn income_paginated(&self, mut query: IncomeQuery) -> Result<Vec<Income>> {
+        let limit = query.limit.unwrap_or(MAX_HISTORY_LIMIT);
+        query.limit = Some(limit);
+        let mut page = query.page.unwrap_or(1);
+        let mut incomes = Vec::new();
+        loop {
+            query.page = Some(page);
+            let batch = self.income(query.clone()).await?;
+            let last_page = batch.len() < limit as usize;
+            incomes.extend(batch);
+            if last_page {
+                return Ok(incomes);
+            }
+            page += 1;
+        }
+    }
+
+    /// Get one page of trades of the symbol
+    pub async fn user_trades(&self, query: UserTradesQuery) -> Result<Vec<UserTrade>> {
+        self.client
+            .get_signed_p("/fapi/v1/userTrades", Some(query), self.recv_window)
+            .await
+    }
+
+    /// Get all the trades matching the query, requesting pages by trade id
+    pub async fn user_trades_paginated(&self, mut query: UserTradesQuery) -> Result<Vec<UserTrade>> {
+        let limit = query.limit.unwrap_or(MAX_HISTORY_LIMIT);
+        query.limit = Some(limit);
+        let end_time = query.end_time;
+        let mut trades: Vec<UserTrade> = Vec::new();
+        loop {
+            let batch = self.user_trades(query.clone()).await?;
+            let last_page = batch.len() < limit as usize;
+            // Pages after the first are requested by id, which cannot be combined with a time range
+            let past_end = |trade: &UserTrade| matches!(end_time, Some(end) if trade.time > end);
+            let reached_end = batch.iter().any(past_end);
+            trades.extend(batch.into_iter().filter(|trade| !past_end(trade)));
+            match trades.last() {
+                Some(trade) if !last_page && !reached_end => {
+                    query.from_id = Some(trade.id + 1);
+                    query.start_time = None;
+                    query.end_time = None;
+                }
+                _ => return Ok(trades),
+            }
+        }
+    }
+
+    /// Get one page of orders of the symbol, in any status
+    pub async fn all_orders(&self, query: AllOrdersQuery) -> Result<Vec<Order>> {
+        self.client
+            .get_signed_p("/fapi/v1/allOrders", Some(query), self.recv_window)
+            .await
+    }
+
+    /// Get all the orders matching the query, requesting pages by order id from the order id or the
+    /// start time of the query, one of which is required since the pages can only go forward
+    pub async fn all_orders_paginated(&self, mut query: AllOrdersQuery) -> Result<Vec<Order>> {
+        if query.order_id.is_none() && query.start_time.is_none() {
+            return Err(Error::Msg(
+                "paginating all orders requires an order id or a start time".to_string(),
+            ));
+        }
+        let limit = query.limit.unwrap_or(MAX_HISTORY_LIMIT);
+        query.limit = Some(limit);
+        let mut orders: Vec<Order> = Vec::new();
+        loop {
+            let batch = self.all_orders(query.clone()).await?;
+            let last_page = batch.len() < limit as usize;
+            orders.extend(batch);
+            match orders.last() {
+                Some(order) if !last_page => query.order_id = Some(order.order_id + 1),
+                _ => return Ok(orders),
+            }
+        }
+    }
+
+    /// Get the maker and taker commission rates of the symbol
+    pub async fn commission_rate<S>(&self, symbol: S) -> Result<CommissionRate>
+    where
+        S: Into<String>,
+    {
+        self.client
+            .get_signed_p(
+                "/fapi/v1/commissionRate",
+                Some(PairQuery { symbol: symbol.into() }),
+                self.recv_window,
+            )
+            .await
+    }
+
+    /// Get the auto-deleveraging queue position of the symbol, or of every symbol with an open position
+    pub async fn adl_quantile(&self, symbol: Option<String>) -> Result<Vec<AdlQuantile>> {
+        let query = OptionalSymbolQuery { symbol };
+        self.client
+            .get_signed_p("/fapi/v1/adlQuantile", Some(query), self.recv_window)
+            .await
+    }
+
+    /// Get the liquidation and auto-deleveraging orders of the account
+    pub async fn force_orders(&self, query: ForceOrdersQuery) -> Result<Vec<Order>> {
+        self.client
+            .get_signed_p("/fapi/v1/forceOrders", Some(query), self.recv_window)
+            .await
+    }
+
     /// Cancel all open orders on this symbol
     pub async fn cancel_all_open_orders<S>(&self, symbol: S) -> Result<()>
     where
@@ -294,3 +963,141 @@ impl FuturesAccount {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn order_specs_build_valid_orders() {
+        let specs = [
+            OrderSpec::Limit {
+                qty: dec!(0.01),
+                price: dec!(20000),
+            },
+            OrderSpec::GoodTillDate {
+                qty: dec!(0.01),
+                price: dec!(20000),
+                good_till_date: 1_700_000_000_000,
+            },
+            OrderSpec::Market { qty: dec!(0.01) },
+            OrderSpec::Stop {
+                qty: dec!(0.01),
+                price: dec!(18900),
+                stop_price: dec!(19000),
+            },
+            OrderSpec::StopMarket {
+                qty: dec!(0.01),
+                stop_price: dec!(19000),
+            },
+            OrderSpec::TakeProfit {
+                qty: dec!(0.01),
+                price: dec!(21100),
+                stop_price: dec!(21000),
+            },
+            OrderSpec::TakeProfitMarket {
+                qty: dec!(0.01),
+                stop_price: dec!(21000),
+            },
+            OrderSpec::TrailingStopMarket {
+                qty: dec!(0.01),
+                callback_rate: dec!(1),
+            },
+        ];
+        for spec in specs {
+            let order = OrderRequest::new("BTCUSDT", OrderSide::Sell, spec);
+            assert!(order.valid().is_ok(), "{order:?}");
+        }
+    }
+
+    #[test]
+    fn order_request_valid() {
+        let invalid = |order: OrderRequest| assert!(order.valid().is_err(), "{order:?}");
+        invalid(OrderRequest {
+            close_position: Some(true),
+            ..OrderRequest::stop_market("BTCUSDT", OrderSide::Sell, dec!(0.01), dec!(19000))
+        });
+        invalid(OrderRequest {
+            callback_rate: None,
+            ..OrderRequest::trailing_stop_market("BTCUSDT", OrderSide::Sell, dec!(0.01), dec!(1))
+        });
+        invalid(OrderRequest {
+            reduce_only: Some(true),
+            position_side: Some(PositionSide::Long),
+            ..OrderRequest::market("BTCUSDT", OrderSide::Sell, dec!(0.01))
+        });
+        invalid(OrderRequest {
+            price_match: Some(PriceMatch::Queue),
+            ..OrderRequest::limit("BTCUSDT", OrderSide::Buy, dec!(0.01), dec!(20000))
+        });
+        invalid(OrderRequest {
+            time_in_force: Some(TimeInForce::GTD),
+            ..OrderRequest::limit("BTCUSDT", OrderSide::Buy, dec!(0.01), dec!(20000))
+        });
+
+        assert!(
+            OrderRequest::good_till_date("BTCUSDT", OrderSide::Buy, dec!(0.01), dec!(20000), 1_700_000_000_000)
+                .valid()
+                .is_ok()
+        );
+        assert!(OrderRequest {
+            close_position: Some(true),
+            quantity: None,
+            ..OrderRequest::stop_market("BTCUSDT", OrderSide::Sell, dec!(0.01), dec!(19000))
+        }
+        .valid()
+        .is_ok());
+        assert!(OrderRequest {
+            price: None,
+            price_match: Some(PriceMatch::Opponent5),
+            self_trade_prevention_mode: Some(SelfTradePreventionMode::ExpireMaker),
+            ..OrderRequest::limit("BTCUSDT", OrderSide::Buy, dec!(0.01), dec!(20000))
+        }
+        .valid()
+        .is_ok());
+        assert!(OrderRequest {
+            reduce_only: Some(true),
+            ..OrderRequest::market("BTCUSDT", OrderSide::Sell, dec!(0.01))
+        }
+        .valid()
+        .is_ok());
+    }
+
+    #[test]
+    fn history_queries() {
+        let income = IncomeQuery {
+            symbol: Some("BTCUSDT".to_string()),
+            income_type: Some(IncomeType::DeliveredSettlement),
+            page: Some(2),
+            ..IncomeQuery::default()
+        };
+        assert_eq!(
+            build_request_p(income).unwrap(),
+            "symbol=BTCUSDT&incomeType=DELIVERED_SETTELMENT&page=2"
+        );
+        let force_orders = ForceOrdersQuery {
+            auto_close_type: Some(AutoCloseType::Adl),
+            limit: Some(100),
+            ..ForceOrdersQuery::default()
+        };
+        assert_eq!(build_request_p(force_orders).unwrap(), "autoCloseType=ADL&limit=100");
+        let trades = UserTradesQuery {
+            symbol: "BTCUSDT".to_string(),
+            from_id: Some(7),
+            ..UserTradesQuery::default()
+        };
+        assert_eq!(build_request_p(trades).unwrap(), "symbol=BTCUSDT&fromId=7");
+    }
+
+    #[test]
+    fn all_orders_pagination_requires_a_start_point() {
+        let account: FuturesAccount = crate::api::Binance::new(None, None);
+        let query = AllOrdersQuery {
+            symbol: "BTCUSDT".to_string(),
+            ..AllOrdersQuery::default()
+        };
+        let result = tokio_test::block_on(account.all_orders_paginated(query));
+        assert!(matches!(result, Err(Error::Msg(_))), "{result:?}");
+    }
+}
@@ -0,0 +1,771 @@
+//! Client-side bracket orders for USD-M futures.
+//!
+//! Futures have no native OCO, so [`BracketManager`] links an entry order to a reduce-only
+//! STOP_MARKET stop loss and TAKE_PROFIT_MARKET take profit itself. The legs are placed once the
+//! entry fills, as reported by `ORDER_TRADE_UPDATE` events, and resized whenever the open quantity
+//! changes, so that partial fills of the entry or of a leg are always covered. Once a leg fills,
+//! the other one is canceled.
+//!
+//! Every order of a bracket carries a client order id `{id}-{leg}`, where the id comes from a
+//! [`ClientOrderIdGenerator`], so that the brackets can be recovered from the open orders after a
+//! restart. The decisions are made by [`BracketManager::apply_update`], which returns the
+//! [`BracketAction`]s to send, so they can be tested and inspected without a connection.
+
+use std::collections::HashMap;
+
+use rust_decimal::Decimal;
+
+use crate::account::OrderCancellation;
+use crate::errors::*;
+use crate::futures::account::{FuturesAccount, OrderRequest};
+use crate::futures::rest_model::{Order, PositionSide, Transaction, WorkingType};
+use crate::futures::ws_model::{OrderTradeUpdate, WebsocketEvent};
+use crate::order_tracker::is_final;
+use crate::rest_model::{OrderSide, OrderStatus};
+use crate::submission::{is_unknown_order, ClientOrderIdGenerator};
+
+/// Cancellation of an order which is no longer open
+const CANCEL_REJECTED: i32 = -2011;
+/// Room left in client order ids for the leg tags, `-E`, or `-S` and `-T` with a version of up to
+/// 4 digits
+const LEG_SUFFIX_LEN: usize = 6;
+
+/// An order of a bracket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BracketLeg {
+    Entry,
+    StopLoss,
+    TakeProfit,
+}
+
+impl BracketLeg {
+    fn tag(&self) -> char {
+        match self {
+            BracketLeg::Entry => 'E',
+            BracketLeg::StopLoss => 'S',
+            BracketLeg::TakeProfit => 'T',
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BracketState {
+    /// Nothing of the entry is filled yet
+    Pending,
+    /// A position is open and protected by the legs
+    Active,
+    /// The position was closed by a leg
+    Closed(BracketLeg),
+    /// The entry was canceled before any fill, or the bracket was canceled
+    Canceled,
+}
+
+/// An entry order and the prices of its exits
+#[derive(Debug, Clone)]
+pub struct BracketRequest {
+    /// Order opening the position, its client order id is replaced by the bracket's
+    pub entry: OrderRequest,
+    pub stop_price: Decimal,
+    pub take_profit_price: Decimal,
+    /// Price triggering the legs, the contract price by default
+    pub working_type: Option<WorkingType>,
+}
+
+/// A stop loss or take profit order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExitOrder {
+    pub client_order_id: String,
+    pub qty: Decimal,
+}
+
+/// An order to send on behalf of a bracket
+#[derive(Debug, Clone)]
+pub enum BracketAction {
+    Place(OrderRequest),
+    Cancel { symbol: String, client_order_id: String },
+}
+
+/// State of an entry and its exits
+#[derive(Debug, Clone)]
+pub struct Bracket {
+    pub id: String,
+    pub symbol: String,
+    /// Side of the entry, the legs are on the other side
+    pub side: OrderSide,
+    /// Side of the position in hedge mode
+    pub position_side: Option<PositionSide>,
+    /// Unknown for a bracket recovered before its legs were placed, see
+    /// [`BracketManager::set_exit_prices`]
+    pub stop_price: Option<Decimal>,
+    pub take_profit_price: Option<Decimal>,
+    pub working_type: Option<WorkingType>,
+    pub entry_qty: Decimal,
+    pub entry_filled: Decimal,
+    /// Whether the entry can no longer fill
+    pub entry_done: bool,
+    pub stop_loss: Option<ExitOrder>,
+    pub take_profit: Option<ExitOrder>,
+    pub state: BracketState,
+    /// Cumulative filled quantity of every exit order, including the replaced ones
+    exit_fills: HashMap<String, Decimal>,
+    next_version: u32,
+}
+
+impl Bracket {
+    fn new(id: String, symbol: String, side: OrderSide, position_side: Option<PositionSide>) -> Self {
+        Self {
+            id,
+            symbol,
+            side,
+            position_side,
+            stop_price: None,
+            take_profit_price: None,
+            working_type: None,
+            entry_qty: Decimal::ZERO,
+            entry_filled: Decimal::ZERO,
+            entry_done: false,
+            stop_loss: None,
+            take_profit: None,
+            state: BracketState::Pending,
+            exit_fills: HashMap::new(),
+            next_version: 0,
+        }
+    }
+
+    /// Quantity closed by the legs
+    pub fn exited_qty(&self) -> Decimal { self.exit_fills.values().sum() }
+
+    /// Quantity of the position opened by the entry and not closed yet
+    pub fn open_qty(&self) -> Decimal { (self.entry_filled - self.exited_qty()).max(Decimal::ZERO) }
+
+    pub fn is_final(&self) -> bool { matches!(self.state, BracketState::Closed(_) | BracketState::Canceled) }
+
+    fn entry_client_order_id(&self) -> String { format!("{}-{}", self.id, BracketLeg::Entry.tag()) }
+
+    fn exit(&mut self, leg: BracketLeg) -> &mut Option<ExitOrder> {
+        match leg {
+            BracketLeg::TakeProfit => &mut self.take_profit,
+            _ => &mut self.stop_loss,
+        }
+    }
+
+    /// Quantity an exit order can still fill
+    fn remaining(&self, exit: &ExitOrder) -> Decimal {
+        exit.qty - self.exit_fills.get(&exit.client_order_id).copied().unwrap_or_default()
+    }
+
+    fn cancel_action(&self, client_order_id: String) -> BracketAction {
+        BracketAction::Cancel {
+            symbol: self.symbol.clone(),
+            client_order_id,
+        }
+    }
+
+    fn exit_order(&self, leg: BracketLeg, client_order_id: String, qty: Decimal, price: Decimal) -> OrderRequest {
+        let side = match self.side {
+            OrderSide::Buy => OrderSide::Sell,
+            OrderSide::Sell => OrderSide::Buy,
+        };
+        let order = match leg {
+            BracketLeg::TakeProfit => OrderRequest::take_profit_market(&self.symbol, side, qty, price),
+            _ => OrderRequest::stop_market(&self.symbol, side, qty, price),
+        };
+        // In hedge mode the position side alone makes the order reduce the position
+        let hedge = matches!(self.position_side, Some(PositionSide::Long | PositionSide::Short));
+        OrderRequest {
+            position_side: self.position_side.clone(),
+            reduce_only: (!hedge).then_some(true),
+            working_type: self.working_type.clone(),
+            new_client_order_id: Some(client_order_id),
+            ..order
+        }
+    }
+
+    /// Places, resizes or cancels the legs so that they cover the open quantity
+    fn sync_exits(&mut self) -> Vec<BracketAction> {
+        let mut actions = vec![];
+        if self.is_final() {
+            return actions;
+        }
+        if self.entry_done && self.entry_filled.is_zero() {
+            self.state = BracketState::Canceled;
+            return actions;
+        }
+        let open_qty = self.open_qty();
+        for (leg, price) in [
+            (BracketLeg::StopLoss, self.stop_price),
+            (BracketLeg::TakeProfit, self.take_profit_price),
+        ] {
+            let Some(price) = price else {
+                continue;
+            };
+            let current = self.exit(leg).clone();
+            if current.as_ref().map(|exit| self.remaining(exit)).unwrap_or_default() == open_qty {
+                continue;
+            }
+            if let Some(exit) = self.exit(leg).take() {
+                actions.push(self.cancel_action(exit.client_order_id));
+            }
+            if open_qty > Decimal::ZERO {
+                let client_order_id = format!("{}-{}{}", self.id, leg.tag(), self.next_version);
+                self.next_version += 1;
+                actions.push(BracketAction::Place(self.exit_order(
+                    leg,
+                    client_order_id.clone(),
+                    open_qty,
+                    price,
+                )));
+                *self.exit(leg) = Some(ExitOrder {
+                    client_order_id,
+                    qty: open_qty,
+                });
+            }
+        }
+        if self.stop_loss.is_some() || self.take_profit.is_some() {
+            self.state = BracketState::Active;
+        }
+        actions
+    }
+
+    /// Cancels whatever is still open and marks the bracket as final
+    fn close(&mut self, state: BracketState) -> Vec<BracketAction> {
+        let mut actions = vec![];
+        if !self.entry_done {
+            actions.push(self.cancel_action(self.entry_client_order_id()));
+        }
+        for leg in [BracketLeg::StopLoss, BracketLeg::TakeProfit] {
+            if let Some(exit) = self.exit(leg).take() {
+                actions.push(self.cancel_action(exit.client_order_id));
+            }
+        }
+        self.state = state;
+        actions
+    }
+
+    fn apply_entry(&mut self, filled: Decimal, status: &OrderStatus) -> Vec<BracketAction> {
+        self.entry_filled = self.entry_filled.max(filled);
+        self.entry_done |= is_final(status);
+        self.sync_exits()
+    }
+
+    fn apply_exit(
+        &mut self,
+        leg: BracketLeg,
+        client_order_id: &str,
+        filled: Decimal,
+        status: &OrderStatus,
+    ) -> Vec<BracketAction> {
+        let fill = self.exit_fills.entry(client_order_id.to_string()).or_default();
+        *fill = (*fill).max(filled);
+        let is_current = self.exit(leg).as_ref().map(|exit| exit.client_order_id.as_str()) == Some(client_order_id);
+        if (is_current && *status == OrderStatus::Filled) || (self.entry_done && self.open_qty().is_zero()) {
+            if is_current {
+                self.exit(leg).take();
+            }
+            return self.close(BracketState::Closed(leg));
+        }
+        if is_current && is_final(status) {
+            // Canceled or expired by the exchange, it is not placed again
+            self.exit(leg).take();
+            return vec![];
+        }
+        self.sync_exits()
+    }
+}
+
+/// Places and follows bracket orders
+#[derive(Debug)]
+pub struct BracketManager {
+    ids: ClientOrderIdGenerator,
+    brackets: HashMap<String, Bracket>,
+}
+
+impl BracketManager {
+    /// Creates a manager tagging its orders with a prefix, which must not be used by other orders
+    pub fn new<S: Into<String>>(prefix: S) -> Result<Self> { Self::with_ids(ClientOrderIdGenerator::new(prefix)?) }
+
+    /// Creates a manager generating its bracket ids with these ids, which must leave room for the
+    /// leg suffixes
+    pub fn with_ids(ids: ClientOrderIdGenerator) -> Result<Self> {
+        Ok(Self {
+            ids: ids.reserve_suffix(LEG_SUFFIX_LEN)?,
+            brackets: HashMap::new(),
+        })
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Bracket> { self.brackets.get(id) }
+
+    pub fn brackets(&self) -> impl Iterator<Item = &Bracket> { self.brackets.values() }
+
+    /// Stops following the brackets which are closed or canceled, and returns them
+    pub fn remove_final(&mut self) -> Vec<Bracket> {
+        let ids: Vec<String> = self
+            .brackets
+            .values()
+            .filter(|b| b.is_final())
+            .map(|b| b.id.clone())
+            .collect();
+        ids.iter().filter_map(|id| self.brackets.remove(id)).collect()
+    }
+
+    /// Registers a bracket and returns its entry order, tagged with the bracket's client order id
+    pub fn open(&mut self, request: BracketRequest) -> Result<OrderRequest> {
+        request.entry.valid()?;
+        let Some(qty) = request.entry.quantity else {
+            return Err(Error::InvalidOrderError {
+                msg: "the entry of a bracket must have a quantity".to_string(),
+            });
+        };
+        let mut bracket = Bracket::new(
+            self.ids.next_id(),
+            request.entry.symbol.clone(),
+            request.entry.side.clone(),
+            request.entry.position_side.clone(),
+        );
+        bracket.entry_qty = qty;
+        bracket.stop_price = Some(request.stop_price);
+        bracket.take_profit_price = Some(request.take_profit_price);
+        bracket.working_type = request.working_type;
+        let entry = OrderRequest {
+            new_client_order_id: Some(bracket.entry_client_order_id()),
+            ..request.entry
+        };
+        self.brackets.insert(bracket.id.clone(), bracket);
+        Ok(entry)
+    }
+
+    /// Registers a bracket and places its entry, the legs follow with the fills
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, config::*, futures::account::*, futures::bracket::*, rest_model::OrderSide};
+    /// use rust_decimal_macros::dec;
+    /// let account: FuturesAccount = Binance::new_with_env(&Config::testnet());
+    /// let mut brackets = BracketManager::new("brk").unwrap();
+    /// let request = BracketRequest {
+    ///     entry: OrderRequest::limit("BTCUSDT", OrderSide::Buy, dec!(0.01), dec!(20000)),
+    ///     stop_price: dec!(19000),
+    ///     take_profit_price: dec!(22000),
+    ///     working_type: None,
+    /// };
+    /// let placed = tokio_test::block_on(brackets.place(&account, request));
+    /// assert!(placed.is_ok(), "{:?}", placed);
+    /// ```
+    pub async fn place(&mut self, account: &FuturesAccount, request: BracketRequest) -> Result<Transaction> {
+        let entry = self.open(request)?;
+        let id = entry.new_client_order_id.as_deref().and_then(|c| self.parse(c));
+        let placed = account.place_order(entry).await;
+        if let (Err(_), Some((id, ..))) = (&placed, id) {
+            self.brackets.remove(&id);
+        }
+        placed
+    }
+
+    /// Sets the exit prices of a bracket, and moves its legs to them
+    pub fn set_exit_prices(&mut self, id: &str, stop_price: Decimal, take_profit_price: Decimal) -> Vec<BracketAction> {
+        let Some(bracket) = self.brackets.get_mut(id) else {
+            return vec![];
+        };
+        let mut actions = vec![];
+        for leg in [BracketLeg::StopLoss, BracketLeg::TakeProfit] {
+            if let Some(exit) = bracket.exit(leg).take() {
+                actions.push(bracket.cancel_action(exit.client_order_id));
+            }
+        }
+        bracket.stop_price = Some(stop_price);
+        bracket.take_profit_price = Some(take_profit_price);
+        actions.extend(bracket.sync_exits());
+        actions
+    }
+
+    /// Cancels the entry and legs of a bracket, an open position is left as is
+    pub fn cancel(&mut self, id: &str) -> Vec<BracketAction> {
+        match self.brackets.get_mut(id) {
+            Some(bracket) if !bracket.is_final() => bracket.close(BracketState::Canceled),
+            _ => vec![],
+        }
+    }
+
+    /// Applies an `ORDER_TRADE_UPDATE` event, returning the orders to place and cancel, events of
+    /// other orders are ignored
+    pub fn apply_update(&mut self, update: &OrderTradeUpdate) -> Vec<BracketAction> {
+        let order = &update.order;
+        let Some((id, leg, _)) = self.parse(&order.client_order_id) else {
+            return vec![];
+        };
+        let Some(bracket) = self.brackets.get_mut(&id) else {
+            return vec![];
+        };
+        if bracket.is_final() {
+            return vec![];
+        }
+        match leg {
+            BracketLeg::Entry => bracket.apply_entry(order.order_filled_accumulated_quantity, &order.order_status),
+            leg => bracket.apply_exit(
+                leg,
+                &order.client_order_id,
+                order.order_filled_accumulated_quantity,
+                &order.order_status,
+            ),
+        }
+    }
+
+    /// Sends the actions, cancellations of orders which are no longer open are ignored.
+    ///
+    /// A failed action does not stop the others, and the first error is returned once all of them
+    /// were sent. A leg which could not be placed is forgotten, so that it is placed again with
+    /// the next update of its bracket.
+    pub async fn execute(&mut self, account: &FuturesAccount, actions: Vec<BracketAction>) -> Result<()> {
+        let mut first_error = None;
+        for action in actions {
+            match action {
+                BracketAction::Place(order) => {
+                    let client_order_id = order.new_client_order_id.clone().unwrap_or_default();
+                    if let Err(e) = account.place_order(order).await {
+                        self.forget_exit(&client_order_id);
+                        first_error.get_or_insert(e);
+                    }
+                }
+                BracketAction::Cancel {
+                    symbol,
+                    client_order_id,
+                } => {
+                    let cancellation = OrderCancellation {
+                        symbol,
+                        orig_client_order_id: Some(client_order_id),
+                        ..OrderCancellation::default()
+                    };
+                    match account.cancel_order(cancellation).await {
+                        Err(e) if !is_unknown_order(&e) && !is_cancel_rejected(&e) => {
+                            first_error.get_or_insert(e);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+
+    /// Drops a leg which was not placed
+    fn forget_exit(&mut self, client_order_id: &str) {
+        let Some((id, leg, _)) = self.parse(client_order_id) else {
+            return;
+        };
+        if let Some(bracket) = self.brackets.get_mut(&id) {
+            let exit = bracket.exit(leg);
+            if exit.as_ref().map(|exit| exit.client_order_id.as_str()) == Some(client_order_id) {
+                exit.take();
+            }
+        }
+    }
+
+    /// Applies a user stream event and sends the resulting orders
+    pub async fn handle_event(&mut self, account: &FuturesAccount, event: &WebsocketEvent) -> Result<()> {
+        if let WebsocketEvent::OrderTradeUpdate(update) = event {
+            let actions = self.apply_update(update);
+            self.execute(account, actions).await?;
+        }
+        Ok(())
+    }
+
+    /// Rebuilds the brackets from the open orders tagged by this manager's prefix, and returns
+    /// the actions repairing their legs.
+    ///
+    /// A bracket whose entry is the only open order gets its exit prices back with
+    /// [`BracketManager::set_exit_prices`]. Brackets with a single leg left are not given a new
+    /// one, since the price of the missing leg is unknown.
+    pub fn recover_orders(&mut self, open_orders: &[Order]) -> Vec<BracketAction> {
+        let mut recovered: HashMap<String, Bracket> = HashMap::new();
+        for order in open_orders {
+            let Some((id, leg, version)) = self.parse(&order.client_order_id) else {
+                continue;
+            };
+            if self.brackets.contains_key(&id) {
+                continue;
+            }
+            let side = match (leg, &order.side) {
+                (BracketLeg::Entry, side) => side.clone(),
+                (_, OrderSide::Buy) => OrderSide::Sell,
+                (_, OrderSide::Sell) => OrderSide::Buy,
+            };
+            let position_side = match order.position_side {
+                PositionSide::Both => None,
+                ref side => Some(side.clone()),
+            };
+            let bracket = recovered
+                .entry(id.clone())
+                .or_insert_with(|| Bracket::new(id, order.symbol.clone(), side, position_side));
+            match leg {
+                BracketLeg::Entry => {
+                    bracket.entry_qty = order.orig_qty;
+                    bracket.entry_filled = order.executed_qty;
+                }
+                leg => {
+                    match leg {
+                        BracketLeg::StopLoss => bracket.stop_price = Some(order.stop_price),
+                        _ => bracket.take_profit_price = Some(order.stop_price),
+                    }
+                    bracket.working_type = Some(order.working_type.clone());
+                    bracket
+                        .exit_fills
+                        .insert(order.client_order_id.clone(), order.executed_qty);
+                    *bracket.exit(leg) = Some(ExitOrder {
+                        client_order_id: order.client_order_id.clone(),
+                        qty: order.orig_qty,
+                    });
+                    bracket.next_version = bracket.next_version.max(version + 1);
+                }
+            }
+        }
+
+        let mut actions = vec![];
+        for (id, mut bracket) in recovered {
+            let entry_open = open_orders
+                .iter()
+                .any(|o| o.client_order_id == bracket.entry_client_order_id());
+            if !entry_open {
+                // The entry is done, the legs were sized to the position when last placed
+                bracket.entry_done = true;
+                let covered = [&bracket.stop_loss, &bracket.take_profit]
+                    .into_iter()
+                    .flatten()
+                    .map(|exit| exit.qty)
+                    .max()
+                    .unwrap_or_default();
+                bracket.entry_filled = covered;
+                bracket.entry_qty = covered;
+            }
+            if bracket.stop_loss.is_some() || bracket.take_profit.is_some() {
+                bracket.state = BracketState::Active;
+            }
+            actions.extend(bracket.sync_exits());
+            self.brackets.insert(id, bracket);
+        }
+        actions
+    }
+
+    /// Rebuilds the brackets of a symbol from its open orders and repairs their legs
+    pub async fn recover(&mut self, account: &FuturesAccount, symbol: &str) -> Result<()> {
+        let open_orders = account.get_open_orders(symbol).await?;
+        let actions = self.recover_orders(&open_orders);
+        self.execute(account, actions).await
+    }
+
+    /// Splits a client order id of this manager into the bracket id, leg and version
+    fn parse(&self, client_order_id: &str) -> Option<(String, BracketLeg, u32)> {
+        if !self.ids.owns(client_order_id) {
+            return None;
+        }
+        let (id, tag) = client_order_id.rsplit_once('-')?;
+        let mut chars = tag.chars();
+        let leg = match chars.next()? {
+            'E' => BracketLeg::Entry,
+            'S' => BracketLeg::StopLoss,
+            'T' => BracketLeg::TakeProfit,
+            _ => return None,
+        };
+        let version = match chars.as_str() {
+            "" => 0,
+            version => version.parse().ok()?,
+        };
+        Some((id.to_string(), leg, version))
+    }
+}
+
+fn is_cancel_rejected(error: &Error) -> bool {
+    matches!(error, Error::BinanceError { response } if response.code == CANCEL_REJECTED)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::api::Binance;
+    use rust_decimal_macros::dec;
+
+    fn manager() -> BracketManager {
+        BracketManager::with_ids(ClientOrderIdGenerator::with_session("brk", "s").unwrap()).unwrap()
+    }
+
+    fn update(client_order_id: &str, status: &str, filled: &str) -> OrderTradeUpdate {
+        serde_json::from_str(&format!(
+            r#"{{"E": 1, "T": 1, "o": {{
+                "s": "BTCUSDT", "c": "{client_order_id}", "S": "BUY", "o": "LIMIT", "f": "GTC", "q": "1",
+                "p": "20000", "ap": "20000", "sp": "0", "x": "TRADE", "X": "{status}", "i": 1, "l": "0",
+                "z": "{filled}", "L": "20000", "T": 1, "t": 1, "b": "0", "a": "0", "m": false, "R": false,
+                "wt": "CONTRACT_PRICE", "ot": "LIMIT", "ps": "BOTH", "cp": false, "pP": false, "rp": "0",
+                "V": "NONE", "pm": "NONE", "gtd": 0
+            }}}}"#
+        ))
+        .unwrap()
+    }
+
+    fn placed(actions: &[BracketAction]) -> Vec<(String, Decimal)> {
+        actions
+            .iter()
+            .filter_map(|action| match action {
+                BracketAction::Place(order) => Some((order.new_client_order_id.clone()?, order.quantity?)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn canceled(actions: &[BracketAction]) -> Vec<String> {
+        actions
+            .iter()
+            .filter_map(|action| match action {
+                BracketAction::Cancel { client_order_id, .. } => Some(client_order_id.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn bracket_lifecycle() {
+        let mut brackets = manager();
+        let entry = brackets
+            .open(BracketRequest {
+                entry: OrderRequest::limit("BTCUSDT", OrderSide::Buy, dec!(1), dec!(20000)),
+                stop_price: dec!(19000),
+                take_profit_price: dec!(22000),
+                working_type: None,
+            })
+            .unwrap();
+        assert_eq!(entry.new_client_order_id.as_deref(), Some("brks-0-E"));
+
+        assert!(brackets.apply_update(&update("brks-0-E", "NEW", "0")).is_empty());
+        let actions = brackets.apply_update(&update("brks-0-E", "PARTIALLY_FILLED", "0.4"));
+        assert_eq!(
+            placed(&actions),
+            vec![
+                ("brks-0-S0".to_string(), dec!(0.4)),
+                ("brks-0-T1".to_string(), dec!(0.4))
+            ]
+        );
+        let BracketAction::Place(stop_loss) = &actions[0] else {
+            panic!("{actions:?}");
+        };
+        assert_eq!(stop_loss.side, OrderSide::Sell);
+        assert_eq!(stop_loss.reduce_only, Some(true));
+        assert!(stop_loss.valid().is_ok());
+
+        // The legs are resized to the filled quantity
+        let actions = brackets.apply_update(&update("brks-0-E", "FILLED", "1"));
+        assert_eq!(canceled(&actions), vec!["brks-0-S0", "brks-0-T1"]);
+        assert_eq!(
+            placed(&actions),
+            vec![("brks-0-S2".to_string(), dec!(1)), ("brks-0-T3".to_string(), dec!(1))]
+        );
+        // A fill reported by the canceled leg still counts
+        let actions = brackets.apply_update(&update("brks-0-T1", "CANCELED", "0"));
+        assert!(actions.is_empty());
+
+        // A partial take profit shrinks the stop loss
+        let actions = brackets.apply_update(&update("brks-0-T3", "PARTIALLY_FILLED", "0.3"));
+        assert_eq!(canceled(&actions), vec!["brks-0-S2"]);
+        assert_eq!(placed(&actions), vec![("brks-0-S4".to_string(), dec!(0.7))]);
+        assert_eq!(brackets.get("brks-0").unwrap().open_qty(), dec!(0.7));
+
+        let actions = brackets.apply_update(&update("brks-0-T3", "FILLED", "1"));
+        assert_eq!(canceled(&actions), vec!["brks-0-S4"]);
+        let bracket = brackets.get("brks-0").unwrap();
+        assert_eq!(bracket.state, BracketState::Closed(BracketLeg::TakeProfit));
+        assert_eq!(brackets.remove_final().len(), 1);
+    }
+
+    #[test]
+    fn entry_canceled_and_foreign_orders() {
+        let mut brackets = manager();
+        brackets
+            .open(BracketRequest {
+                entry: OrderRequest::limit("BTCUSDT", OrderSide::Buy, dec!(1), dec!(20000)),
+                stop_price: dec!(19000),
+                take_profit_price: dec!(22000),
+                working_type: None,
+            })
+            .unwrap();
+        assert!(brackets.apply_update(&update("other-0-E", "FILLED", "1")).is_empty());
+        assert!(brackets.apply_update(&update("brks-0-E", "CANCELED", "0")).is_empty());
+        assert_eq!(brackets.get("brks-0").unwrap().state, BracketState::Canceled);
+    }
+
+    #[test]
+    fn recover_orders() {
+        let order = |client_order_id: &str, order_type: &str, side: &str, qty: &str, stop_price: &str| -> Order {
+            serde_json::from_str(&format!(
+                r#"{{"clientOrderId": "{client_order_id}", "cumQuote": "0", "executedQty": "0", "orderId": 1,
+                    "avgPrice": "0", "origQty": "{qty}", "price": "0", "side": "{side}", "reduceOnly": true,
+                    "positionSide": "BOTH", "status": "NEW", "stopPrice": "{stop_price}", "closePosition": false,
+                    "symbol": "BTCUSDT", "timeInForce": "GTC", "type": "{order_type}", "origType": "{order_type}",
+                    "updateTime": 1, "workingType": "MARK_PRICE"}}"#
+            ))
+            .unwrap()
+        };
+        let mut brackets = manager();
+        let actions = brackets.recover_orders(&[
+            order("brks-7-S4", "STOP_MARKET", "SELL", "0.5", "19000"),
+            order("brks-7-T5", "TAKE_PROFIT_MARKET", "SELL", "0.5", "22000"),
+            order("brks-8-E", "LIMIT", "BUY", "1", "0"),
+            order("manual-1", "LIMIT", "BUY", "1", "0"),
+        ]);
+        assert!(actions.is_empty(), "{actions:?}");
+        let active = brackets.get("brks-7").unwrap();
+        assert_eq!(active.state, BracketState::Active);
+        assert_eq!(active.side, OrderSide::Buy);
+        assert_eq!(active.open_qty(), dec!(0.5));
+        assert_eq!(active.take_profit_price, Some(dec!(22000)));
+        let pending = brackets.get("brks-8").unwrap();
+        assert_eq!(pending.state, BracketState::Pending);
+        assert_eq!(pending.stop_price, None);
+        assert_eq!(brackets.brackets().count(), 2);
+
+        // The next legs do not reuse the recovered client order ids
+        let actions = brackets.apply_update(&update("brks-7-S4", "PARTIALLY_FILLED", "0.2"));
+        assert_eq!(placed(&actions), vec![("brks-7-T6".to_string(), dec!(0.3))]);
+
+        let actions = brackets.set_exit_prices("brks-8", dec!(19000), dec!(22000));
+        assert!(actions.is_empty());
+        let actions = brackets.apply_update(&update("brks-8-E", "FILLED", "1"));
+        assert_eq!(placed(&actions).len(), 2);
+    }
+
+    #[test]
+    fn failed_legs_are_placed_again() {
+        let mut brackets = manager();
+        brackets
+            .open(BracketRequest {
+                entry: OrderRequest::limit("BTCUSDT", OrderSide::Buy, dec!(1), dec!(20000)),
+                stop_price: dec!(19000),
+                take_profit_price: dec!(22000),
+                working_type: None,
+            })
+            .unwrap();
+        let actions = brackets.apply_update(&update("brks-0-E", "FILLED", "1"));
+        assert_eq!(placed(&actions).len(), 2);
+
+        // Both placements are rejected before being sent, the second one is still attempted
+        let rejected = actions
+            .into_iter()
+            .map(|action| match action {
+                BracketAction::Place(order) => BracketAction::Place(OrderRequest {
+                    price: Some(dec!(20000)),
+                    ..order
+                }),
+                action => action,
+            })
+            .collect();
+        let account: FuturesAccount = Binance::new(None, None);
+        let executed = tokio_test::block_on(brackets.execute(&account, rejected));
+        assert!(matches!(executed, Err(Error::InvalidOrderError { .. })), "{executed:?}");
+        let bracket = brackets.get("brks-0").unwrap();
+        assert!(bracket.stop_loss.is_none());
+        assert!(bracket.take_profit.is_none());
+
+        // The legs are not canceled, but placed again with the next update
+        let actions = brackets.apply_update(&update("brks-0-E", "FILLED", "1"));
+        assert!(canceled(&actions).is_empty());
+        assert_eq!(
+            placed(&actions),
+            vec![("brks-0-S2".to_string(), dec!(1)), ("brks-0-T3".to_string(), dec!(1))]
+        );
+    }
+}
@@ -0,0 +1,342 @@
+//! Funding of USD-M perpetuals.
+//!
+//! [`FundingTracker`] is seeded from [`FuturesMarket::get_funding_info`] and
+//! [`FuturesMarket::get_mark_prices`], then kept current with the `markPriceUpdate` events of the
+//! mark price streams. It projects the next funding payment of open positions and sums the funding
+//! fees actually paid or received from the income history, so that carry strategies can compare
+//! expected and realized funding per symbol.
+
+use std::collections::{HashMap, HashSet};
+
+use rust_decimal::Decimal;
+
+use crate::errors::*;
+use crate::futures::account::{FuturesAccount, IncomeQuery};
+use crate::futures::market::FuturesMarket;
+use crate::futures::rest_model::{FundingInfo, Income, IncomeType, MarkPrice, Position};
+use crate::ws_model::{MarkPriceEvent, WebsocketEvent};
+
+/// Funding interval of the symbols absent from the funding info
+pub const DEFAULT_FUNDING_INTERVAL_HOURS: u64 = 8;
+
+/// Funding state of a symbol
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolFunding {
+    pub mark_price: Decimal,
+    /// Rate of the current funding period, positive when longs pay shorts
+    pub funding_rate: Decimal,
+    /// Time of the next funding, in milliseconds
+    pub next_funding_time: u64,
+    pub funding_interval_hours: u64,
+    pub funding_rate_cap: Option<Decimal>,
+    pub funding_rate_floor: Option<Decimal>,
+    /// Time of the mark price this state reflects, in milliseconds
+    pub update_time: u64,
+}
+
+impl Default for SymbolFunding {
+    fn default() -> Self {
+        Self {
+            mark_price: Decimal::ZERO,
+            funding_rate: Decimal::ZERO,
+            next_funding_time: 0,
+            funding_interval_hours: DEFAULT_FUNDING_INTERVAL_HOURS,
+            funding_rate_cap: None,
+            funding_rate_floor: None,
+            update_time: 0,
+        }
+    }
+}
+
+impl SymbolFunding {
+    /// Funding rate bounded by the cap and floor of the symbol, when known
+    pub fn bounded_rate(&self) -> Decimal {
+        let mut rate = self.funding_rate;
+        if let Some(cap) = self.funding_rate_cap {
+            rate = rate.min(cap);
+        }
+        if let Some(floor) = self.funding_rate_floor {
+            rate = rate.max(floor);
+        }
+        rate
+    }
+
+    /// Funding rate over a year of funding periods at the current rate
+    pub fn annualized_rate(&self) -> Decimal {
+        let periods_per_day = Decimal::from(24) / Decimal::from(self.funding_interval_hours.max(1));
+        self.bounded_rate() * periods_per_day * Decimal::from(365)
+    }
+}
+
+/// Funding expected at the next funding time of a position
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FundingPayment {
+    pub symbol: String,
+    /// Time of the funding, in milliseconds
+    pub funding_time: u64,
+    pub funding_rate: Decimal,
+    /// Signed notional of the position at the mark price, negative when short
+    pub notional: Decimal,
+    /// Amount received by the position, negative when it pays
+    pub amount: Decimal,
+}
+
+/// Funding rates, projections and realized funding per symbol
+#[derive(Debug, Clone, Default)]
+pub struct FundingTracker {
+    symbols: HashMap<String, SymbolFunding>,
+    realized: HashMap<String, Decimal>,
+    seen_incomes: HashSet<u64>,
+    last_income_time: u64,
+}
+
+impl FundingTracker {
+    /// Creates a tracker from the funding info and mark prices of all symbols
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, config::*, futures::funding::*, futures::market::*};
+    /// let market: FuturesMarket = Binance::new_with_env(&Config::default());
+    /// let tracker = tokio_test::block_on(FundingTracker::from_market(&market));
+    /// assert!(tracker.is_ok(), "{:?}", tracker);
+    /// ```
+    pub async fn from_market(market: &FuturesMarket) -> Result<Self> {
+        let mut tracker = Self::default();
+        tracker.refresh(market).await?;
+        Ok(tracker)
+    }
+
+    /// Applies the adjusted caps, floors and intervals of the symbols
+    pub fn apply_funding_info(&mut self, infos: &[FundingInfo]) {
+        for info in infos {
+            let funding = self.symbols.entry(info.symbol.clone()).or_default();
+            funding.funding_interval_hours = info.funding_interval_hours;
+            funding.funding_rate_cap = Some(info.adjusted_funding_rate_cap);
+            funding.funding_rate_floor = Some(info.adjusted_funding_rate_floor);
+        }
+    }
+
+    /// Applies a mark price from the REST API, older than the known state it is ignored
+    pub fn apply_mark_price(&mut self, mark_price: &MarkPrice) {
+        self.set_mark_price(
+            &mark_price.symbol,
+            mark_price.mark_price,
+            mark_price.last_funding_rate,
+            mark_price.next_funding_time,
+            mark_price.time,
+        );
+    }
+
+    /// Applies a `markPriceUpdate` event, events with unparsable prices are ignored
+    pub fn apply_mark_price_event(&mut self, event: &MarkPriceEvent) {
+        if let (Ok(mark_price), Ok(funding_rate)) = (event.mark_price.parse(), event.funding_rate.parse()) {
+            self.set_mark_price(
+                &event.symbol,
+                mark_price,
+                funding_rate,
+                event.next_funding_time,
+                event.event_time,
+            );
+        }
+    }
+
+    /// Applies the stream events which change funding, ignoring the others
+    pub fn apply_event(&mut self, event: &WebsocketEvent) {
+        if let WebsocketEvent::MarkPriceUpdate(event) = event {
+            self.apply_mark_price_event(event);
+        }
+    }
+
+    /// Refreshes the funding info and mark prices of all symbols from the REST API
+    pub async fn refresh(&mut self, market: &FuturesMarket) -> Result<()> {
+        let infos = market.get_funding_info().await?;
+        self.apply_funding_info(&infos);
+        for mark_price in market.get_mark_prices(None).await? {
+            self.apply_mark_price(&mark_price);
+        }
+        Ok(())
+    }
+
+    pub fn funding(&self, symbol: &str) -> Option<&SymbolFunding> { self.symbols.get(symbol) }
+
+    pub fn fundings(&self) -> &HashMap<String, SymbolFunding> { &self.symbols }
+
+    /// Projects the next funding payment of a position, `None` when the position is closed or the
+    /// symbol has no mark price yet
+    pub fn projected_payment(&self, position: &Position) -> Option<FundingPayment> {
+        let funding = self.symbols.get(&position.symbol)?;
+        if position.position_amount.is_zero() || funding.update_time == 0 {
+            return None;
+        }
+        let funding_rate = funding.bounded_rate();
+        let notional = position.position_amount * funding.mark_price;
+        Some(FundingPayment {
+            symbol: position.symbol.clone(),
+            funding_time: funding.next_funding_time,
+            funding_rate,
+            notional,
+            amount: -notional * funding_rate,
+        })
+    }
+
+    /// Projects the next funding payment of every open position
+    pub fn projected_payments(&self, positions: &[Position]) -> Vec<FundingPayment> {
+        positions.iter().filter_map(|p| self.projected_payment(p)).collect()
+    }
+
+    /// Sums the funding fees of an income history, incomes already applied are ignored
+    pub fn apply_incomes(&mut self, incomes: &[Income]) {
+        for income in incomes {
+            if income.income_type != IncomeType::FundingFee || !self.seen_incomes.insert(income.tran_id) {
+                continue;
+            }
+            *self.realized.entry(income.symbol.clone()).or_default() += income.income;
+            self.last_income_time = self.last_income_time.max(income.time);
+        }
+    }
+
+    /// Fetches the funding fees since a time, or since the last fee already applied when later
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, config::*, futures::account::*, futures::funding::*};
+    /// let account: FuturesAccount = Binance::new_with_env(&Config::default());
+    /// let mut tracker = FundingTracker::default();
+    /// let synced = tokio_test::block_on(tracker.sync_realized(&account, 1_700_000_000_000));
+    /// assert!(synced.is_ok(), "{:?}", synced);
+    /// println!("{:?}", tracker.realized_funding());
+    /// ```
+    pub async fn sync_realized(&mut self, account: &FuturesAccount, start_time: u64) -> Result<()> {
+        let incomes = account
+            .income_paginated(IncomeQuery {
+                income_type: Some(IncomeType::FundingFee),
+                start_time: Some(start_time.max(self.last_income_time)),
+                ..IncomeQuery::default()
+            })
+            .await?;
+        self.apply_incomes(&incomes);
+        Ok(())
+    }
+
+    /// Funding received on a symbol, negative when paid
+    pub fn realized(&self, symbol: &str) -> Decimal { self.realized.get(symbol).copied().unwrap_or_default() }
+
+    pub fn realized_funding(&self) -> &HashMap<String, Decimal> { &self.realized }
+
+    fn set_mark_price(
+        &mut self,
+        symbol: &str,
+        mark_price: Decimal,
+        funding_rate: Decimal,
+        next_funding_time: u64,
+        update_time: u64,
+    ) {
+        let funding = self.symbols.entry(symbol.to_string()).or_default();
+        if update_time >= funding.update_time {
+            funding.mark_price = mark_price;
+            funding.funding_rate = funding_rate;
+            funding.next_funding_time = next_funding_time;
+            funding.update_time = update_time;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::futures::rest_model::{MarginType, PositionSide};
+    use rust_decimal_macros::dec;
+
+    fn position(symbol: &str, amount: Decimal) -> Position {
+        Position {
+            entry_price: dec!(20000),
+            margin_type: MarginType::Cross,
+            is_auto_add_margin: false,
+            isolated_margin: Decimal::ZERO,
+            leverage: 10,
+            liquidation_price: Decimal::ZERO,
+            mark_price: dec!(20000),
+            max_notional_value: dec!(1000000),
+            position_amount: amount,
+            symbol: symbol.to_string(),
+            unrealized_profit: Decimal::ZERO,
+            position_side: PositionSide::Both,
+            update_time: 0,
+            notional: Decimal::ZERO,
+            isolated_wallet: Decimal::ZERO,
+            break_even_price: None,
+        }
+    }
+
+    fn mark_price_event(time: u64, mark_price: &str, funding_rate: &str) -> WebsocketEvent {
+        WebsocketEvent::MarkPriceUpdate(Box::new(MarkPriceEvent {
+            event_time: time,
+            symbol: "BTCUSDT".to_string(),
+            mark_price: mark_price.to_string(),
+            index_price: mark_price.to_string(),
+            estimated_settle_price: mark_price.to_string(),
+            funding_rate: funding_rate.to_string(),
+            next_funding_time: 1_700_006_400_000,
+        }))
+    }
+
+    fn funding_fee(tran_id: u64, symbol: &str, income: Decimal) -> Income {
+        Income {
+            symbol: symbol.to_string(),
+            income_type: IncomeType::FundingFee,
+            income,
+            asset: "USDT".to_string(),
+            info: "FUNDING_FEE".to_string(),
+            time: 1_700_000_000_000 + tran_id,
+            tran_id,
+            trade_id: String::new(),
+        }
+    }
+
+    #[test]
+    fn projected_payments() {
+        let mut tracker = FundingTracker::default();
+        assert_eq!(tracker.projected_payment(&position("BTCUSDT", dec!(1))), None);
+
+        tracker.apply_event(&mark_price_event(2, "30000", "0.0001"));
+        // An older event does not override the state
+        tracker.apply_event(&mark_price_event(1, "10000", "0.0003"));
+        let long = tracker.projected_payment(&position("BTCUSDT", dec!(2))).unwrap();
+        assert_eq!(long.notional, dec!(60000));
+        assert_eq!(long.amount, dec!(-6));
+        assert_eq!(long.funding_time, 1_700_006_400_000);
+        let short = tracker.projected_payment(&position("BTCUSDT", dec!(-1))).unwrap();
+        assert_eq!(short.amount, dec!(3));
+        assert_eq!(tracker.projected_payment(&position("BTCUSDT", dec!(0))), None);
+
+        tracker.apply_funding_info(&[FundingInfo {
+            symbol: "BTCUSDT".to_string(),
+            adjusted_funding_rate_cap: dec!(0.00005),
+            adjusted_funding_rate_floor: dec!(-0.00005),
+            funding_interval_hours: 4,
+            disclaimer: false,
+        }]);
+        let capped = tracker.projected_payment(&position("BTCUSDT", dec!(1))).unwrap();
+        assert_eq!(capped.funding_rate, dec!(0.00005));
+        assert_eq!(capped.amount, dec!(-1.5));
+        assert_eq!(tracker.funding("BTCUSDT").unwrap().annualized_rate(), dec!(0.1095));
+    }
+
+    #[test]
+    fn realized_funding() {
+        let mut tracker = FundingTracker::default();
+        let mut incomes = vec![
+            funding_fee(1, "BTCUSDT", dec!(-1.5)),
+            funding_fee(2, "BTCUSDT", dec!(0.5)),
+            funding_fee(3, "ETHUSDT", dec!(2)),
+        ];
+        incomes.push(Income {
+            income_type: IncomeType::Commission,
+            ..funding_fee(4, "BTCUSDT", dec!(-10))
+        });
+        tracker.apply_incomes(&incomes);
+        // Overlapping pages are only counted once
+        tracker.apply_incomes(&incomes[1..]);
+        assert_eq!(tracker.realized("BTCUSDT"), dec!(-1));
+        assert_eq!(tracker.realized("ETHUSDT"), dec!(2));
+        assert_eq!(tracker.realized("BNBUSDT"), Decimal::ZERO);
+    }
+}
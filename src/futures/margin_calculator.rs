@@ -0,0 +1,359 @@
+//! Offline margin and liquidation price calculator of USDⓈ-M futures positions, based on the
+//! leverage brackets returned by [`crate::futures::market::FuturesMarket::get_notional_leverage_brackets`].
+//!
+//! The liquidation price follows Binance's formula, in which the positions of a symbol, including
+//! both sides in hedge mode, are liquidated together at the price where the margin balance meets
+//! the maintenance margin:
+//!
+//! `LP = (WB - TMM1 + UPNL1 + Σcum - Σ(qty × entry)) / (Σ(|qty| × mmr) - Σqty)`
+//!
+//! where `WB` is the wallet balance (cross wallet, or the isolated wallet of the position), `TMM1`
+//! and `UPNL1` the maintenance margin and unrealized PnL of the cross positions of other symbols
+//! (zero when isolated) and `qty` the signed position amount.
+
+use std::collections::HashMap;
+
+use rust_decimal::Decimal;
+
+use super::rest_model::{LeverageBracket, MarginType, Position, PositionSide, SymbolBrackets};
+use crate::errors::*;
+
+/// A position, real or hypothetical, as seen by the calculator
+#[derive(Debug, Clone)]
+pub struct MarginPosition {
+    pub symbol: String,
+    pub position_side: PositionSide,
+    /// Signed amount, negative when short
+    pub amount: Decimal,
+    pub entry_price: Decimal,
+    pub mark_price: Decimal,
+    pub leverage: u64,
+    pub margin_type: MarginType,
+    /// Wallet of an isolated position, ignored in cross margin
+    pub isolated_wallet: Decimal,
+}
+
+impl MarginPosition {
+    /// A one-way, cross margin position marked at its entry price
+    pub fn new(symbol: impl Into<String>, amount: Decimal, entry_price: Decimal, leverage: u64) -> Self {
+        Self {
+            symbol: symbol.into(),
+            position_side: PositionSide::Both,
+            amount,
+            entry_price,
+            mark_price: entry_price,
+            leverage,
+            margin_type: MarginType::Cross,
+            isolated_wallet: Decimal::ZERO,
+        }
+    }
+
+    pub fn set_mark_price(mut self, mark_price: Decimal) -> Self {
+        self.mark_price = mark_price;
+        self
+    }
+
+    /// Side of the position in hedge mode
+    pub fn set_position_side(mut self, position_side: PositionSide) -> Self {
+        self.position_side = position_side;
+        self
+    }
+
+    /// Isolated margin, with the margin put in the position's wallet
+    pub fn set_isolated(mut self, isolated_wallet: Decimal) -> Self {
+        self.margin_type = MarginType::Isolated;
+        self.isolated_wallet = isolated_wallet;
+        self
+    }
+
+    pub fn is_isolated(&self) -> bool { matches!(self.margin_type, MarginType::Isolated) }
+
+    /// Notional value at the mark price
+    pub fn notional(&self) -> Decimal { self.amount.abs() * self.mark_price }
+
+    pub fn unrealized_pnl(&self) -> Decimal { self.amount * (self.mark_price - self.entry_price) }
+}
+
+impl From<&Position> for MarginPosition {
+    fn from(position: &Position) -> Self {
+        Self {
+            symbol: position.symbol.clone(),
+            position_side: position.position_side.clone(),
+            amount: position.position_amount,
+            entry_price: position.entry_price,
+            mark_price: position.mark_price,
+            leverage: position.leverage,
+            margin_type: position.margin_type.clone(),
+            isolated_wallet: position.isolated_wallet,
+        }
+    }
+}
+
+/// Margins and liquidation price of a position
+#[derive(Debug, Clone)]
+pub struct PositionMargin {
+    pub symbol: String,
+    pub position_side: PositionSide,
+    pub notional: Decimal,
+    pub initial_margin: Decimal,
+    pub maintenance_margin: Decimal,
+    pub unrealized_pnl: Decimal,
+    /// Maintenance margin over margin balance, of the position when isolated or of the account
+    /// when cross, the position is liquidated once it reaches 1. None when the margin balance is
+    /// not positive
+    pub margin_ratio: Option<Decimal>,
+    /// None when no positive price liquidates the position
+    pub liquidation_price: Option<Decimal>,
+}
+
+/// Margins of the cross margin positions of an account
+#[derive(Debug, Clone)]
+pub struct AccountMargin {
+    /// Margins of all the positions, isolated ones included
+    pub positions: Vec<PositionMargin>,
+    pub wallet_balance: Decimal,
+    pub unrealized_pnl: Decimal,
+    pub margin_balance: Decimal,
+    pub initial_margin: Decimal,
+    pub maintenance_margin: Decimal,
+    /// None when the margin balance is not positive
+    pub margin_ratio: Option<Decimal>,
+}
+
+pub struct MarginCalculator {
+    brackets: HashMap<String, Vec<LeverageBracket>>,
+}
+
+impl MarginCalculator {
+    pub fn new(brackets: Vec<SymbolBrackets>) -> Self {
+        Self {
+            brackets: brackets
+                .into_iter()
+                .map(|mut symbol_brackets| {
+                    symbol_brackets.brackets.sort_by_key(|b| b.notional_floor);
+                    (symbol_brackets.symbol, symbol_brackets.brackets)
+                })
+                .collect(),
+        }
+    }
+
+    /// Bracket of a notional value, notional values above the last cap fall in the last bracket
+    pub fn bracket(&self, symbol: &str, notional: Decimal) -> Result<&LeverageBracket> {
+        self.brackets
+            .get(symbol)
+            .and_then(|brackets| {
+                brackets
+                    .iter()
+                    .rev()
+                    .find(|b| Decimal::from(b.notional_floor) <= notional)
+            })
+            .ok_or_else(|| Error::UnknownSymbol(symbol.to_string()))
+    }
+
+    pub fn initial_margin(&self, position: &MarginPosition) -> Decimal {
+        position.notional() / Decimal::from(position.leverage.max(1))
+    }
+
+    pub fn maintenance_margin(&self, position: &MarginPosition) -> Result<Decimal> {
+        let notional = position.notional();
+        let bracket = self.bracket(&position.symbol, notional)?;
+        Ok(notional * bracket.maint_margin_ratio - bracket.cum)
+    }
+
+    /// Margins and liquidation price of an isolated position, out of its isolated wallet
+    pub fn isolated(&self, position: &MarginPosition) -> Result<PositionMargin> {
+        let maintenance_margin = self.maintenance_margin(position)?;
+        let unrealized_pnl = position.unrealized_pnl();
+        let liquidation_price = self.liquidation_price(position.isolated_wallet, std::slice::from_ref(position))?;
+        Ok(PositionMargin {
+            symbol: position.symbol.clone(),
+            position_side: position.position_side.clone(),
+            notional: position.notional(),
+            initial_margin: self.initial_margin(position),
+            maintenance_margin,
+            unrealized_pnl,
+            margin_ratio: ratio(maintenance_margin, position.isolated_wallet + unrealized_pnl),
+            liquidation_price,
+        })
+    }
+
+    /// Margins of an account holding positions in cross margin out of a wallet balance, isolated
+    /// positions are computed on their own and left out of the account totals
+    /// # Examples
+    /// ```rust
+    /// use binance::futures::{margin_calculator::*, rest_model::{LeverageBracket, SymbolBrackets}};
+    /// use rust_decimal_macros::dec;
+    /// let calculator = MarginCalculator::new(vec![SymbolBrackets {
+    ///     symbol: "BTCUSDT".to_string(),
+    ///     notional_coef: None,
+    ///     brackets: vec![LeverageBracket {
+    ///         bracket: 1,
+    ///         initial_leverage: 125,
+    ///         notional_cap: 50000,
+    ///         notional_floor: 0,
+    ///         maint_margin_ratio: dec!(0.004),
+    ///         cum: dec!(0),
+    ///     }],
+    /// }]);
+    /// let position = MarginPosition::new("BTCUSDT", dec!(1), dec!(20000), 10);
+    /// let account = calculator.cross(dec!(2000), &[position]).unwrap();
+    /// assert_eq!(account.maintenance_margin, dec!(80));
+    /// assert_eq!(account.positions[0].liquidation_price.unwrap().round_dp(2), dec!(18072.29));
+    /// ```
+    pub fn cross(&self, wallet_balance: Decimal, positions: &[MarginPosition]) -> Result<AccountMargin> {
+        let cross: Vec<&MarginPosition> = positions.iter().filter(|p| !p.is_isolated()).collect();
+        let mut maintenance_margins = Vec::with_capacity(cross.len());
+        for position in &cross {
+            maintenance_margins.push(self.maintenance_margin(position)?);
+        }
+        let maintenance_margin: Decimal = maintenance_margins.iter().sum();
+        let unrealized_pnl: Decimal = cross.iter().map(|p| p.unrealized_pnl()).sum();
+        let margin_balance = wallet_balance + unrealized_pnl;
+        let margin_ratio = ratio(maintenance_margin, margin_balance);
+
+        let mut margins = Vec::with_capacity(positions.len());
+        for position in positions {
+            if position.is_isolated() {
+                margins.push(self.isolated(position)?);
+                continue;
+            }
+            // Positions of other symbols are valued at the mark price, those of the symbol are
+            // liquidated together
+            let (same, other): (Vec<_>, Vec<_>) = cross
+                .iter()
+                .zip(&maintenance_margins)
+                .partition(|(p, _)| p.symbol == position.symbol);
+            let other_maintenance: Decimal = other.iter().map(|(_, mm)| **mm).sum();
+            let other_pnl: Decimal = other.iter().map(|(p, _)| p.unrealized_pnl()).sum();
+            let same: Vec<MarginPosition> = same.into_iter().map(|(p, _)| (*p).clone()).collect();
+            margins.push(PositionMargin {
+                symbol: position.symbol.clone(),
+                position_side: position.position_side.clone(),
+                notional: position.notional(),
+                initial_margin: self.initial_margin(position),
+                maintenance_margin: self.maintenance_margin(position)?,
+                unrealized_pnl: position.unrealized_pnl(),
+                margin_ratio,
+                liquidation_price: self.liquidation_price(wallet_balance - other_maintenance + other_pnl, &same)?,
+            });
+        }
+
+        Ok(AccountMargin {
+            positions: margins,
+            wallet_balance,
+            unrealized_pnl,
+            margin_balance,
+            initial_margin: cross.iter().map(|p| self.initial_margin(p)).sum(),
+            maintenance_margin,
+            margin_ratio,
+        })
+    }
+
+    /// Price at which positions of a symbol backed by a balance are liquidated together
+    fn liquidation_price(&self, balance: Decimal, positions: &[MarginPosition]) -> Result<Option<Decimal>> {
+        let mut numerator = balance;
+        let mut denominator = Decimal::ZERO;
+        for position in positions {
+            let bracket = self.bracket(&position.symbol, position.notional())?;
+            numerator += bracket.cum - position.amount * position.entry_price;
+            denominator += position.amount.abs() * bracket.maint_margin_ratio - position.amount;
+        }
+        if denominator.is_zero() {
+            return Ok(None);
+        }
+        let price = numerator / denominator;
+        Ok((price > Decimal::ZERO).then_some(price))
+    }
+}
+
+fn ratio(maintenance_margin: Decimal, margin_balance: Decimal) -> Option<Decimal> {
+    (margin_balance > Decimal::ZERO).then(|| maintenance_margin / margin_balance)
+}
+
+#[cfg(test)]
+mod test {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    fn calculator() -> MarginCalculator {
+        let bracket = |bracket, floor, cap, mmr, cum| LeverageBracket {
+            bracket,
+            initial_leverage: 125,
+            notional_cap: cap,
+            notional_floor: floor,
+            maint_margin_ratio: mmr,
+            cum,
+        };
+        MarginCalculator::new(vec![
+            SymbolBrackets {
+                symbol: "BTCUSDT".to_string(),
+                notional_coef: None,
+                brackets: vec![
+                    bracket(2, 50000, 250000, dec!(0.005), dec!(50)),
+                    bracket(1, 0, 50000, dec!(0.004), dec!(0)),
+                ],
+            },
+            SymbolBrackets {
+                symbol: "ETHUSDT".to_string(),
+                notional_coef: None,
+                brackets: vec![bracket(1, 0, 50000, dec!(0.005), dec!(0))],
+            },
+        ])
+    }
+
+    #[test]
+    fn isolated_positions() {
+        let calculator = calculator();
+        let long = MarginPosition::new("BTCUSDT", dec!(1), dec!(20000), 10).set_isolated(dec!(2000));
+        let margin = calculator.isolated(&long).unwrap();
+        assert_eq!(margin.initial_margin, dec!(2000));
+        assert_eq!(margin.maintenance_margin, dec!(80));
+        assert_eq!(margin.margin_ratio, Some(dec!(0.04)));
+        assert_eq!(margin.liquidation_price.unwrap().round_dp(2), dec!(18072.29));
+
+        let short = MarginPosition::new("BTCUSDT", dec!(-1), dec!(20000), 10).set_isolated(dec!(2000));
+        let margin = calculator.isolated(&short).unwrap();
+        assert_eq!(margin.liquidation_price.unwrap().round_dp(2), dec!(21912.35));
+
+        let large = MarginPosition::new("BTCUSDT", dec!(3), dec!(20000), 10).set_isolated(dec!(6000));
+        assert_eq!(calculator.maintenance_margin(&large).unwrap(), dec!(250));
+    }
+
+    #[test]
+    fn cross_hedged_positions() {
+        let calculator = calculator();
+        let positions = [
+            MarginPosition::new("BTCUSDT", dec!(1), dec!(20000), 10).set_position_side(PositionSide::Long),
+            MarginPosition::new("BTCUSDT", dec!(-0.5), dec!(21000), 10)
+                .set_position_side(PositionSide::Short)
+                .set_mark_price(dec!(20000)),
+            MarginPosition::new("ETHUSDT", dec!(10), dec!(1500), 20).set_mark_price(dec!(1600)),
+        ];
+        let account = calculator.cross(dec!(1000), &positions).unwrap();
+        assert_eq!(account.maintenance_margin, dec!(200));
+        assert_eq!(account.unrealized_pnl, dec!(1500));
+        assert_eq!(account.margin_ratio, Some(dec!(0.08)));
+        assert_eq!(account.initial_margin, dec!(3800));
+
+        let btc_liquidation = account.positions[0].liquidation_price.unwrap();
+        assert_eq!(btc_liquidation.round_dp(2), dec!(15344.13));
+        assert_eq!(account.positions[1].liquidation_price, Some(btc_liquidation));
+
+        let hedged = [
+            MarginPosition::new("BTCUSDT", dec!(1), dec!(20000), 10).set_position_side(PositionSide::Long),
+            MarginPosition::new("BTCUSDT", dec!(-1), dec!(20000), 10).set_position_side(PositionSide::Short),
+        ];
+        let account = calculator.cross(dec!(1000), &hedged).unwrap();
+        assert_eq!(account.positions[0].liquidation_price, Some(dec!(125000)));
+        let account = calculator.cross(dec!(-1), &hedged).unwrap();
+        assert_eq!(account.positions[0].liquidation_price, None);
+        assert_eq!(account.margin_ratio, None);
+    }
+
+    #[test]
+    fn unknown_symbol() {
+        let position = MarginPosition::new("XRPUSDT", dec!(1), dec!(1), 10);
+        assert!(calculator().cross(dec!(100), &[position]).is_err());
+    }
+}
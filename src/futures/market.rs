@@ -7,10 +7,13 @@ use crate::rest_model::{
 use crate::util::*;
 use serde_json::Value;
 
-//TODO : Validate intervals and start/end times in history queries
+//TODO : Validate intervals in history queries
 //TODO : find out the repartition of kline/candlestick columns in the future kline rows
 //TODO : make limit optional where applicable
 
+/// Maximum page size of the funding rate history
+pub const MAX_FUNDING_RATE_LIMIT: u16 = 1000;
+
 #[derive(Clone)]
 pub struct FuturesMarket {
     pub client: Client,
@@ -126,6 +129,41 @@ impl FuturesMarket {
             .await
     }
 
+    /// Get the whole funding rate history of a symbol between two times, requesting one page after
+    /// the other
+    pub async fn get_funding_rate_paginated<S>(
+        &self,
+        symbol: S,
+        start_time: u64,
+        end_time: Option<u64>,
+    ) -> Result<Vec<FundingRate>>
+    where
+        S: Into<String>,
+    {
+        let symbol = symbol.into();
+        let mut start_time = start_time;
+        let mut rates = Vec::new();
+        loop {
+            let batch = self
+                .get_funding_rate(symbol.clone(), start_time, end_time, MAX_FUNDING_RATE_LIMIT)
+                .await?;
+            let last_page = batch.len() < MAX_FUNDING_RATE_LIMIT as usize;
+            if let Some(last) = batch.last() {
+                start_time = last.funding_time + 1;
+            }
+            rates.extend(batch);
+            if last_page {
+                return Ok(rates);
+            }
+        }
+    }
+
+    /// Funding rate caps, floors and intervals of the symbols which were adjusted, the others use
+    /// an 8 hours interval
+    pub async fn get_funding_info(&self) -> Result<Vec<FundingInfo>> {
+        self.client.get_p("/fapi/v1/fundingInfo", None).await
+    }
+
     /// Get open interest history
     pub async fn get_open_interest_history<S1, S2, S3, S4, S5>(
         &self,
@@ -254,7 +292,8 @@ impl FuturesMarket {
             .await
     }
 
-    /// Get Taker Long/Short Ratio
+    /// Get Taker Buy/Sell Volume
+    #[deprecated(note = "returns taker buy and sell volumes, use get_taker_volume")]
     pub async fn get_taker_long_short_ratio<S1, S2, S3, S4, S5>(
         &self,
         symbol: S1,
@@ -262,7 +301,33 @@ impl FuturesMarket {
         start_time: S3,
         end_time: S4,
         limit: S5,
-    ) -> Result<Vec<LongShortRatio>>
+    ) -> Result<Vec<TakerVolume>>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<Option<u64>>,
+        S4: Into<Option<u64>>,
+        S5: Into<u16>,
+    {
+        self.get_taker_volume(symbol, period, start_time, end_time, limit).await
+    }
+
+    /// Get Taker Buy/Sell Volume
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, config::*, futures::market::*};
+    /// let market: FuturesMarket = Binance::new_with_env(&Config::default());
+    /// let volumes = tokio_test::block_on(market.get_taker_volume("BTCUSDT", "5m", None, None, 30u16));
+    /// assert!(volumes.is_ok(), "{:?}", volumes);
+    /// ```
+    pub async fn get_taker_volume<S1, S2, S3, S4, S5>(
+        &self,
+        symbol: S1,
+        period: S2,
+        start_time: S3,
+        end_time: S4,
+        limit: S5,
+    ) -> Result<Vec<TakerVolume>>
     where
         S1: Into<String>,
         S2: Into<String>,
@@ -285,6 +350,87 @@ impl FuturesMarket {
             .await
     }
 
+    /// Get the basis of a pair, between the price of a contract and the index price
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, config::*, futures::market::*, futures::rest_model::ContractType};
+    /// let market: FuturesMarket = Binance::new_with_env(&Config::default());
+    /// let basis = tokio_test::block_on(market.get_basis("BTCUSDT", ContractType::Perpetual, "1h", None, None, 30u16));
+    /// assert!(basis.is_ok(), "{:?}", basis);
+    /// ```
+    pub async fn get_basis<S1, S2, S3, S4, S5>(
+        &self,
+        pair: S1,
+        contract_type: ContractType,
+        period: S2,
+        start_time: S3,
+        end_time: S4,
+        limit: S5,
+    ) -> Result<Vec<Basis>>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<Option<u64>>,
+        S4: Into<Option<u64>>,
+        S5: Into<u16>,
+    {
+        let query = BasisQuery {
+            pair: pair.into(),
+            contract_type,
+            period: period.into(),
+            limit: limit.into(),
+            start_time: start_time.into(),
+            end_time: end_time.into(),
+        };
+        query.validate()?;
+        self.client.get_d("/futures/data/basis", Some(query)).await
+    }
+
+    /// Get the settlement prices of the delivered quarterly contracts of a pair
+    pub async fn get_delivery_price<S>(&self, pair: S) -> Result<Vec<DeliveryPrice>>
+    where
+        S: Into<String>,
+    {
+        self.client
+            .get_d(
+                "/futures/data/delivery-price",
+                Some(PairNameQuery { pair: pair.into() }),
+            )
+            .await
+    }
+
+    /// Returns up to 'limit' premium index klines for given symbol and interval ("1m", "5m", ...),
+    /// the volumes are always zero
+    /// https://binance-docs.github.io/apidocs/futures/en/#premium-index-kline-data
+    pub async fn get_premium_index_klines<S1, S2, S3, S4, S5>(
+        &self,
+        symbol: S1,
+        interval: S2,
+        limit: S3,
+        start_time: S4,
+        end_time: S5,
+    ) -> Result<KlineSummaries>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<u16>,
+        S4: Into<Option<u64>>,
+        S5: Into<Option<u64>>,
+    {
+        let query = HistoryQuery {
+            start_time: start_time.into(),
+            end_time: end_time.into(),
+            limit: limit.into(),
+            symbol: symbol.into(),
+            interval: Some(interval.into()),
+            from_id: None,
+            period: None,
+        };
+        query.validate()?;
+        let data: Vec<Vec<Value>> = self.client.get_d("/fapi/v1/premiumIndexKlines", Some(query)).await?;
+        Ok(kline_summaries(&data))
+    }
+
     /// Returns up to 'limit' klines for given symbol and interval ("1m", "5m", ...)
     /// https://github.com/binance-exchange/binance-official-api-docs/blob/master/rest-api.md#klinecandlestick-data
     pub async fn get_klines<S1, S2, S3, S4, S5>(
@@ -312,25 +458,7 @@ impl FuturesMarket {
             period: None,
         };
         let data: Vec<Vec<Value>> = self.client.get_d("/fapi/v1/klines", Some(query)).await?;
-
-        let klines = KlineSummaries::AllKlineSummaries(
-            data.iter()
-                .map(|row| KlineSummary {
-                    open_time: to_i64(&row[0]),
-                    open: to_decimal(&row[1]),
-                    high: to_decimal(&row[2]),
-                    low: to_decimal(&row[3]),
-                    close: to_decimal(&row[4]),
-                    volume: to_decimal(&row[5]),
-                    close_time: to_i64(&row[6]),
-                    quote_asset_volume: to_decimal(&row[7]),
-                    number_of_trades: to_i64(&row[8]),
-                    taker_buy_base_asset_volume: to_decimal(&row[9]),
-                    taker_buy_quote_asset_volume: to_decimal(&row[10]),
-                })
-                .collect(),
-        );
-        Ok(klines)
+        Ok(kline_summaries(&data))
     }
 
     /// Returns up to 'limit' blvt klines for given symbol and interval ("1m", "5m", ...)
@@ -479,6 +607,13 @@ impl FuturesMarket {
             .await
     }
 
+    /// Notional and leverage brackets of every symbol
+    pub async fn get_all_notional_leverage_brackets(&self) -> Result<Vec<SymbolBrackets>> {
+        self.client
+            .get_signed_p("/fapi/v1/leverageBracket", Option::<String>::None, self.recv_window)
+            .await
+    }
+
     /// https://binance-docs.github.io/apidocs/futures/en/#composite-index-symbol-information
     /// Only for composite symbols (ex: DEFIUSDT)
     pub async fn get_index_info<S>(&self, symbol: Option<S>) -> Result<PriceStats>
@@ -489,6 +624,31 @@ impl FuturesMarket {
         self.client.get_d("/fapi/v1/indexInfo", p).await
     }
 
+    /// Get the exchanges and pairs of which the index price of a symbol is composed
+    pub async fn get_index_constituents<S>(&self, symbol: S) -> Result<IndexConstituents>
+    where
+        S: Into<String>,
+    {
+        self.client
+            .get_d("/fapi/v1/constituents", Some(PairQuery { symbol: symbol.into() }))
+            .await
+    }
+
+    /// Get the index of a multi-assets mode margin asset (ex: BTCUSD)
+    pub async fn get_asset_index<S>(&self, symbol: S) -> Result<AssetIndex>
+    where
+        S: Into<String>,
+    {
+        self.client
+            .get_d("/fapi/v1/assetIndex", Some(PairQuery { symbol: symbol.into() }))
+            .await
+    }
+
+    /// Get the indexes of all multi-assets mode margin assets
+    pub async fn get_asset_indexes(&self) -> Result<Vec<AssetIndex>> {
+        self.client.get_p("/fapi/v1/assetIndex", None).await
+    }
+
     /// 24hr ticker price change statistics
     pub async fn get_24h_price_stats<S>(&self, symbol: S) -> Result<PriceStats>
     where
@@ -530,13 +690,19 @@ impl FuturesMarket {
             .await
     }
 
+    /// Premium index of a symbol, with its mark price, last funding rate and next funding time
+    pub async fn get_premium_index<S>(&self, symbol: S) -> Result<MarkPrice>
+    where
+        S: Into<String>,
+    {
+        self.client
+            .get_d("/fapi/v1/premiumIndex", Some(PairQuery { symbol: symbol.into() }))
+            .await
+    }
+
     pub async fn get_mark_prices(&self, symbol: Option<String>) -> Result<Vec<MarkPrice>> {
         if let Some(symbol) = symbol {
-            Ok(vec![
-                self.client
-                    .get_d::<MarkPrice, PairQuery>("/fapi/v1/premiumIndex", Some(PairQuery { symbol }))
-                    .await?,
-            ])
+            Ok(vec![self.get_premium_index(symbol).await?])
         } else {
             self.client.get_p("/fapi/v1/premiumIndex", None).await
         }
@@ -555,3 +721,23 @@ impl FuturesMarket {
             .await
     }
 }
+
+fn kline_summaries(data: &[Vec<Value>]) -> KlineSummaries {
+    KlineSummaries::AllKlineSummaries(
+        data.iter()
+            .map(|row| KlineSummary {
+                open_time: to_i64(&row[0]),
+                open: to_decimal(&row[1]),
+                high: to_decimal(&row[2]),
+                low: to_decimal(&row[3]),
+                close: to_decimal(&row[4]),
+                volume: to_decimal(&row[5]),
+                close_time: to_i64(&row[6]),
+                quote_asset_volume: to_decimal(&row[7]),
+                number_of_trades: to_i64(&row[8]),
+                taker_buy_base_asset_volume: to_decimal(&row[9]),
+                taker_buy_quote_asset_volume: to_decimal(&row[10]),
+            })
+            .collect(),
+    )
+}
@@ -1,7 +1,12 @@
 pub mod account;
+pub mod bracket;
+pub mod funding;
 pub mod general;
+pub mod margin_calculator;
 pub mod market;
+pub mod position_book;
 pub mod rest_model;
+pub mod symbol_rules;
 pub mod userstream;
 pub mod websockets;
 pub mod ws_model;
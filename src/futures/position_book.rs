@@ -0,0 +1,663 @@
+//! Live USD-M futures positions.
+//!
+//! [`PositionBook`] is seeded from [`FuturesAccount::account_information`] and
+//! [`FuturesAccount::position_information`], kept current with the `ACCOUNT_UPDATE` events of the
+//! user stream and marked to market with the `markPriceUpdate` events of the mark price streams.
+//! It exposes the size, entry price, unrealized PnL and margin ratio of every position, per side in
+//! hedge mode, along with the equity of the account in its margin asset, and calls a handler
+//! whenever the margin ratio of a position crosses one of the configured risk thresholds.
+
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use rust_decimal::Decimal;
+
+use crate::errors::*;
+use crate::futures::account::FuturesAccount;
+use crate::futures::margin_calculator::{MarginCalculator, MarginPosition};
+use crate::futures::market::FuturesMarket;
+use crate::futures::rest_model::{AccountInformation, MarginType, Position, PositionSide};
+use crate::futures::ws_model::{self, AccountUpdate};
+use crate::util::get_timestamp;
+use crate::ws_model::{MarkPriceEvent, WebsocketEvent};
+
+/// Symbol and side of a position, the side is [`PositionSide::Both`] in one-way mode
+pub type PositionKey = (String, PositionSide);
+
+/// An open position
+#[derive(Debug, Clone)]
+pub struct TrackedPosition {
+    pub symbol: String,
+    pub position_side: PositionSide,
+    /// Signed amount, negative when short
+    pub amount: Decimal,
+    pub entry_price: Decimal,
+    pub break_even_price: Option<Decimal>,
+    pub mark_price: Decimal,
+    pub unrealized_pnl: Decimal,
+    pub leverage: u64,
+    pub margin_type: MarginType,
+    /// Wallet of an isolated position, zero in cross margin
+    pub isolated_wallet: Decimal,
+    /// Maintenance margin over notional as of the last account snapshot, zero for the positions
+    /// opened since, only used for the symbols the calculator has no brackets for
+    pub maintenance_margin_rate: Decimal,
+    /// Time of the account change this position reflects, in milliseconds
+    pub update_time: u64,
+}
+
+impl TrackedPosition {
+    fn new(symbol: &str, position_side: PositionSide) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            position_side,
+            amount: Decimal::ZERO,
+            entry_price: Decimal::ZERO,
+            break_even_price: None,
+            mark_price: Decimal::ZERO,
+            unrealized_pnl: Decimal::ZERO,
+            leverage: 1,
+            margin_type: MarginType::Cross,
+            isolated_wallet: Decimal::ZERO,
+            maintenance_margin_rate: Decimal::ZERO,
+            update_time: 0,
+        }
+    }
+
+    pub fn is_isolated(&self) -> bool { matches!(self.margin_type, MarginType::Isolated) }
+
+    /// Notional value at the mark price
+    pub fn notional(&self) -> Decimal { self.amount.abs() * self.mark_price }
+
+    fn set_mark_price(&mut self, mark_price: Decimal) {
+        self.mark_price = mark_price;
+        self.unrealized_pnl = self.amount * (mark_price - self.entry_price);
+    }
+}
+
+/// Wallet of a margin asset
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AssetWallet {
+    pub wallet_balance: Decimal,
+    pub cross_wallet_balance: Decimal,
+    /// Time of the account change this wallet reflects, in milliseconds
+    pub update_time: u64,
+}
+
+/// Margin ratio of a position crossing a risk threshold, the ratio is None when the margin balance
+/// is not positive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RiskEvent {
+    /// The margin ratio rose to or above the threshold
+    Breached {
+        threshold: Decimal,
+        margin_ratio: Option<Decimal>,
+    },
+    /// The margin ratio fell back below the threshold
+    Recovered {
+        threshold: Decimal,
+        margin_ratio: Option<Decimal>,
+    },
+}
+
+type RiskHandler<'a> = Box<dyn FnMut(&RiskEvent, &TrackedPosition) + 'a + Send>;
+
+/// Positions per symbol and side, wallets per asset
+pub struct PositionBook<'a> {
+    positions: HashMap<PositionKey, TrackedPosition>,
+    wallets: HashMap<String, AssetWallet>,
+    mark_prices: HashMap<String, (Decimal, u64)>,
+    calculator: Option<MarginCalculator>,
+    margin_asset: String,
+    thresholds: Vec<Decimal>,
+    breached: HashMap<PositionKey, usize>,
+    handler: Option<RiskHandler<'a>>,
+    resync_interval: Duration,
+    last_sync: u64,
+}
+
+impl<'a> Default for PositionBook<'a> {
+    fn default() -> Self {
+        Self {
+            positions: HashMap::new(),
+            wallets: HashMap::new(),
+            mark_prices: HashMap::new(),
+            calculator: None,
+            margin_asset: "USDT".to_string(),
+            thresholds: vec![],
+            breached: HashMap::new(),
+            handler: None,
+            resync_interval: Duration::from_secs(300),
+            last_sync: 0,
+        }
+    }
+}
+
+impl<'a> PositionBook<'a> {
+    pub fn new() -> Self { Self::default() }
+
+    /// Creates a book from the account information and the positions of the account, with a
+    /// calculator built from the leverage brackets of every symbol
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, config::*, futures::account::*, futures::market::*, futures::position_book::*};
+    /// let account: FuturesAccount = Binance::new_with_env(&Config::testnet());
+    /// let market: FuturesMarket = Binance::new_with_env(&Config::testnet());
+    /// let book = tokio_test::block_on(PositionBook::from_account(&account, &market));
+    /// assert!(book.is_ok(), "{:?}", book.err());
+    /// ```
+    pub async fn from_account(account: &FuturesAccount, market: &FuturesMarket) -> Result<PositionBook<'a>> {
+        let brackets = market.get_all_notional_leverage_brackets().await?;
+        let mut book = Self::default().set_calculator(MarginCalculator::new(brackets));
+        book.resync(account).await?;
+        Ok(book)
+    }
+
+    /// Calls the handler for every risk threshold crossed by the margin ratio of a position
+    pub fn with_handler<Callback>(mut self, handler: Callback) -> Self
+    where
+        Callback: FnMut(&RiskEvent, &TrackedPosition) + 'a + Send,
+    {
+        self.handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Sets the margin ratios at which the handler is called, a position is liquidated once its
+    /// margin ratio reaches 1
+    pub fn set_risk_thresholds(mut self, mut thresholds: Vec<Decimal>) -> Self {
+        thresholds.sort();
+        thresholds.dedup();
+        self.thresholds = thresholds;
+        self.breached.clear();
+        self
+    }
+
+    /// Computes maintenance margins from the leverage brackets instead of the rate of the last
+    /// snapshot, which also covers the positions opened since
+    pub fn set_calculator(mut self, calculator: MarginCalculator) -> Self {
+        self.calculator = Some(calculator);
+        self
+    }
+
+    /// Sets the asset the positions are margined in, USDT by default, the balances of other
+    /// assets are left out of the equity and margin balance
+    pub fn set_margin_asset<S: Into<String>>(mut self, margin_asset: S) -> Self {
+        self.margin_asset = margin_asset.into();
+        self
+    }
+
+    /// Sets how often [`PositionBook::resync_if_due`] refreshes the book from the REST API
+    pub fn set_resync_interval(mut self, resync_interval: Duration) -> Self {
+        self.resync_interval = resync_interval;
+        self
+    }
+
+    /// Applies the wallets and positions of the account, replacing those which did not change
+    /// since the snapshot was taken
+    pub fn apply_account_information(&mut self, information: &AccountInformation) {
+        for asset in &information.assets {
+            self.set_wallet(
+                &asset.asset,
+                asset.wallet_balance,
+                asset.cross_wallet_balance,
+                asset.update_time,
+            );
+        }
+        for account_position in &information.positions {
+            let key = (account_position.symbol.clone(), account_position.position_side.clone());
+            let Some(position) =
+                self.position_entry(key, account_position.position_amount, account_position.update_time)
+            else {
+                continue;
+            };
+            position.amount = account_position.position_amount;
+            position.entry_price = account_position.entry_price;
+            position.unrealized_pnl = account_position.unrealized_profit;
+            position.mark_price =
+                account_position.entry_price + account_position.unrealized_profit / account_position.position_amount;
+            position.leverage = account_position.leverage;
+            if account_position.isolated != position.is_isolated() {
+                position.margin_type = if account_position.isolated {
+                    MarginType::Isolated
+                } else {
+                    MarginType::Cross
+                };
+                position.isolated_wallet = Decimal::ZERO;
+            }
+            let notional = position.notional();
+            if notional > Decimal::ZERO {
+                position.maintenance_margin_rate = account_position.maintenance_margin / notional;
+            }
+            position.update_time = account_position.update_time;
+        }
+        self.check_risk();
+    }
+
+    /// Applies positions from the REST API, those older than the known positions are ignored
+    pub fn apply_position_information(&mut self, positions: &[Position]) {
+        for risk in positions {
+            let key = (risk.symbol.clone(), risk.position_side.clone());
+            let Some(position) = self.position_entry(key, risk.position_amount, risk.update_time) else {
+                continue;
+            };
+            position.amount = risk.position_amount;
+            position.entry_price = risk.entry_price;
+            position.break_even_price = risk.break_even_price;
+            position.mark_price = risk.mark_price;
+            position.unrealized_pnl = risk.unrealized_profit;
+            position.leverage = risk.leverage;
+            position.margin_type = risk.margin_type.clone();
+            position.isolated_wallet = risk.isolated_wallet;
+            position.update_time = risk.update_time;
+        }
+        self.check_risk();
+    }
+
+    /// Applies an `ACCOUNT_UPDATE` event, balances and positions older than the known ones are
+    /// ignored
+    pub fn apply_account_update(&mut self, update: &AccountUpdate) {
+        let time = update.transaction_time;
+        for balance in &update.account.balances {
+            self.set_wallet(
+                &balance.asset,
+                balance.wallet_balance,
+                balance.cross_wallet_balance,
+                time,
+            );
+        }
+        for event in &update.account.positions {
+            let mark_price = self.mark_prices.get(&event.symbol).map(|(price, _)| *price);
+            let key = (event.symbol.clone(), event.position_side.clone());
+            let Some(position) = self.position_entry(key, event.position_amount, time) else {
+                continue;
+            };
+            position.amount = event.position_amount;
+            position.entry_price = event.entry_price;
+            position.break_even_price = Some(event.breakeven_price);
+            position.margin_type = event.margin_type.clone();
+            position.isolated_wallet = event.isolated_wallet;
+            match mark_price {
+                Some(mark_price) => position.set_mark_price(mark_price),
+                None => {
+                    position.unrealized_pnl = event.unrealized_profit;
+                    position.mark_price = event.entry_price + event.unrealized_profit / event.position_amount;
+                }
+            }
+            position.update_time = time;
+        }
+        self.check_risk();
+    }
+
+    /// Marks the positions of a symbol to a price, prices older than the known one are ignored
+    pub fn apply_mark_price(&mut self, symbol: &str, mark_price: Decimal, time: u64) {
+        let known = self.mark_prices.entry(symbol.to_string()).or_default();
+        if time < known.1 {
+            return;
+        }
+        *known = (mark_price, time);
+        for position in self.positions.values_mut().filter(|p| p.symbol == symbol) {
+            position.set_mark_price(mark_price);
+        }
+        self.check_risk();
+    }
+
+    /// Applies a `markPriceUpdate` event, events with an unparsable price are ignored
+    pub fn apply_mark_price_event(&mut self, event: &MarkPriceEvent) {
+        if let Ok(mark_price) = event.mark_price.parse() {
+            self.apply_mark_price(&event.symbol, mark_price, event.event_time);
+        }
+    }
+
+    /// Applies the user stream events which change positions, ignoring the others
+    pub fn apply_event(&mut self, event: &ws_model::WebsocketEvent) {
+        if let ws_model::WebsocketEvent::AccountUpdate(update) = event {
+            self.apply_account_update(update);
+        }
+    }
+
+    /// Applies the market stream events which change mark prices, ignoring the others
+    pub fn apply_market_event(&mut self, event: &WebsocketEvent) {
+        if let WebsocketEvent::MarkPriceUpdate(event) = event {
+            self.apply_mark_price_event(event);
+        }
+    }
+
+    /// Refreshes the book from the REST API, the positions of every symbol with an open position
+    /// are queried to get their isolated wallets and mark prices
+    pub async fn resync(&mut self, account: &FuturesAccount) -> Result<()> {
+        let information = account.account_information().await?;
+        self.apply_account_information(&information);
+        let symbols: HashSet<String> = self.positions.values().map(|p| p.symbol.clone()).collect();
+        for symbol in symbols {
+            let positions = account.position_information(symbol).await?;
+            self.apply_position_information(&positions);
+        }
+        self.last_sync = get_timestamp()?;
+        Ok(())
+    }
+
+    /// Refreshes the book from the REST API if the resync interval elapsed, returns whether it did
+    pub async fn resync_if_due(&mut self, account: &FuturesAccount) -> Result<bool> {
+        if get_timestamp()?.saturating_sub(self.last_sync) < self.resync_interval.as_millis() as u64 {
+            return Ok(false);
+        }
+        self.resync(account).await?;
+        Ok(true)
+    }
+
+    pub fn position(&self, symbol: &str, position_side: &PositionSide) -> Option<&TrackedPosition> {
+        self.positions.get(&(symbol.to_string(), position_side.clone()))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = &TrackedPosition> { self.positions.values() }
+
+    /// Positions of a symbol, both sides in hedge mode
+    pub fn symbol_positions<'b>(&'b self, symbol: &'b str) -> impl Iterator<Item = &'b TrackedPosition> {
+        self.positions.values().filter(move |p| p.symbol == symbol)
+    }
+
+    /// Signed amount of a position, zero when there is none
+    pub fn size(&self, symbol: &str, position_side: &PositionSide) -> Decimal {
+        self.position(symbol, position_side)
+            .map(|p| p.amount)
+            .unwrap_or_default()
+    }
+
+    /// Signed amount of a symbol across both sides in hedge mode
+    pub fn net_size(&self, symbol: &str) -> Decimal { self.symbol_positions(symbol).map(|p| p.amount).sum() }
+
+    pub fn wallet(&self, asset: &str) -> Option<&AssetWallet> { self.wallets.get(asset) }
+
+    pub fn wallets(&self) -> &HashMap<String, AssetWallet> { &self.wallets }
+
+    pub fn unrealized_pnl(&self) -> Decimal { self.positions.values().map(|p| p.unrealized_pnl).sum() }
+
+    /// Wallet balance of the margin asset plus the unrealized PnL of all positions
+    pub fn equity(&self) -> Decimal { self.margin_wallet().wallet_balance + self.unrealized_pnl() }
+
+    /// Cross wallet balance of the margin asset plus the unrealized PnL of the cross margin
+    /// positions
+    pub fn cross_margin_balance(&self) -> Decimal {
+        let unrealized_pnl: Decimal = self
+            .positions
+            .values()
+            .filter(|p| !p.is_isolated())
+            .map(|p| p.unrealized_pnl)
+            .sum();
+        self.margin_wallet().cross_wallet_balance + unrealized_pnl
+    }
+
+    fn margin_wallet(&self) -> AssetWallet { self.wallet(&self.margin_asset).cloned().unwrap_or_default() }
+
+    /// Maintenance margin of a position at its mark price
+    pub fn maintenance_margin(&self, position: &TrackedPosition) -> Decimal {
+        self.calculator
+            .as_ref()
+            .and_then(|calculator| {
+                let margin_position = MarginPosition::new(
+                    position.symbol.clone(),
+                    position.amount,
+                    position.entry_price,
+                    position.leverage,
+                )
+                .set_mark_price(position.mark_price);
+                calculator.maintenance_margin(&margin_position).ok()
+            })
+            .unwrap_or_else(|| position.notional() * position.maintenance_margin_rate)
+    }
+
+    /// Maintenance margin over margin balance of the cross margin positions, None when the margin
+    /// balance is not positive
+    pub fn cross_margin_ratio(&self) -> Option<Decimal> {
+        let maintenance_margin: Decimal = self
+            .positions
+            .values()
+            .filter(|p| !p.is_isolated())
+            .map(|p| self.maintenance_margin(p))
+            .sum();
+        ratio(maintenance_margin, self.cross_margin_balance())
+    }
+
+    /// Margin ratio of a position, of its isolated wallet when isolated or of the account when
+    /// cross, None when there is no such position or its margin balance is not positive
+    pub fn margin_ratio(&self, symbol: &str, position_side: &PositionSide) -> Option<Decimal> {
+        self.position(symbol, position_side)
+            .and_then(|position| self.position_margin_ratio(position))
+    }
+
+    fn position_margin_ratio(&self, position: &TrackedPosition) -> Option<Decimal> {
+        if position.is_isolated() {
+            ratio(
+                self.maintenance_margin(position),
+                position.isolated_wallet + position.unrealized_pnl,
+            )
+        } else {
+            self.cross_margin_ratio()
+        }
+    }
+
+    /// Entry of a position changed at this time, None when the change is older than the known
+    /// position or closes it
+    fn position_entry(&mut self, key: PositionKey, amount: Decimal, update_time: u64) -> Option<&mut TrackedPosition> {
+        if self.positions.get(&key).is_some_and(|p| update_time < p.update_time) {
+            return None;
+        }
+        if amount.is_zero() {
+            self.positions.remove(&key);
+            return None;
+        }
+        let (symbol, position_side) = &key;
+        let position = TrackedPosition::new(symbol, position_side.clone());
+        Some(self.positions.entry(key).or_insert(position))
+    }
+
+    fn set_wallet(&mut self, asset: &str, wallet_balance: Decimal, cross_wallet_balance: Decimal, update_time: u64) {
+        let wallet = self.wallets.entry(asset.to_string()).or_default();
+        if update_time >= wallet.update_time {
+            *wallet = AssetWallet {
+                wallet_balance,
+                cross_wallet_balance,
+                update_time,
+            };
+        }
+    }
+
+    fn check_risk(&mut self) {
+        let mut events = vec![];
+        let cross_margin_ratio = self.cross_margin_ratio();
+        for (key, position) in &self.positions {
+            let margin_ratio = if position.is_isolated() {
+                self.position_margin_ratio(position)
+            } else {
+                cross_margin_ratio
+            };
+            let level = match margin_ratio {
+                Some(margin_ratio) => self.thresholds.iter().filter(|t| margin_ratio >= **t).count(),
+                None => self.thresholds.len(),
+            };
+            let previous = self.breached.get(key).copied().unwrap_or_default();
+            for threshold in self.thresholds.iter().take(level).skip(previous) {
+                events.push((
+                    key.clone(),
+                    RiskEvent::Breached {
+                        threshold: *threshold,
+                        margin_ratio,
+                    },
+                ));
+            }
+            for threshold in self.thresholds.iter().take(previous).skip(level).rev() {
+                events.push((
+                    key.clone(),
+                    RiskEvent::Recovered {
+                        threshold: *threshold,
+                        margin_ratio,
+                    },
+                ));
+            }
+        }
+        // Closed positions no longer have a margin ratio to report
+        let positions = &self.positions;
+        self.breached.retain(|key, _| positions.contains_key(key));
+        for (key, event) in events {
+            let level = self.breached.entry(key.clone()).or_default();
+            match event {
+                RiskEvent::Breached { .. } => *level += 1,
+                RiskEvent::Recovered { .. } => *level -= 1,
+            }
+            if let (Some(handler), Some(position)) = (self.handler.as_mut(), self.positions.get(&key)) {
+                handler(&event, position);
+            }
+        }
+    }
+}
+
+fn ratio(maintenance_margin: Decimal, margin_balance: Decimal) -> Option<Decimal> {
+    if margin_balance > Decimal::ZERO {
+        Some(maintenance_margin / margin_balance)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::futures::rest_model::{LeverageBracket, SymbolBrackets};
+    use crate::futures::ws_model::{Account, Balance, ReasonType};
+    use rust_decimal_macros::dec;
+    use std::sync::{Arc, Mutex};
+
+    fn account_update(time: u64, wallet: Decimal, positions: Vec<ws_model::Position>) -> AccountUpdate {
+        AccountUpdate {
+            event_time: time,
+            transaction_time: time,
+            account: Account {
+                reason_type: ReasonType::Order,
+                balances: vec![Balance {
+                    asset: "USDT".to_string(),
+                    wallet_balance: wallet,
+                    cross_wallet_balance: wallet,
+                    balance_change: Decimal::ZERO,
+                }],
+                positions,
+            },
+        }
+    }
+
+    fn position(side: PositionSide, amount: Decimal, entry_price: Decimal) -> ws_model::Position {
+        ws_model::Position {
+            symbol: "BTCUSDT".to_string(),
+            position_amount: amount,
+            entry_price,
+            breakeven_price: entry_price,
+            accumulated_realized: Decimal::ZERO,
+            unrealized_profit: Decimal::ZERO,
+            margin_type: MarginType::Cross,
+            isolated_wallet: Decimal::ZERO,
+            position_side: side,
+        }
+    }
+
+    #[test]
+    fn hedge_sides_are_marked_to_market() {
+        let mut book = PositionBook::new();
+        let mut update = account_update(
+            1,
+            dec!(1000),
+            vec![
+                position(PositionSide::Long, dec!(0.2), dec!(20000)),
+                position(PositionSide::Short, dec!(-0.1), dec!(21000)),
+            ],
+        );
+        // Balances of other assets are not counted in the equity
+        update.account.balances.push(Balance {
+            asset: "BNB".to_string(),
+            wallet_balance: dec!(2),
+            cross_wallet_balance: dec!(2),
+            balance_change: Decimal::ZERO,
+        });
+        book.apply_account_update(&update);
+        book.apply_mark_price("BTCUSDT", dec!(20500), 2);
+
+        let long = book.position("BTCUSDT", &PositionSide::Long).unwrap();
+        assert_eq!(long.unrealized_pnl, dec!(100));
+        assert_eq!(book.size("BTCUSDT", &PositionSide::Short), dec!(-0.1));
+        assert_eq!(book.net_size("BTCUSDT"), dec!(0.1));
+        assert_eq!(book.unrealized_pnl(), dec!(150));
+        assert_eq!(book.equity(), dec!(1150));
+
+        // A stale mark price and a stale update are ignored, closing removes the position
+        book.apply_mark_price("BTCUSDT", dec!(10000), 1);
+        book.apply_account_update(&account_update(0, dec!(0), vec![]));
+        assert_eq!(book.equity(), dec!(1150));
+        book.apply_account_update(&account_update(
+            3,
+            dec!(1050),
+            vec![position(PositionSide::Short, Decimal::ZERO, Decimal::ZERO)],
+        ));
+        assert!(book.position("BTCUSDT", &PositionSide::Short).is_none());
+        assert_eq!(book.equity(), dec!(1150));
+    }
+
+    #[test]
+    fn risk_thresholds_call_the_handler_once_per_crossing() {
+        let events = Arc::new(Mutex::new(vec![]));
+        let recorded = events.clone();
+        // 0.5% of the notional is maintenance margin
+        let calculator = MarginCalculator::new(vec![SymbolBrackets {
+            symbol: "BTCUSDT".to_string(),
+            notional_coef: None,
+            brackets: vec![LeverageBracket {
+                bracket: 1,
+                initial_leverage: 125,
+                notional_cap: 50000,
+                notional_floor: 0,
+                maint_margin_ratio: dec!(0.005),
+                cum: dec!(0),
+            }],
+        }]);
+        let mut book = PositionBook::new()
+            .set_calculator(calculator)
+            .set_risk_thresholds(vec![dec!(0.8), dec!(0.5)])
+            .with_handler(move |event: &RiskEvent, _: &TrackedPosition| recorded.lock().unwrap().push(event.clone()));
+        // The position is opened after the snapshot, without a maintenance margin rate
+        book.apply_account_update(&account_update(
+            1,
+            dec!(100),
+            vec![position(PositionSide::Both, dec!(1), dec!(1000))],
+        ));
+        book.apply_mark_price("BTCUSDT", dec!(1000), 1);
+        assert_eq!(book.margin_ratio("BTCUSDT", &PositionSide::Both), Some(dec!(0.05)));
+        assert!(events.lock().unwrap().is_empty());
+
+        book.apply_mark_price("BTCUSDT", dec!(910), 2);
+        book.apply_mark_price("BTCUSDT", dec!(908), 3);
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![RiskEvent::Breached {
+                threshold: dec!(0.5),
+                margin_ratio: Some(dec!(0.5675)),
+            }]
+        );
+
+        book.apply_mark_price("BTCUSDT", dec!(890), 4);
+        book.apply_mark_price("BTCUSDT", dec!(1000), 5);
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 4);
+        assert_eq!(
+            events[1],
+            RiskEvent::Breached {
+                threshold: dec!(0.8),
+                margin_ratio: None,
+            }
+        );
+        assert_eq!(
+            events[2],
+            RiskEvent::Recovered {
+                threshold: dec!(0.8),
+                margin_ratio: Some(dec!(0.05)),
+            }
+        );
+        assert!(matches!(events[3], RiskEvent::Recovered { threshold, .. } if threshold == dec!(0.5)));
+    }
+}
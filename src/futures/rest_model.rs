@@ -6,6 +6,7 @@ pub use crate::rest_model::{
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -70,9 +71,11 @@ pub enum ContractType {
     PerpetualDelivering,
     #[serde(rename = "")]
     Empty,
+    #[serde(other)]
+    Other,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum OrderType {
     Limit,
@@ -91,7 +94,7 @@ impl Default for OrderType {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum PositionSide {
     Both,
@@ -139,9 +142,12 @@ pub enum Filters {
     #[serde(rename = "MARKET_LOT_SIZE")]
     #[serde(rename_all = "camelCase")]
     MarketLotSize {
-        min_qty: String,
-        max_qty: String,
-        step_size: String,
+        #[serde(with = "string_or_float")]
+        min_qty: Decimal,
+        #[serde(with = "string_or_float")]
+        max_qty: Decimal,
+        #[serde(with = "string_or_float")]
+        step_size: Decimal,
     },
     #[serde(rename = "MAX_NUM_ORDERS")]
     #[serde(rename_all = "camelCase")]
@@ -344,8 +350,13 @@ pub struct Order {
     pub activate_price: Decimal,
     #[serde(with = "string_or_float", default = "default_price_rate")]
     pub price_rate: Decimal,
+    /// Creation time, not included in every response
+    #[serde(default)]
+    pub time: u64,
     pub update_time: u64,
     pub working_type: WorkingType,
+    /// Not included in force orders
+    #[serde(default)]
     pub price_protect: bool,
 }
 
@@ -360,8 +371,11 @@ pub struct Transaction {
     #[serde(with = "string_or_float")]
     pub executed_qty: Decimal,
     pub order_id: u64,
-    #[serde(with = "string_or_float")]
+    /// Not included in cancellation responses
+    #[serde(default, with = "string_or_float")]
     pub avg_price: Decimal,
+    #[serde(default, with = "string_or_float")]
+    pub price: Decimal,
     #[serde(with = "string_or_float")]
     pub orig_qty: Decimal,
     pub reduce_only: bool,
@@ -454,6 +468,8 @@ pub struct Position {
     pub notional: Decimal,
     #[serde(with = "string_or_float")]
     pub isolated_wallet: Decimal,
+    #[serde(default, with = "string_or_float_opt")]
+    pub break_even_price: Option<Decimal>,
 }
 
 // https://binance-docs.github.io/apidocs/futures/en/#account-information-v2-user_data
@@ -582,6 +598,173 @@ pub struct ChangeLeverageResponse {
     pub symbol: String,
 }
 
+/// Generic acknowledgement, such as `{"code": 200, "msg": "success"}`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StatusResponse {
+    pub code: i32,
+    pub msg: String,
+}
+
+#[derive(Debug, Serialize_repr, Deserialize_repr, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum PositionMarginType {
+    Add = 1,
+    Reduce = 2,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionMarginResponse {
+    pub amount: Decimal,
+    pub code: i32,
+    pub msg: String,
+    #[serde(rename = "type")]
+    pub margin_type: PositionMarginType,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionMarginChange {
+    pub symbol: String,
+    #[serde(rename = "type")]
+    pub margin_type: PositionMarginType,
+    /// Origin of the change, such as `TRADE` or `USER_ADJUST`
+    #[serde(default)]
+    pub delta_type: Option<String>,
+    #[serde(with = "string_or_float")]
+    pub amount: Decimal,
+    pub asset: String,
+    pub time: u64,
+    pub position_side: PositionSide,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CountdownCancelAll {
+    pub symbol: String,
+    /// Countdown in milliseconds, 0 when canceled
+    #[serde(with = "string_or_u64")]
+    pub countdown_time: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionMode {
+    /// Hedge mode when true, one-way mode otherwise
+    pub dual_side_position: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiAssetsMode {
+    pub multi_assets_margin: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum IncomeType {
+    Transfer,
+    WelcomeBonus,
+    RealizedPnl,
+    FundingFee,
+    Commission,
+    InsuranceClear,
+    ReferralKickback,
+    CommissionRebate,
+    ApiRebate,
+    ContestReward,
+    CrossCollateralTransfer,
+    OptionsPremiumFee,
+    OptionsSettleProfit,
+    InternalTransfer,
+    AutoExchange,
+    #[serde(rename = "DELIVERED_SETTELMENT")]
+    DeliveredSettlement,
+    CoinSwapDeposit,
+    CoinSwapWithdraw,
+    PositionLimitIncreaseFee,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Income {
+    /// Empty for incomes which are not related to a symbol, such as transfers
+    pub symbol: String,
+    pub income_type: IncomeType,
+    #[serde(with = "string_or_float")]
+    pub income: Decimal,
+    pub asset: String,
+    pub info: String,
+    pub time: u64,
+    #[serde(with = "string_or_u64")]
+    pub tran_id: u64,
+    /// Empty for incomes which are not related to a trade
+    pub trade_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UserTrade {
+    pub buyer: bool,
+    #[serde(with = "string_or_float")]
+    pub commission: Decimal,
+    pub commission_asset: String,
+    pub id: u64,
+    pub maker: bool,
+    pub order_id: u64,
+    #[serde(with = "string_or_float")]
+    pub price: Decimal,
+    #[serde(with = "string_or_float")]
+    pub qty: Decimal,
+    #[serde(with = "string_or_float")]
+    pub quote_qty: Decimal,
+    #[serde(with = "string_or_float")]
+    pub realized_pnl: Decimal,
+    pub side: OrderSide,
+    pub position_side: PositionSide,
+    pub symbol: String,
+    pub time: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CommissionRate {
+    pub symbol: String,
+    #[serde(with = "string_or_float")]
+    pub maker_commission_rate: Decimal,
+    #[serde(with = "string_or_float")]
+    pub taker_commission_rate: Decimal,
+}
+
+/// Auto-deleveraging queue position of a symbol, from 0 to 4, a higher quantile being more likely
+/// to be deleveraged
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AdlQuantile {
+    pub symbol: String,
+    pub adl_quantile: AdlQuantiles,
+}
+
+/// Quantiles per position side, `both` in one-way mode and `long`, `short` and `hedge` in hedge mode
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub struct AdlQuantiles {
+    pub long: Option<u8>,
+    pub short: Option<u8>,
+    pub both: Option<u8>,
+    /// The larger quantile of the long and short positions, when both are open
+    pub hedge: Option<u8>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AutoCloseType {
+    Liquidation,
+    Adl,
+}
+
 fn default_stop_price() -> Decimal {
     dec!(0.0)
 }
@@ -606,13 +789,49 @@ pub(crate) struct HistoryQuery {
 
 impl HistoryQuery {
     pub fn validate(&self) -> crate::errors::Result<()> {
-        if let Some(period) = &self.period {
-            if !PERIODS.contains(&period.as_str()) {
-                return Err(crate::errors::Error::InvalidPeriod(period.clone()));
-            }
+        validate_history(self.period.as_deref(), self.start_time, self.end_time)
+    }
+}
+
+/// Checks the period and time range shared by the history queries
+pub(crate) fn validate_history(
+    period: Option<&str>,
+    start_time: Option<u64>,
+    end_time: Option<u64>,
+) -> crate::errors::Result<()> {
+    if let Some(period) = period {
+        if !PERIODS.contains(&period) {
+            return Err(crate::errors::Error::InvalidPeriod(period.to_string()));
         }
-        Ok(())
     }
+    if let (Some(start_time), Some(end_time)) = (start_time, end_time) {
+        if start_time > end_time {
+            return Err(crate::errors::Error::InvalidTimeRange { start_time, end_time });
+        }
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BasisQuery {
+    pub pair: String,
+    pub contract_type: ContractType,
+    pub period: String,
+    pub limit: u16,
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+}
+
+impl BasisQuery {
+    pub fn validate(&self) -> crate::errors::Result<()> {
+        validate_history(Some(&self.period), self.start_time, self.end_time)
+    }
+}
+
+#[derive(Serialize)]
+pub(crate) struct PairNameQuery {
+    pub pair: String,
 }
 
 #[derive(Serialize)]
@@ -634,6 +853,20 @@ pub struct FundingRate {
     pub funding_rate: Decimal,
 }
 
+/// Funding parameters of a symbol whose cap, floor or interval were adjusted
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FundingInfo {
+    pub symbol: String,
+    #[serde(with = "string_or_float")]
+    pub adjusted_funding_rate_cap: Decimal,
+    #[serde(with = "string_or_float")]
+    pub adjusted_funding_rate_floor: Decimal,
+    pub funding_interval_hours: u64,
+    #[serde(default)]
+    pub disclaimer: bool,
+}
+
 pub static PERIODS: &[&str] = &["5m", "15m", "30m", "1h", "2h", "4h", "6h", "12h", "1d"];
 
 #[derive(Deserialize)]
@@ -660,7 +893,95 @@ pub struct LongShortRatio {
     pub timestamp: u64,
 }
 
-#[derive(Deserialize)]
+/// Taker buy and sell volumes over a period
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TakerVolume {
+    #[serde(with = "string_or_float")]
+    pub buy_sell_ratio: Decimal,
+    #[serde(with = "string_or_float")]
+    pub buy_vol: Decimal,
+    #[serde(with = "string_or_float")]
+    pub sell_vol: Decimal,
+    pub timestamp: u64,
+}
+
+/// Difference between the futures price and the index price of a pair
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Basis {
+    pub pair: String,
+    pub contract_type: ContractType,
+    #[serde(with = "string_or_float")]
+    pub futures_price: Decimal,
+    #[serde(with = "string_or_float")]
+    pub index_price: Decimal,
+    #[serde(with = "string_or_float")]
+    pub basis: Decimal,
+    #[serde(with = "string_or_float")]
+    pub basis_rate: Decimal,
+    /// Empty for perpetuals
+    #[serde(default, with = "string_or_float_opt")]
+    pub annualized_basis_rate: Option<Decimal>,
+    pub timestamp: u64,
+}
+
+/// Settlement price of a delivered quarterly contract
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DeliveryPrice {
+    pub delivery_time: u64,
+    #[serde(with = "string_or_float")]
+    pub delivery_price: Decimal,
+}
+
+/// Exchanges and pairs of which the index price of a symbol is composed
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexConstituents {
+    pub symbol: String,
+    pub time: u64,
+    pub constituents: Vec<IndexConstituent>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexConstituent {
+    pub exchange: String,
+    pub symbol: String,
+    #[serde(default, with = "string_or_float_opt")]
+    pub price: Option<Decimal>,
+    #[serde(default, with = "string_or_float_opt")]
+    pub weight: Option<Decimal>,
+}
+
+/// Index of a multi-assets mode margin asset, with the haircuts applied to it as collateral
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetIndex {
+    pub symbol: String,
+    pub time: u64,
+    #[serde(with = "string_or_float")]
+    pub index: Decimal,
+    #[serde(with = "string_or_float")]
+    pub bid_buffer: Decimal,
+    #[serde(with = "string_or_float")]
+    pub ask_buffer: Decimal,
+    #[serde(with = "string_or_float")]
+    pub bid_rate: Decimal,
+    #[serde(with = "string_or_float")]
+    pub ask_rate: Decimal,
+    #[serde(with = "string_or_float")]
+    pub auto_exchange_bid_buffer: Decimal,
+    #[serde(with = "string_or_float")]
+    pub auto_exchange_ask_buffer: Decimal,
+    #[serde(with = "string_or_float")]
+    pub auto_exchange_bid_rate: Decimal,
+    #[serde(with = "string_or_float")]
+    pub auto_exchange_ask_rate: Decimal,
+}
+
+#[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct LeverageBracket {
     pub bracket: u8,
@@ -671,10 +992,109 @@ pub struct LeverageBracket {
     pub cum: Decimal,
 }
 
-#[derive(Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SymbolBrackets {
     pub symbol: String,
     pub notional_coef: Option<Decimal>,
     pub brackets: Vec<LeverageBracket>,
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn income_serde() {
+        let incomes: Vec<Income> = serde_json::from_str(
+            r#"[
+                {"symbol": "", "incomeType": "TRANSFER", "income": "-0.37500000", "asset": "USDT", "info": "TRANSFER",
+                 "time": 1570608000000, "tranId": 9689322392, "tradeId": ""},
+                {"symbol": "BTCUSDT", "incomeType": "COMMISSION", "income": "-0.01000000", "asset": "USDT",
+                 "info": "COMMISSION", "time": 1570636800000, "tranId": "9689322392", "tradeId": "2059192"}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(incomes[0].income_type, IncomeType::Transfer);
+        assert_eq!(incomes[0].income, dec!(-0.375));
+        assert_eq!(incomes[1].tran_id, 9689322392);
+        assert_eq!(incomes[1].trade_id, "2059192");
+
+        let income_type = |name: &str| serde_json::from_str::<IncomeType>(&format!("\"{name}\"")).unwrap();
+        assert_eq!(income_type("FUNDING_FEE"), IncomeType::FundingFee);
+        assert_eq!(income_type("DELIVERED_SETTELMENT"), IncomeType::DeliveredSettlement);
+        assert_eq!(
+            income_type("POSITION_LIMIT_INCREASE_FEE"),
+            IncomeType::PositionLimitIncreaseFee
+        );
+        assert_eq!(income_type("STRATEGY_UMFUTURES_TRANSFER"), IncomeType::Other);
+        assert_eq!(
+            serde_json::to_string(&IncomeType::CrossCollateralTransfer).unwrap(),
+            "\"CROSS_COLLATERAL_TRANSFER\""
+        );
+    }
+
+    #[test]
+    fn user_trade_serde() {
+        let trade: UserTrade = serde_json::from_str(
+            r#"{"buyer": false, "commission": "-0.07819010", "commissionAsset": "USDT", "id": 698759,
+                "maker": false, "orderId": 25851813, "price": "7819.01", "qty": "0.002", "quoteQty": "15.63802",
+                "realizedPnl": "-0.91539999", "side": "SELL", "positionSide": "SHORT", "symbol": "BTCUSDT",
+                "time": 1569514978020}"#,
+        )
+        .unwrap();
+        assert_eq!(trade.side, OrderSide::Sell);
+        assert_eq!(trade.position_side, PositionSide::Short);
+        assert_eq!(trade.realized_pnl, dec!(-0.91539999));
+        assert_eq!(trade.quote_qty, dec!(15.63802));
+    }
+
+    #[test]
+    fn commission_rate_and_adl_quantile_serde() {
+        let rate: CommissionRate = serde_json::from_str(
+            r#"{"symbol": "BTCUSDT", "makerCommissionRate": "0.0002", "takerCommissionRate": "0.0004"}"#,
+        )
+        .unwrap();
+        assert_eq!(rate.maker_commission_rate, dec!(0.0002));
+        assert_eq!(rate.taker_commission_rate, dec!(0.0004));
+
+        let quantiles: Vec<AdlQuantile> = serde_json::from_str(
+            r#"[
+                {"symbol": "ETHUSDT", "adlQuantile": {"LONG": 3, "SHORT": 3, "HEDGE": 0}},
+                {"symbol": "BTCUSDT", "adlQuantile": {"LONG": 1, "SHORT": 2, "BOTH": 0}}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(quantiles[0].adl_quantile.hedge, Some(0));
+        assert_eq!(quantiles[0].adl_quantile.both, None);
+        assert_eq!(quantiles[1].adl_quantile.short, Some(2));
+        assert_eq!(quantiles[1].adl_quantile.both, Some(0));
+    }
+
+    #[test]
+    fn taker_volume_serde() {
+        let volumes: Vec<TakerVolume> = serde_json::from_str(
+            r#"[{"buySellRatio": "1.5586", "buyVol": "387.3300", "sellVol": "248.5030", "timestamp": 1585614900000}]"#,
+        )
+        .unwrap();
+        assert_eq!(volumes[0].buy_sell_ratio, dec!(1.5586));
+        assert_eq!(volumes[0].sell_vol, dec!(248.503));
+    }
+
+    #[test]
+    fn force_order_serde() {
+        let orders: Vec<Order> = serde_json::from_str(
+            r#"[{"orderId": 6071832819, "symbol": "BTCUSDT", "status": "FILLED",
+                 "clientOrderId": "autoclose-1596107620040000020", "price": "10871.09", "avgPrice": "10913.21000",
+                 "origQty": "0.001", "executedQty": "0.001", "cumQuote": "10.91321", "timeInForce": "IOC",
+                 "type": "LIMIT", "reduceOnly": false, "closePosition": false, "side": "SELL", "positionSide": "BOTH",
+                 "stopPrice": "0", "workingType": "CONTRACT_PRICE", "origType": "LIMIT", "time": 1596107620044,
+                 "updateTime": 1596107620087}]"#,
+        )
+        .unwrap();
+        assert_eq!(orders[0].status, OrderStatus::Filled);
+        assert_eq!(orders[0].avg_price, dec!(10913.21));
+        assert_eq!(orders[0].time, 1596107620044);
+        assert!(!orders[0].price_protect);
+    }
+}
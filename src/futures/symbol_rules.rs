@@ -0,0 +1,508 @@
+//! Trading rules of USD-M futures symbols.
+//!
+//! [`SymbolRegistry`] caches the filters of [`FuturesGeneral::exchange_info`] so that
+//! [`OrderRequest`]s can be rounded to the tick and step sizes and checked locally before they are
+//! sent, instead of being rejected by the exchange. The registry is refreshed on demand, since the
+//! rules of a symbol rarely change.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use rust_decimal::Decimal;
+
+use crate::errors::*;
+use crate::futures::account::{FuturesAccount, OrderRequest};
+use crate::futures::general::FuturesGeneral;
+use crate::futures::rest_model::{ContractType, ExchangeInformation, Filters, OrderType, Symbol, Transaction};
+use crate::rest_model::{OrderSide, SymbolStatus};
+use crate::util::get_timestamp;
+
+/// Minimum, maximum and step of a quantity, a zero bound is not enforced
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LotRules {
+    pub min_qty: Decimal,
+    pub max_qty: Decimal,
+    pub step_size: Decimal,
+}
+
+/// Rules of a symbol, extracted from its filters
+#[derive(Debug, Clone)]
+pub struct SymbolRules {
+    pub symbol: String,
+    pub status: SymbolStatus,
+    pub contract_type: ContractType,
+    pub price_precision: u16,
+    pub quantity_precision: u16,
+    pub min_price: Decimal,
+    pub max_price: Decimal,
+    pub tick_size: Decimal,
+    pub lot_size: LotRules,
+    pub market_lot_size: LotRules,
+    pub min_notional: Decimal,
+    /// Bounds of the price of an order relative to the mark price
+    pub multiplier_up: Option<Decimal>,
+    pub multiplier_down: Option<Decimal>,
+    pub max_num_orders: Option<u16>,
+    pub order_types: Vec<OrderType>,
+}
+
+impl From<&Symbol> for SymbolRules {
+    fn from(symbol: &Symbol) -> Self {
+        let mut rules = Self {
+            symbol: symbol.symbol.clone(),
+            status: symbol.status.clone(),
+            contract_type: symbol.contract_type.clone(),
+            price_precision: symbol.price_precision,
+            quantity_precision: symbol.quantity_precision,
+            min_price: Decimal::ZERO,
+            max_price: Decimal::ZERO,
+            tick_size: Decimal::ZERO,
+            lot_size: LotRules::default(),
+            market_lot_size: LotRules::default(),
+            min_notional: Decimal::ZERO,
+            multiplier_up: None,
+            multiplier_down: None,
+            max_num_orders: None,
+            order_types: symbol.order_types.clone(),
+        };
+        for filter in &symbol.filters {
+            match filter {
+                Filters::PriceFilter {
+                    min_price,
+                    max_price,
+                    tick_size,
+                } => {
+                    rules.min_price = *min_price;
+                    rules.max_price = *max_price;
+                    rules.tick_size = *tick_size;
+                }
+                Filters::LotSize {
+                    min_qty,
+                    max_qty,
+                    step_size,
+                } => {
+                    rules.lot_size = LotRules {
+                        min_qty: *min_qty,
+                        max_qty: *max_qty,
+                        step_size: *step_size,
+                    }
+                }
+                Filters::MarketLotSize {
+                    min_qty,
+                    max_qty,
+                    step_size,
+                } => {
+                    rules.market_lot_size = LotRules {
+                        min_qty: *min_qty,
+                        max_qty: *max_qty,
+                        step_size: *step_size,
+                    }
+                }
+                Filters::MinNotional { notional } => rules.min_notional = *notional,
+                Filters::PercentPrice {
+                    multiplier_up,
+                    multiplier_down,
+                    ..
+                } => {
+                    rules.multiplier_up = Some(*multiplier_up);
+                    rules.multiplier_down = Some(*multiplier_down);
+                }
+                Filters::MaxNumOrders { limit } => rules.max_num_orders = Some(*limit),
+                _ => {}
+            }
+        }
+        rules
+    }
+}
+
+impl SymbolRules {
+    /// Whether orders of this type are filled at the market, and use the market lot size
+    pub fn is_market(order_type: &OrderType) -> bool {
+        matches!(
+            order_type,
+            OrderType::Market | OrderType::StopMarket | OrderType::TakeProfitMarket | OrderType::TrailingStopMarket
+        )
+    }
+
+    /// Lot rules of an order type
+    pub fn lot(&self, order_type: &OrderType) -> &LotRules {
+        if Self::is_market(order_type) && !self.market_lot_size.step_size.is_zero() {
+            &self.market_lot_size
+        } else {
+            &self.lot_size
+        }
+    }
+
+    /// Rounds a price to the tick size, down when buying and up when selling so that the order
+    /// never gets a worse price than requested
+    pub fn round_price(&self, price: Decimal, side: &OrderSide) -> Decimal {
+        if self.tick_size.is_zero() {
+            return price;
+        }
+        let ticks = (price - self.min_price) / self.tick_size;
+        let ticks = match side {
+            OrderSide::Buy => ticks.floor(),
+            OrderSide::Sell => ticks.ceil(),
+        };
+        (self.min_price + ticks * self.tick_size).normalize()
+    }
+
+    /// Rounds a quantity down to the step size of an order type
+    pub fn round_qty(&self, qty: Decimal, order_type: &OrderType) -> Decimal {
+        let lot = self.lot(order_type);
+        if lot.step_size.is_zero() {
+            return qty;
+        }
+        (lot.min_qty + ((qty - lot.min_qty) / lot.step_size).floor() * lot.step_size).normalize()
+    }
+
+    /// Rounds the prices and quantity of an order
+    pub fn normalize(&self, order: &mut OrderRequest) {
+        for price in [&mut order.price, &mut order.stop_price, &mut order.activation_price]
+            .into_iter()
+            .flatten()
+        {
+            *price = self.round_price(*price, &order.side);
+        }
+        if let Some(qty) = order.quantity.as_mut() {
+            *qty = self.round_qty(*qty, &order.order_type);
+        }
+    }
+
+    /// Checks an order with [`OrderRequest::valid`] and against the rules, the mark price is
+    /// required to check the notional of orders without a price and the `PERCENT_PRICE` bounds
+    pub fn validate(&self, order: &OrderRequest, mark_price: Option<Decimal>) -> Result<()> {
+        let error = |msg: String| {
+            Err(Error::InvalidOrderError {
+                msg: format!("{msg} on {}", self.symbol),
+            })
+        };
+        if self.status != SymbolStatus::Trading {
+            return error(format!("trading is not allowed with status {:?}", self.status));
+        }
+        if !self.order_types.contains(&order.order_type) {
+            return error(format!("{:?} orders are not supported", order.order_type));
+        }
+        order.valid()?;
+        let reduce_only = order.reduce_only.unwrap_or(false) || order.close_position.unwrap_or(false);
+
+        for price in [order.price, order.stop_price, order.activation_price]
+            .into_iter()
+            .flatten()
+        {
+            if (!self.min_price.is_zero() && price < self.min_price)
+                || (!self.max_price.is_zero() && price > self.max_price)
+            {
+                return error(format!(
+                    "PRICE_FILTER: price {price} is outside [{}, {}]",
+                    self.min_price, self.max_price
+                ));
+            }
+            if !self.tick_size.is_zero() && !((price - self.min_price) % self.tick_size).is_zero() {
+                return error(format!(
+                    "PRICE_FILTER: price {price} is not a multiple of the tick size {}",
+                    self.tick_size
+                ));
+            }
+        }
+
+        if let Some(qty) = order.quantity {
+            let filter = if Self::is_market(&order.order_type) && !self.market_lot_size.step_size.is_zero() {
+                "MARKET_LOT_SIZE"
+            } else {
+                "LOT_SIZE"
+            };
+            let lot = self.lot(&order.order_type);
+            if (!lot.min_qty.is_zero() && qty < lot.min_qty) || (!lot.max_qty.is_zero() && qty > lot.max_qty) {
+                return error(format!(
+                    "{filter}: quantity {qty} is outside [{}, {}]",
+                    lot.min_qty, lot.max_qty
+                ));
+            }
+            if !lot.step_size.is_zero() && !((qty - lot.min_qty) % lot.step_size).is_zero() {
+                return error(format!(
+                    "{filter}: quantity {qty} is not a multiple of the step size {}",
+                    lot.step_size
+                ));
+            }
+            // Orders which can only reduce a position are exempt from the minimum notional
+            let price = order.price.or(order.stop_price).or(mark_price);
+            if let (false, Some(price)) = (reduce_only, price) {
+                if qty * price < self.min_notional {
+                    return error(format!(
+                        "MIN_NOTIONAL: notional {} is below {}",
+                        qty * price,
+                        self.min_notional
+                    ));
+                }
+            }
+        }
+
+        if let (Some(price), Some(mark_price)) = (order.price, mark_price) {
+            if let Some(up) = self.multiplier_up.filter(|_| order.side == OrderSide::Buy) {
+                if price > mark_price * up {
+                    return error(format!("PERCENT_PRICE: price {price} is above {}", mark_price * up));
+                }
+            }
+            if let Some(down) = self.multiplier_down.filter(|_| order.side == OrderSide::Sell) {
+                if price < mark_price * down {
+                    return error(format!("PERCENT_PRICE: price {price} is below {}", mark_price * down));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that one more order can be opened on the symbol
+    pub fn check_open_orders(&self, open_orders: usize) -> Result<()> {
+        match self.max_num_orders {
+            Some(limit) if open_orders >= limit as usize => Err(Error::InvalidOrderError {
+                msg: format!(
+                    "MAX_NUM_ORDERS: {open_orders} orders are already open on {}",
+                    self.symbol
+                ),
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Cached rules of all symbols
+#[derive(Debug, Clone)]
+pub struct SymbolRegistry {
+    symbols: HashMap<String, SymbolRules>,
+    refresh_interval: Duration,
+    last_refresh: u64,
+}
+
+impl Default for SymbolRegistry {
+    fn default() -> Self {
+        Self {
+            symbols: HashMap::new(),
+            refresh_interval: Duration::from_secs(3600),
+            last_refresh: 0,
+        }
+    }
+}
+
+impl SymbolRegistry {
+    /// Creates a registry from the exchange information
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, config::*, futures::general::*, futures::symbol_rules::*};
+    /// let general: FuturesGeneral = Binance::new_with_env(&Config::default());
+    /// let registry = tokio_test::block_on(SymbolRegistry::from_general(&general));
+    /// assert!(registry.is_ok(), "{:?}", registry);
+    /// ```
+    pub async fn from_general(general: &FuturesGeneral) -> Result<Self> {
+        let mut registry = Self::default();
+        registry.refresh(general).await?;
+        Ok(registry)
+    }
+
+    /// Sets how often [`SymbolRegistry::refresh_if_due`] fetches the exchange information
+    pub fn set_refresh_interval(mut self, refresh_interval: Duration) -> Self {
+        self.refresh_interval = refresh_interval;
+        self
+    }
+
+    /// Replaces the rules of all symbols
+    pub fn apply_exchange_info(&mut self, info: &ExchangeInformation) {
+        self.symbols = info
+            .symbols
+            .iter()
+            .map(|symbol| (symbol.symbol.clone(), SymbolRules::from(symbol)))
+            .collect();
+    }
+
+    /// Fetches the exchange information
+    pub async fn refresh(&mut self, general: &FuturesGeneral) -> Result<()> {
+        let info = general.exchange_info().await?;
+        self.apply_exchange_info(&info);
+        self.last_refresh = get_timestamp()?;
+        Ok(())
+    }
+
+    /// Fetches the exchange information if the refresh interval elapsed, returns whether it did
+    pub async fn refresh_if_due(&mut self, general: &FuturesGeneral) -> Result<bool> {
+        if get_timestamp()?.saturating_sub(self.last_refresh) < self.refresh_interval.as_millis() as u64 {
+            return Ok(false);
+        }
+        self.refresh(general).await?;
+        Ok(true)
+    }
+
+    pub fn rules(&self, symbol: &str) -> Result<&SymbolRules> {
+        self.symbols
+            .get(symbol)
+            .ok_or_else(|| Error::UnknownSymbol(symbol.to_string()))
+    }
+
+    pub fn symbols(&self) -> &HashMap<String, SymbolRules> { &self.symbols }
+
+    /// Rounds the prices and quantity of an order to the rules of its symbol
+    pub fn normalize(&self, mut order: OrderRequest) -> Result<OrderRequest> {
+        self.rules(&order.symbol)?.normalize(&mut order);
+        Ok(order)
+    }
+
+    /// Checks an order against the rules of its symbol
+    pub fn validate(&self, order: &OrderRequest, mark_price: Option<Decimal>) -> Result<()> {
+        self.rules(&order.symbol)?.validate(order, mark_price)
+    }
+
+    /// Normalizes and validates an order, then places it if one more order can be opened on its
+    /// symbol, given the number of orders already open there
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, config::*, futures::account::*, futures::general::*, futures::symbol_rules::*};
+    /// use binance::rest_model::OrderSide;
+    /// use rust_decimal_macros::dec;
+    /// let general: FuturesGeneral = Binance::new_with_env(&Config::testnet());
+    /// let account: FuturesAccount = Binance::new_with_env(&Config::testnet());
+    /// let registry = tokio_test::block_on(SymbolRegistry::from_general(&general)).unwrap();
+    /// let order = OrderRequest::limit("BTCUSDT", OrderSide::Buy, dec!(0.0012345), dec!(20000.123));
+    /// let open_orders = tokio_test::block_on(account.get_open_orders("BTCUSDT")).unwrap();
+    /// let placed = tokio_test::block_on(registry.place_order(&account, order, None, open_orders.len()));
+    /// assert!(placed.is_ok(), "{:?}", placed);
+    /// ```
+    pub async fn place_order(
+        &self,
+        account: &FuturesAccount,
+        order: OrderRequest,
+        mark_price: Option<Decimal>,
+        open_orders: usize,
+    ) -> Result<Transaction> {
+        let order = self.normalize(order)?;
+        self.validate(&order, mark_price)?;
+        self.rules(&order.symbol)?.check_open_orders(open_orders)?;
+        account.place_order(order).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn rules() -> SymbolRules {
+        let symbol: Symbol = serde_json::from_str(
+            r#"{
+                "symbol": "BTCUSDT", "pair": "BTCUSDT", "contractType": "PERPETUAL", "deliveryDate": 4133404800000,
+                "onboardDate": 1569398400000, "status": "TRADING", "maintMarginPercent": "2.5000",
+                "requiredMarginPercent": "5.0000", "baseAsset": "BTC", "quoteAsset": "USDT", "pricePrecision": 2,
+                "quantityPrecision": 3, "baseAssetPrecision": 8, "quotePrecision": 8, "underlyingType": "COIN",
+                "underlyingSubType": ["PoW"], "settlePlan": 0, "triggerProtect": "0.0500",
+                "filters": [
+                    {"filterType": "PRICE_FILTER", "minPrice": "556.80", "maxPrice": "4529764", "tickSize": "0.10"},
+                    {"filterType": "LOT_SIZE", "minQty": "0.001", "maxQty": "1000", "stepSize": "0.001"},
+                    {"filterType": "MARKET_LOT_SIZE", "minQty": "0.001", "maxQty": "120", "stepSize": "0.001"},
+                    {"filterType": "MAX_NUM_ORDERS", "limit": 200},
+                    {"filterType": "MAX_NUM_ALGO_ORDERS", "limit": 10},
+                    {"filterType": "MIN_NOTIONAL", "notional": "100"},
+                    {"filterType": "PERCENT_PRICE", "multiplierUp": "1.0500", "multiplierDown": "0.9500",
+                     "multiplierDecimal": "4"}
+                ],
+                "orderTypes": ["LIMIT", "MARKET", "STOP", "STOP_MARKET", "TAKE_PROFIT", "TAKE_PROFIT_MARKET",
+                               "TRAILING_STOP_MARKET"],
+                "timeInForce": ["GTC", "IOC", "FOK", "GTX"]
+            }"#,
+        )
+        .unwrap();
+        SymbolRules::from(&symbol)
+    }
+
+    #[test]
+    fn normalize() {
+        let rules = rules();
+        let mut buy = OrderRequest::limit("BTCUSDT", OrderSide::Buy, dec!(0.0129), dec!(20000.17));
+        rules.normalize(&mut buy);
+        assert_eq!(buy.price, Some(dec!(20000.1)));
+        assert_eq!(buy.quantity, Some(dec!(0.012)));
+        let mut sell = OrderRequest::stop_market("BTCUSDT", OrderSide::Sell, dec!(0.0129), dec!(19000.01));
+        rules.normalize(&mut sell);
+        assert_eq!(sell.stop_price, Some(dec!(19000.1)));
+        assert!(rules.validate(&buy, None).is_ok());
+        assert!(rules.validate(&sell, None).is_ok());
+    }
+
+    #[test]
+    fn validate() {
+        let rules = rules();
+        let invalid = |order: OrderRequest, mark_price: Option<Decimal>| {
+            let result = rules.validate(&order, mark_price);
+            assert!(matches!(result, Err(Error::InvalidOrderError { .. })), "{order:?}");
+        };
+        invalid(
+            OrderRequest::limit("BTCUSDT", OrderSide::Buy, dec!(0.01), dec!(20000.05)),
+            None,
+        );
+        invalid(
+            OrderRequest::limit("BTCUSDT", OrderSide::Buy, dec!(0.0015), dec!(20000)),
+            None,
+        );
+        invalid(OrderRequest::market("BTCUSDT", OrderSide::Buy, dec!(150)), None);
+        // 0.004 * 20000 is below the minimum notional of 100
+        invalid(
+            OrderRequest::limit("BTCUSDT", OrderSide::Buy, dec!(0.004), dec!(20000)),
+            None,
+        );
+        invalid(
+            OrderRequest::market("BTCUSDT", OrderSide::Buy, dec!(0.004)),
+            Some(dec!(20000)),
+        );
+        invalid(
+            OrderRequest::limit("BTCUSDT", OrderSide::Buy, dec!(0.01), dec!(22000)),
+            Some(dec!(20000)),
+        );
+        invalid(
+            OrderRequest::limit("BTCUSDT", OrderSide::Sell, dec!(0.01), dec!(18000)),
+            Some(dec!(20000)),
+        );
+        invalid(
+            OrderRequest {
+                close_position: Some(true),
+                ..OrderRequest::stop_market("BTCUSDT", OrderSide::Sell, dec!(0.01), dec!(19000))
+            },
+            None,
+        );
+        invalid(
+            OrderRequest {
+                close_position: Some(true),
+                quantity: None,
+                ..OrderRequest::limit("BTCUSDT", OrderSide::Sell, dec!(0.01), dec!(21000))
+            },
+            None,
+        );
+
+        let reduce_only = OrderRequest {
+            reduce_only: Some(true),
+            ..OrderRequest::limit("BTCUSDT", OrderSide::Sell, dec!(0.001), dec!(21000))
+        };
+        assert!(rules.validate(&reduce_only, Some(dec!(20000))).is_ok());
+        let close_position = OrderRequest {
+            close_position: Some(true),
+            quantity: None,
+            ..OrderRequest::take_profit_market("BTCUSDT", OrderSide::Sell, dec!(0.01), dec!(21000))
+        };
+        assert!(rules.validate(&close_position, None).is_ok());
+        assert!(rules.check_open_orders(199).is_ok());
+        assert!(rules.check_open_orders(200).is_err());
+    }
+
+    #[test]
+    fn place_order_checks_the_open_orders() {
+        // The client has no credentials, so only a rejection by the rules can come back as an order error
+        let account: FuturesAccount = crate::api::Binance::new(None, None);
+        let registry = SymbolRegistry {
+            symbols: HashMap::from([("BTCUSDT".to_string(), rules())]),
+            ..SymbolRegistry::default()
+        };
+        let order = OrderRequest::limit("BTCUSDT", OrderSide::Buy, dec!(0.01), dec!(20000));
+        let result = tokio_test::block_on(registry.place_order(&account, order, None, 200));
+        assert!(
+            matches!(&result, Err(Error::InvalidOrderError { msg }) if msg.starts_with("MAX_NUM_ORDERS")),
+            "{result:?}"
+        );
+    }
+}
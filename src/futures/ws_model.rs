@@ -1,4 +1,5 @@
 use crate::futures::rest_model::{MarginType, OrderType, PositionSide, WorkingType};
+pub use crate::rest_model::SelfTradePreventionMode;
 use crate::rest_model::{string_or_float, string_or_float_opt, ExecutionType, OrderSide, OrderStatus, TimeInForce};
 use rust_decimal::Decimal;
 
@@ -167,7 +168,7 @@ pub struct Order {
     pub good_till_date: u64,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum PriceMatch {
     /// No price match
@@ -175,30 +176,23 @@ pub enum PriceMatch {
     /// Counterparty best price
     Opponent,
     /// The 5th best price from the counterparty
+    #[serde(rename = "OPPONENT_5")]
     Opponent5,
     /// The 10th best price from the counterparty
+    #[serde(rename = "OPPONENT_10")]
     Opponent10,
     /// The 20th best price from the counterparty
+    #[serde(rename = "OPPONENT_20")]
     Opponent20,
     /// The best price on the same side of the order book
     Queue,
     /// The 5th best price on the same side of the order book
+    #[serde(rename = "QUEUE_5")]
     Queue5,
     /// The 10th best price on the same side of the order book
+    #[serde(rename = "QUEUE_10")]
     Queue10,
     /// The 20th best price on the same side of the order book
+    #[serde(rename = "QUEUE_20")]
     Queue20,
 }
-
-#[derive(Clone, Debug, Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum SelfTradePreventionMode {
-    /// No Self-Trade Prevention
-    None,
-    /// Expire taker order when STP trigger
-    ExpireTaker,
-    /// Expire taker and maker order when STP trigger
-    ExpireBoth,
-    /// Expire maker order when STP trigger
-    ExpireMaker,
-}
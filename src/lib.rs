@@ -71,12 +71,18 @@ extern crate serde_qs as qs;
 pub use util::bool_to_string;
 pub use util::bool_to_string_some;
 
+#[cfg(any(feature = "futures_api", feature = "options_api"))]
+mod batch;
 mod client;
 pub mod errors;
 pub mod util;
 
 pub mod account;
 pub mod api;
+pub mod backtest;
+pub mod balance_book;
+#[cfg(feature = "coin_futures_api")]
+pub mod coin_futures;
 pub mod config;
 #[cfg(feature = "futures_api")]
 pub mod futures;
@@ -84,9 +90,18 @@ pub mod general;
 #[cfg(feature = "margin_api")]
 pub mod margin;
 pub mod market;
+#[cfg(feature = "options_api")]
+pub mod options;
+pub mod order_tracker;
+pub mod paper;
+#[cfg(feature = "portfolio_margin_api")]
+pub mod portfolio_margin;
+pub mod pnl;
 pub mod rest_model;
 #[cfg(feature = "savings_api")]
 pub mod savings;
+pub mod submission;
+pub mod trading;
 pub mod userstream;
 #[cfg(feature = "wallet_api")]
 pub mod wallet;
@@ -1,7 +1,9 @@
+use crate::account::{valid_order_params, valid_order_quantity};
 use crate::client::*;
 use crate::errors::*;
 use crate::rest_model::*;
 use crate::util::bool_to_string;
+use rust_decimal::Decimal;
 
 static SAPI_V1_MARGIN_TRANSFER: &str = "/sapi/v1/margin/transfer";
 static SAPI_V1_MARGIN_ISOLATED_TRANSFER: &str = "/sapi/v1/margin/isolated/transfer";
@@ -42,6 +44,93 @@ pub struct Margin {
     pub recv_window: u64,
 }
 
+impl MarginOrder {
+    /// An order of a kind, which carries the parameters its type requires
+    pub fn new(symbol: impl Into<String>, side: OrderSide, kind: OrderSpec) -> Self {
+        Self {
+            symbol: symbol.into(),
+            side,
+            order_type: kind.order_type(),
+            quantity: kind.quantity(),
+            quote_order_qty: kind.quote_order_qty(),
+            price: kind.price(),
+            stop_price: kind.stop_price(),
+            new_client_order_id: None,
+            iceberg_qty: None,
+            new_order_resp_type: OrderResponse::Ack,
+            time_in_force: kind.time_in_force(),
+            is_isolated: None,
+            side_effect_type: SideEffectType::NoSideEffect,
+        }
+    }
+
+    /// A LIMIT order, good till canceled
+    pub fn limit(symbol: impl Into<String>, side: OrderSide, qty: Decimal, price: Decimal) -> Self {
+        Self::new(symbol, side, OrderSpec::Limit { qty, price })
+    }
+
+    /// A LIMIT_MAKER order, rejected if it would immediately match as a taker
+    pub fn limit_maker(symbol: impl Into<String>, side: OrderSide, qty: Decimal, price: Decimal) -> Self {
+        Self::new(symbol, side, OrderSpec::LimitMaker { qty, price })
+    }
+
+    /// A MARKET order for a quantity of the base asset
+    pub fn market(symbol: impl Into<String>, side: OrderSide, qty: Decimal) -> Self {
+        Self::new(symbol, side, OrderSpec::Market { qty })
+    }
+
+    /// A MARKET order spending (when buying) or receiving (when selling) a quantity of the quote asset
+    pub fn market_quote(symbol: impl Into<String>, side: OrderSide, quote_qty: Decimal) -> Self {
+        Self::new(symbol, side, OrderSpec::MarketQuote { quote_qty })
+    }
+
+    /// A STOP_LOSS order, executing a MARKET order once the stop price is reached
+    pub fn stop_loss(symbol: impl Into<String>, side: OrderSide, qty: Decimal, stop_price: Decimal) -> Self {
+        Self::new(symbol, side, OrderSpec::StopLoss { qty, stop_price })
+    }
+
+    /// A STOP_LOSS_LIMIT order, placing a good till canceled LIMIT order once the stop price is reached
+    pub fn stop_loss_limit(
+        symbol: impl Into<String>,
+        side: OrderSide,
+        qty: Decimal,
+        price: Decimal,
+        stop_price: Decimal,
+    ) -> Self {
+        Self::new(symbol, side, OrderSpec::StopLossLimit { qty, price, stop_price })
+    }
+
+    /// A TAKE_PROFIT order, executing a MARKET order once the stop price is reached
+    pub fn take_profit(symbol: impl Into<String>, side: OrderSide, qty: Decimal, stop_price: Decimal) -> Self {
+        Self::new(symbol, side, OrderSpec::TakeProfit { qty, stop_price })
+    }
+
+    /// A TAKE_PROFIT_LIMIT order, placing a good till canceled LIMIT order once the stop price is reached
+    pub fn take_profit_limit(
+        symbol: impl Into<String>,
+        side: OrderSide,
+        qty: Decimal,
+        price: Decimal,
+        stop_price: Decimal,
+    ) -> Self {
+        Self::new(symbol, side, OrderSpec::TakeProfitLimit { qty, price, stop_price })
+    }
+
+    /// Checks that the order carries the parameters its order type requires
+    pub fn valid(&self) -> Result<()> {
+        valid_order_quantity(&self.order_type, self.quantity, self.quote_order_qty)?;
+        valid_order_params(
+            "order",
+            &self.order_type,
+            self.price,
+            self.stop_price,
+            None,
+            self.time_in_force.as_ref(),
+            self.iceberg_qty,
+        )
+    }
+}
+
 impl Margin {
     /// Execute transfer between spot account and margin account.
     /// # Examples
@@ -193,26 +282,14 @@ impl Margin {
     /// # Examples
     /// ```rust,no_run
     /// use binance::{api::*, margin::*, config::*, rest_model::*};
+    /// use rust_decimal_macros::dec;
     /// let margin: Margin = Binance::new_with_env(&Config::testnet());
-    /// let margin_order = MarginOrder {
-    ///     symbol: "BTCUSDT".to_string(),
-    ///     side: OrderSide::Sell,
-    ///     order_type: OrderType::Limit,
-    ///     quantity: Some(0.001),
-    ///     quote_order_qty: None,
-    ///     price: Some(10.0),
-    ///     stop_price: Some(10.0),
-    ///     new_client_order_id: Some("my_id".to_string()),
-    ///     iceberg_qty: Some(10.0),
-    ///     new_order_resp_type: OrderResponse::Ack,
-    ///     time_in_force: Some(TimeInForce::FOK),
-    ///     side_effect_type: SideEffectType::NoSideEffect,
-    ///     is_isolated: None,
-    /// };
+    /// let margin_order = MarginOrder::limit("BTCUSDT", OrderSide::Sell, dec!(0.001), dec!(10));
     /// let transaction_id = tokio_test::block_on(margin.trade(margin_order));
     /// assert!(transaction_id.is_ok(), "{:?}", transaction_id);
     /// ```
     pub async fn trade(&self, margin_order: MarginOrder) -> Result<MarginOrderResult> {
+        margin_order.valid()?;
         self.client
             .post_signed_p(SAPI_V1_MARGIN_ORDER, margin_order, self.recv_window)
             .await
@@ -289,13 +366,30 @@ impl Margin {
     {
         let margin_order_cancellation: MarginOrderCancellation = MarginOrderCancellation {
             symbol: symbol.into(),
-            order_id: order_id.into(),
-            orig_client_order_id,
-            new_client_order_id,
+            order_id: Some(order_id.into()),
+            orig_client_order_id: Some(orig_client_order_id).filter(|id| !id.is_empty()),
+            new_client_order_id: Some(new_client_order_id).filter(|id| !id.is_empty()),
             is_isolated: is_isolated.map(bool_to_string),
         };
+        self.cancel_order(margin_order_cancellation).await
+    }
+
+    /// Cancel an existing order by order id or by client order id
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, margin::*, config::*, rest_model::*};
+    /// let margin: Margin = Binance::new_with_env(&Config::testnet());
+    /// let cancellation = MarginOrderCancellation {
+    ///     symbol: "BTCUSDT".to_string(),
+    ///     orig_client_order_id: Some("my_id".to_string()),
+    ///     ..MarginOrderCancellation::default()
+    /// };
+    /// let result = tokio_test::block_on(margin.cancel_order(cancellation));
+    /// assert!(result.is_ok(), "{:?}", result);
+    /// ```
+    pub async fn cancel_order(&self, cancellation: MarginOrderCancellation) -> Result<MarginOrderCancellationResult> {
         self.client
-            .delete_signed_p(SAPI_V1_MARGIN_ORDER, margin_order_cancellation, self.recv_window)
+            .delete_signed_p(SAPI_V1_MARGIN_ORDER, cancellation, self.recv_window)
             .await
     }
 
@@ -989,3 +1083,57 @@ impl Margin {
             .await
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::api::Binance;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn margin_order_constructors_are_valid() {
+        let orders = [
+            MarginOrder::limit("BTCUSDT", OrderSide::Buy, dec!(1), dec!(20000)),
+            MarginOrder::limit_maker("BTCUSDT", OrderSide::Buy, dec!(1), dec!(20000)),
+            MarginOrder::market("BTCUSDT", OrderSide::Sell, dec!(1)),
+            MarginOrder::market_quote("BTCUSDT", OrderSide::Buy, dec!(100)),
+            MarginOrder::stop_loss("BTCUSDT", OrderSide::Sell, dec!(1), dec!(19000)),
+            MarginOrder::stop_loss_limit("BTCUSDT", OrderSide::Sell, dec!(1), dec!(18900), dec!(19000)),
+            MarginOrder::take_profit("BTCUSDT", OrderSide::Sell, dec!(1), dec!(21000)),
+            MarginOrder::take_profit_limit("BTCUSDT", OrderSide::Sell, dec!(1), dec!(21100), dec!(21000)),
+        ];
+        for order in orders {
+            assert!(order.valid().is_ok(), "{order:?}");
+        }
+        let order = MarginOrder::new(
+            "BTCUSDT",
+            OrderSide::Sell,
+            OrderSpec::StopLossLimit {
+                qty: dec!(1),
+                price: dec!(18900),
+                stop_price: dec!(19000),
+            },
+        );
+        assert_eq!(order.order_type, OrderType::StopLossLimit);
+        assert_eq!(order.time_in_force, Some(TimeInForce::GTC));
+        assert_eq!(order.stop_price, Some(dec!(19000)));
+    }
+
+    #[test]
+    fn trade_rejects_invalid_orders_before_sending() {
+        // The client has no credentials, so only a rejection by `valid` can come back as an order error
+        let margin: Margin = Binance::new(None, None);
+        let missing_price = MarginOrder {
+            price: None,
+            ..MarginOrder::limit("BTCUSDT", OrderSide::Buy, dec!(1), dec!(20000))
+        };
+        let result = tokio_test::block_on(margin.trade(missing_price));
+        assert!(matches!(result, Err(Error::InvalidOrderError { .. })), "{result:?}");
+        let both_quantities = MarginOrder {
+            quote_order_qty: Some(dec!(100)),
+            ..MarginOrder::market("BTCUSDT", OrderSide::Buy, dec!(1))
+        };
+        let result = tokio_test::block_on(margin.trade(both_quantities));
+        assert!(matches!(result, Err(Error::InvalidOrderError { .. })), "{result:?}");
+    }
+}
@@ -0,0 +1,208 @@
+use super::rest_model::{
+    AccountInformation, Order, OrderResponseType, OrderSide, OrderType, Position, StatusResponse, TimeInForce,
+};
+use crate::batch::{batch_results, encode_orders};
+use crate::client::Client;
+use crate::errors::*;
+use crate::rest_model::PairQuery;
+use rust_decimal::Decimal;
+use serde_json::Value;
+
+#[derive(Clone)]
+pub struct OptionsAccount {
+    pub client: Client,
+    pub recv_window: u64,
+}
+
+#[derive(Serialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderRequest {
+    pub symbol: String,
+    pub side: OrderSide,
+    #[serde(rename = "type")]
+    pub order_type: OrderType,
+    /// Quantity in contracts
+    pub quantity: Decimal,
+    pub price: Option<Decimal>,
+    pub time_in_force: Option<TimeInForce>,
+    pub reduce_only: Option<bool>,
+    pub post_only: Option<bool>,
+    pub new_order_resp_type: Option<OrderResponseType>,
+    pub client_order_id: Option<String>,
+    /// Whether the order is subject to market maker protection
+    pub is_mmp: Option<bool>,
+}
+
+impl OrderRequest {
+    /// A LIMIT order, good till canceled, answered with the full order
+    pub fn limit(symbol: impl Into<String>, side: OrderSide, qty: Decimal, price: Decimal) -> Self {
+        Self {
+            symbol: symbol.into(),
+            side,
+            order_type: OrderType::Limit,
+            quantity: qty,
+            price: Some(price),
+            time_in_force: Some(TimeInForce::GTC),
+            new_order_resp_type: Some(OrderResponseType::Result),
+            ..Self::default()
+        }
+    }
+}
+
+/// Identifies an order by its id or by its client order id
+#[derive(Serialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderQuery {
+    pub symbol: String,
+    pub order_id: Option<u64>,
+    pub client_order_id: Option<String>,
+}
+
+impl OrderQuery {
+    pub fn by_id(symbol: impl Into<String>, order_id: u64) -> Self {
+        Self {
+            symbol: symbol.into(),
+            order_id: Some(order_id),
+            client_order_id: None,
+        }
+    }
+
+    pub fn by_client_id(symbol: impl Into<String>, client_order_id: impl Into<String>) -> Self {
+        Self {
+            symbol: symbol.into(),
+            order_id: None,
+            client_order_id: Some(client_order_id.into()),
+        }
+    }
+}
+
+/// Result of one order of a batch, which fails or succeeds independently of the others
+pub type BatchOrderResult = std::result::Result<Order, BinanceContentError>;
+
+/// Maximum number of orders placed or canceled in one batch
+pub const MAX_BATCH_ORDERS: usize = 10;
+
+#[derive(Serialize)]
+struct BatchOrdersRequest {
+    orders: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BatchCancellationRequest {
+    symbol: String,
+    order_ids: String,
+}
+
+#[derive(Serialize)]
+struct OptionalSymbolQuery {
+    pub symbol: Option<String>,
+}
+
+impl OptionsAccount {
+    /// Place an order
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, config::*, options::account::*, rest_model::OrderSide};
+    /// use rust_decimal_macros::dec;
+    /// let account: OptionsAccount = Binance::new_with_env(&Config::default());
+    /// let order = OrderRequest::limit("BTC-240628-60000-C", OrderSide::Buy, dec!(0.01), dec!(500));
+    /// let placed = tokio_test::block_on(account.place_order(order));
+    /// assert!(placed.is_ok(), "{:?}", placed);
+    /// ```
+    pub async fn place_order(&self, order: OrderRequest) -> Result<Order> {
+        self.client
+            .post_signed_p("/eapi/v1/order", order, self.recv_window)
+            .await
+    }
+
+    /// Place up to [`MAX_BATCH_ORDERS`] orders in one request
+    pub async fn place_batch_orders(&self, orders: Vec<OrderRequest>) -> Result<Vec<BatchOrderResult>> {
+        let request = BatchOrdersRequest {
+            orders: encode_orders(&orders, MAX_BATCH_ORDERS)?,
+        };
+        let entries: Vec<Value> = self
+            .client
+            .post_signed_p("/eapi/v1/batchOrders", request, self.recv_window)
+            .await?;
+        batch_results(entries)
+    }
+
+    /// Get an order
+    pub async fn get_order(&self, order: OrderQuery) -> Result<Order> {
+        self.client
+            .get_signed_p("/eapi/v1/order", Some(order), self.recv_window)
+            .await
+    }
+
+    /// Cancel an order
+    pub async fn cancel_order(&self, order: OrderQuery) -> Result<Order> {
+        self.client
+            .delete_signed_p("/eapi/v1/order", order, self.recv_window)
+            .await
+    }
+
+    /// Cancel up to [`MAX_BATCH_ORDERS`] orders of a symbol in one request
+    pub async fn cancel_batch_orders<S>(&self, symbol: S, order_ids: Vec<u64>) -> Result<Vec<BatchOrderResult>>
+    where
+        S: Into<String>,
+    {
+        if order_ids.is_empty() || order_ids.len() > MAX_BATCH_ORDERS {
+            return Err(Error::InvalidOrderError {
+                msg: format!("a batch must have between 1 and {MAX_BATCH_ORDERS} orders"),
+            });
+        }
+        let request = BatchCancellationRequest {
+            symbol: symbol.into(),
+            order_ids: serde_json::to_string(&order_ids)?,
+        };
+        let entries: Vec<Value> = self
+            .client
+            .delete_signed_p("/eapi/v1/batchOrders", request, self.recv_window)
+            .await?;
+        batch_results(entries)
+    }
+
+    /// Cancel all open orders on this symbol
+    pub async fn cancel_all_open_orders<S>(&self, symbol: S) -> Result<StatusResponse>
+    where
+        S: Into<String>,
+    {
+        self.client
+            .delete_signed_p(
+                "/eapi/v1/allOpenOrders",
+                PairQuery { symbol: symbol.into() },
+                self.recv_window,
+            )
+            .await
+    }
+
+    /// Get currently open orders of a symbol, or of all symbols
+    pub async fn get_open_orders(&self, symbol: Option<String>) -> Result<Vec<Order>> {
+        self.client
+            .get_signed_p(
+                "/eapi/v1/openOrders",
+                Some(OptionalSymbolQuery { symbol }),
+                self.recv_window,
+            )
+            .await
+    }
+
+    /// Get the positions of a symbol, or of all symbols
+    pub async fn position_information(&self, symbol: Option<String>) -> Result<Vec<Position>> {
+        self.client
+            .get_signed_p(
+                "/eapi/v1/position",
+                Some(OptionalSymbolQuery { symbol }),
+                self.recv_window,
+            )
+            .await
+    }
+
+    /// Get the balances of the margin assets and the greeks of the positions per underlying
+    pub async fn account_information(&self) -> Result<AccountInformation> {
+        self.client
+            .get_signed_p("/eapi/v1/account", Option::<String>::None, self.recv_window)
+            .await
+    }
+}
@@ -0,0 +1,97 @@
+use crate::client::*;
+use crate::errors::*;
+use crate::options::rest_model::*;
+use crate::rest_model::PairQuery;
+
+#[derive(Clone)]
+pub struct OptionsMarket {
+    pub client: Client,
+    pub recv_window: u64,
+}
+
+#[derive(Serialize)]
+struct OptionalSymbolQuery {
+    pub symbol: Option<String>,
+}
+
+#[derive(Serialize)]
+struct UnderlyingQuery {
+    pub underlying: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct KlinesQuery {
+    pub symbol: String,
+    pub interval: String,
+    pub limit: u16,
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+}
+
+impl OptionsMarket {
+    /// Order book (Default 100; max 1000)
+    pub async fn get_depth<S>(&self, symbol: S) -> Result<OrderBook>
+    where
+        S: Into<String>,
+    {
+        self.client
+            .get_d("/eapi/v1/depth", Some(PairQuery { symbol: symbol.into() }))
+            .await
+    }
+
+    /// Returns up to 'limit' klines for given symbol and interval ("1m", "5m", ...)
+    pub async fn get_klines<S1, S2, S3, S4, S5>(
+        &self,
+        symbol: S1,
+        interval: S2,
+        limit: S3,
+        start_time: S4,
+        end_time: S5,
+    ) -> Result<Vec<Kline>>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<u16>,
+        S4: Into<Option<u64>>,
+        S5: Into<Option<u64>>,
+    {
+        let query = KlinesQuery {
+            symbol: symbol.into(),
+            interval: interval.into(),
+            limit: limit.into(),
+            start_time: start_time.into(),
+            end_time: end_time.into(),
+        };
+        self.client.get_d("/eapi/v1/klines", Some(query)).await
+    }
+
+    /// Mark price, implied volatilities and greeks of an option, or of all options
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, config::*, options::market::*};
+    /// let market: OptionsMarket = Binance::new(None, None);
+    /// let marks = tokio_test::block_on(market.get_mark(Some("BTC-240628-60000-C".to_string())));
+    /// assert!(marks.is_ok(), "{:?}", marks);
+    /// ```
+    pub async fn get_mark(&self, symbol: Option<String>) -> Result<Vec<OptionMark>> {
+        self.client
+            .get_d("/eapi/v1/mark", Some(OptionalSymbolQuery { symbol }))
+            .await
+    }
+
+    /// Spot index price of an underlying, such as "BTCUSDT"
+    pub async fn get_index_price<S>(&self, underlying: S) -> Result<IndexPrice>
+    where
+        S: Into<String>,
+    {
+        self.client
+            .get_d(
+                "/eapi/v1/index",
+                Some(UnderlyingQuery {
+                    underlying: underlying.into(),
+                }),
+            )
+            .await
+    }
+}
@@ -0,0 +1,12 @@
+//! European options, settled in USDT, on `/eapi`.
+//!
+//! Options are named after their underlying asset, expiry date, strike price and side, such as
+//! "BTC-240628-60000-C", see [`rest_model::Symbol`].
+
+pub mod account;
+pub mod general;
+pub mod market;
+pub mod rest_model;
+pub mod userstream;
+pub mod websockets;
+pub mod ws_model;
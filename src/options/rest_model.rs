@@ -0,0 +1,384 @@
+//! Models of the European options API.
+
+use crate::rest_model::string_or_float_opt;
+pub use crate::rest_model::{string_or_float, Asks, Bids, Filters, OrderSide, RateLimit, ServerTime, TimeInForce};
+use rust_decimal::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExchangeInformation {
+    pub timezone: String,
+    pub server_time: u64,
+    pub option_contracts: Vec<OptionContract>,
+    pub option_assets: Vec<OptionAsset>,
+    pub option_symbols: Vec<Symbol>,
+    pub rate_limits: Vec<RateLimit>,
+}
+
+/// An underlying on which options are listed
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OptionContract {
+    pub base_asset: String,
+    pub quote_asset: String,
+    pub underlying: String,
+    pub settle_asset: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OptionAsset {
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OptionSide {
+    Call,
+    Put,
+}
+
+/// An option, such as "BTC-240628-60000-C"
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Symbol {
+    pub symbol: String,
+    pub side: OptionSide,
+    #[serde(with = "string_or_float")]
+    pub strike_price: Decimal,
+    pub underlying: String,
+    /// Expiry time in ms
+    pub expiry_date: u64,
+    /// Quantity of underlying per contract
+    pub unit: u64,
+    #[serde(with = "string_or_float")]
+    pub maker_fee_rate: Decimal,
+    #[serde(with = "string_or_float")]
+    pub taker_fee_rate: Decimal,
+    #[serde(with = "string_or_float")]
+    pub min_qty: Decimal,
+    #[serde(with = "string_or_float")]
+    pub max_qty: Decimal,
+    #[serde(with = "string_or_float")]
+    pub initial_margin: Decimal,
+    #[serde(with = "string_or_float")]
+    pub maintenance_margin: Decimal,
+    #[serde(with = "string_or_float")]
+    pub min_initial_margin: Decimal,
+    #[serde(with = "string_or_float")]
+    pub min_maintenance_margin: Decimal,
+    pub price_scale: u32,
+    pub quantity_scale: u32,
+    pub quote_asset: String,
+    pub filters: Vec<Filters>,
+}
+
+impl Symbol {
+    /// Whether the option has expired at a time in ms
+    pub fn is_expired(&self, now: u64) -> bool { now >= self.expiry_date }
+
+    /// Value of exercising one unit of the option at a price of the underlying
+    pub fn intrinsic_value(&self, underlying_price: Decimal) -> Decimal {
+        let value = match self.side {
+            OptionSide::Call => underlying_price - self.strike_price,
+            OptionSide::Put => self.strike_price - underlying_price,
+        };
+        value.max(Decimal::ZERO)
+    }
+}
+
+/// Mark price, implied volatilities and greeks of an option
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OptionMark {
+    pub symbol: String,
+    #[serde(with = "string_or_float")]
+    pub mark_price: Decimal,
+    #[serde(rename = "bidIV", with = "string_or_float")]
+    pub bid_iv: Decimal,
+    #[serde(rename = "askIV", with = "string_or_float")]
+    pub ask_iv: Decimal,
+    #[serde(rename = "markIV", with = "string_or_float")]
+    pub mark_iv: Decimal,
+    #[serde(with = "string_or_float")]
+    pub delta: Decimal,
+    #[serde(with = "string_or_float")]
+    pub theta: Decimal,
+    #[serde(with = "string_or_float")]
+    pub gamma: Decimal,
+    #[serde(with = "string_or_float")]
+    pub vega: Decimal,
+    #[serde(with = "string_or_float")]
+    pub high_price_limit: Decimal,
+    #[serde(with = "string_or_float")]
+    pub low_price_limit: Decimal,
+    #[serde(default, with = "string_or_float_opt")]
+    pub risk_free_interest: Option<Decimal>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OrderBook {
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+    #[serde(rename = "u")]
+    pub update_id: u64,
+    pub bids: Vec<Bids>,
+    pub asks: Vec<Asks>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Kline {
+    pub interval: String,
+    pub open_time: u64,
+    pub close_time: u64,
+    #[serde(with = "string_or_float")]
+    pub open: Decimal,
+    #[serde(with = "string_or_float")]
+    pub high: Decimal,
+    #[serde(with = "string_or_float")]
+    pub low: Decimal,
+    #[serde(with = "string_or_float")]
+    pub close: Decimal,
+    /// Volume in contracts
+    #[serde(with = "string_or_float")]
+    pub volume: Decimal,
+    /// Volume in quote asset
+    #[serde(with = "string_or_float")]
+    pub amount: Decimal,
+    pub trade_count: u64,
+    #[serde(with = "string_or_float")]
+    pub taker_volume: Decimal,
+    #[serde(with = "string_or_float")]
+    pub taker_amount: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OrderType {
+    #[default]
+    Limit,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OrderStatus {
+    Accepted,
+    Rejected,
+    PartiallyFilled,
+    Filled,
+    Cancelled,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OrderResponseType {
+    Ack,
+    Result,
+}
+
+/// An order, as returned by queries, placements and cancellations
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Order {
+    pub order_id: u64,
+    pub symbol: String,
+    #[serde(with = "string_or_float")]
+    pub price: Decimal,
+    #[serde(with = "string_or_float")]
+    pub quantity: Decimal,
+    #[serde(with = "string_or_float")]
+    pub executed_qty: Decimal,
+    #[serde(with = "string_or_float")]
+    pub fee: Decimal,
+    pub side: OrderSide,
+    #[serde(rename = "type")]
+    pub order_type: OrderType,
+    pub time_in_force: TimeInForce,
+    pub reduce_only: bool,
+    pub post_only: bool,
+    pub create_time: u64,
+    pub update_time: u64,
+    pub status: OrderStatus,
+    #[serde(with = "string_or_float")]
+    pub avg_price: Decimal,
+    #[serde(default)]
+    pub client_order_id: Option<String>,
+    pub price_scale: u32,
+    pub quantity_scale: u32,
+    pub option_side: OptionSide,
+    pub quote_asset: String,
+    /// Whether the order is subject to market maker protection
+    #[serde(default)]
+    pub mmp: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PositionSide {
+    Long,
+    Short,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Position {
+    pub symbol: String,
+    pub side: PositionSide,
+    pub option_side: OptionSide,
+    #[serde(with = "string_or_float")]
+    pub strike_price: Decimal,
+    pub expiry_date: u64,
+    /// Quantity in contracts, negative when short
+    #[serde(with = "string_or_float")]
+    pub quantity: Decimal,
+    #[serde(with = "string_or_float")]
+    pub reducible_qty: Decimal,
+    #[serde(with = "string_or_float")]
+    pub entry_price: Decimal,
+    #[serde(with = "string_or_float")]
+    pub mark_price: Decimal,
+    #[serde(with = "string_or_float")]
+    pub mark_value: Decimal,
+    #[serde(with = "string_or_float")]
+    pub position_cost: Decimal,
+    /// Return on the position cost
+    #[serde(with = "string_or_float")]
+    pub ror: Decimal,
+    #[serde(rename = "unrealizedPNL", with = "string_or_float")]
+    pub unrealized_pnl: Decimal,
+    pub price_scale: u32,
+    pub quantity_scale: u32,
+    pub quote_asset: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountInformation {
+    #[serde(rename = "asset")]
+    pub assets: Vec<AccountAsset>,
+    #[serde(rename = "greek")]
+    pub greeks: Vec<Greeks>,
+    pub time: u64,
+    #[serde(default)]
+    pub risk_level: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountAsset {
+    pub asset: String,
+    #[serde(with = "string_or_float")]
+    pub margin_balance: Decimal,
+    #[serde(with = "string_or_float")]
+    pub equity: Decimal,
+    #[serde(with = "string_or_float")]
+    pub available: Decimal,
+    #[serde(with = "string_or_float")]
+    pub locked: Decimal,
+    #[serde(rename = "unrealizedPNL", with = "string_or_float")]
+    pub unrealized_pnl: Decimal,
+}
+
+/// Greeks of the positions on an underlying
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Greeks {
+    pub underlying: String,
+    #[serde(with = "string_or_float")]
+    pub delta: Decimal,
+    #[serde(with = "string_or_float")]
+    pub gamma: Decimal,
+    #[serde(with = "string_or_float")]
+    pub theta: Decimal,
+    #[serde(with = "string_or_float")]
+    pub vega: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StatusResponse {
+    pub code: i64,
+    pub msg: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexPrice {
+    pub time: u64,
+    #[serde(with = "string_or_float")]
+    pub index_price: Decimal,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn symbol(name: &str, side: &str) -> Symbol {
+        serde_json::from_str(&format!(
+            r#"{{
+                "expiryDate": 1719561600000, "filters": [
+                    {{"filterType": "PRICE_FILTER", "minPrice": "5", "maxPrice": "20000", "tickSize": "5"}},
+                    {{"filterType": "LOT_SIZE", "minQty": "0.01", "maxQty": "100", "stepSize": "0.01"}}
+                ],
+                "symbol": "{name}", "side": "{side}", "strikePrice": "60000.00000000", "underlying": "BTCUSDT",
+                "unit": 1, "makerFeeRate": "0.00020000", "takerFeeRate": "0.00020000", "minQty": "0.01",
+                "maxQty": "100", "initialMargin": "0.15000000", "maintenanceMargin": "0.07500000",
+                "minInitialMargin": "0.10000000", "minMaintenanceMargin": "0.05000000", "priceScale": 0,
+                "quantityScale": 2, "quoteAsset": "USDT"
+            }}"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn intrinsic_value() {
+        let call = symbol("BTC-240628-60000-C", "CALL");
+        assert_eq!(call.side, OptionSide::Call);
+        assert_eq!(call.intrinsic_value(dec!(62500)), dec!(2500));
+        assert_eq!(call.intrinsic_value(dec!(58000)), dec!(0));
+        let put = symbol("BTC-240628-60000-P", "PUT");
+        assert_eq!(put.intrinsic_value(dec!(58000)), dec!(2000));
+        assert_eq!(put.intrinsic_value(dec!(62500)), dec!(0));
+        assert!(!put.is_expired(1719561599999));
+        assert!(put.is_expired(1719561600000));
+    }
+
+    #[test]
+    fn option_mark_serde() {
+        let marks: Vec<OptionMark> = serde_json::from_str(
+            r#"[{"symbol": "BTC-240628-60000-C", "markPrice": "1875.5", "bidIV": "0.5012", "askIV": "0.5210",
+                 "markIV": "0.51", "delta": "0.41823", "theta": "-34.12", "gamma": "0.00004", "vega": "61.2",
+                 "highPriceLimit": "2480", "lowPriceLimit": "1270", "riskFreeInterest": "0.1"}]"#,
+        )
+        .unwrap();
+        assert_eq!(marks[0].bid_iv, dec!(0.5012));
+        assert_eq!(marks[0].ask_iv, dec!(0.5210));
+        assert_eq!(marks[0].mark_iv, dec!(0.51));
+        assert_eq!(marks[0].risk_free_interest, Some(dec!(0.1)));
+        let serialized = serde_json::to_value(&marks[0]).unwrap();
+        assert_eq!(serialized["markIV"], "0.51");
+        assert!(serialized.get("markIv").is_none());
+    }
+
+    #[test]
+    fn order_serde() {
+        let order: Order = serde_json::from_str(
+            r#"{"orderId": 4729002469353472, "symbol": "BTC-240628-60000-C", "price": "100", "quantity": "1",
+                "executedQty": "0", "fee": "0", "side": "BUY", "type": "LIMIT", "timeInForce": "GTC",
+                "reduceOnly": false, "postOnly": false, "createTime": 1592465880683, "updateTime": 1566818724722,
+                "status": "ACCEPTED", "avgPrice": "0", "source": "API", "clientOrderId": "", "priceScale": 3,
+                "quantityScale": 4, "optionSide": "CALL", "quoteAsset": "USDT", "mmp": false}"#,
+        )
+        .unwrap();
+        assert_eq!(order.status, OrderStatus::Accepted);
+        assert_eq!(order.order_type, OrderType::Limit);
+        assert_eq!(order.option_side, OptionSide::Call);
+        assert_eq!(order.client_order_id.as_deref(), Some(""));
+        let status: OrderStatus = serde_json::from_str("\"EXPIRED\"").unwrap();
+        assert_eq!(status, OrderStatus::Other);
+    }
+}
@@ -0,0 +1,34 @@
+use crate::client::*;
+use crate::errors::*;
+use crate::rest_model::*;
+
+static USER_DATA_STREAM: &str = "/eapi/v1/listenKey";
+
+#[derive(Clone)]
+pub struct OptionsUserStream {
+    pub client: Client,
+    pub recv_window: u64,
+}
+
+impl OptionsUserStream {
+    /// Get a listen key for the stream
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, options::userstream::*, config::*};
+    /// let userstream: OptionsUserStream = Binance::new_with_env(&Config::default());
+    /// let start = tokio_test::block_on(userstream.start());
+    /// assert!(start.is_ok(), "{:?}", start);
+    /// assert!(start.unwrap().listen_key.len() > 0)
+    /// ```
+    pub async fn start(&self) -> Result<UserDataStream> { self.client.post(USER_DATA_STREAM, None).await }
+
+    /// Keep the connection alive, as the listen key becomes invalid after 60mn
+    pub async fn keep_alive(&self, listen_key: &str) -> Result<Success> {
+        self.client.put(USER_DATA_STREAM, listen_key, None).await
+    }
+
+    /// Invalidate the listen key
+    pub async fn close(&self, listen_key: &str) -> Result<Success> {
+        self.client.delete(USER_DATA_STREAM, listen_key, None).await
+    }
+}
@@ -0,0 +1,48 @@
+//! Market streams of the options websocket endpoint, on which symbols are upper case.
+
+pub use crate::websockets::{kline_stream, trade_stream, WebSockets};
+
+use crate::config::Config;
+use crate::errors::*;
+
+/// 24hr ticker, with greeks and implied volatilities, of an option such as "BTC-240628-60000-C"
+pub fn ticker_stream(symbol: &str) -> String { format!("{symbol}@ticker") }
+
+/// 24hr tickers of all the options of an underlying asset, such as "BTC", expiring on a date
+/// formatted as "YYMMDD"
+pub fn expiry_ticker_stream(underlying_asset: &str, expiry_date: &str) -> String {
+    format!("{underlying_asset}@ticker@{expiry_date}")
+}
+
+/// Mark prices of all the options of an underlying asset, such as "BTC"
+pub fn mark_price_stream(underlying_asset: &str) -> String { format!("{underlying_asset}@markPrice") }
+
+/// Index price of an underlying, such as "BTCUSDT"
+pub fn index_stream(underlying: &str) -> String { format!("{underlying}@index") }
+
+/// # Arguments
+///
+/// * `symbol`: the option symbol
+/// * `levels`: 10, 20, 50 or 100
+pub fn partial_book_depth_stream(symbol: &str, levels: u16) -> String { format!("{symbol}@depth{levels}") }
+
+/// New websocket holder connecting to the options endpoint of the configuration
+/// # Examples
+/// ```rust,no_run
+/// use binance::{options::{websockets::*, ws_model::WebsocketEvent}, config::*};
+/// use std::sync::atomic::AtomicBool;
+/// let mut ws = new_with_options(|event: WebsocketEvent| { println!("{event:?}"); Ok(()) }, Config::default());
+/// tokio_test::block_on(ws.connect(&mark_price_stream("BTC"))).unwrap();
+/// tokio_test::block_on(ws.event_loop(&AtomicBool::new(true))).unwrap();
+/// ```
+pub fn new_with_options<'a, WE, Callback>(handler: Callback, conf: Config) -> WebSockets<'a, WE>
+where
+    WE: serde::de::DeserializeOwned,
+    Callback: FnMut(WE) -> Result<()> + 'a + Send,
+{
+    let conf = Config {
+        ws_endpoint: conf.options_ws_endpoint.clone(),
+        ..conf
+    };
+    WebSockets::new_with_options(handler, conf)
+}
@@ -0,0 +1,108 @@
+use crate::rest_model::string_or_float;
+use rust_decimal::Decimal;
+
+/// An event of the option market streams, the mark price stream sends the mark prices of all the
+/// options of an underlying at once
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum WebsocketEvent {
+    MarkPrices(Vec<MarkPriceEvent>),
+    Event(Event),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "e")]
+pub enum Event {
+    #[serde(rename = "24hrTicker")]
+    Ticker(Box<TickerEvent>),
+    #[serde(rename = "index")]
+    Index(IndexEvent),
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct TickerEvent {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "o", with = "string_or_float")]
+    pub open: Decimal,
+    #[serde(rename = "h", with = "string_or_float")]
+    pub high: Decimal,
+    #[serde(rename = "l", with = "string_or_float")]
+    pub low: Decimal,
+    #[serde(rename = "c", with = "string_or_float")]
+    pub close: Decimal,
+    /// Volume in contracts
+    #[serde(rename = "V", with = "string_or_float")]
+    pub volume: Decimal,
+    /// Volume in quote asset
+    #[serde(rename = "A", with = "string_or_float")]
+    pub amount: Decimal,
+    #[serde(rename = "P", with = "string_or_float")]
+    pub price_change_percent: Decimal,
+    #[serde(rename = "p", with = "string_or_float")]
+    pub price_change: Decimal,
+    #[serde(rename = "Q", with = "string_or_float")]
+    pub last_qty: Decimal,
+    #[serde(rename = "F")]
+    pub first_trade_id: String,
+    #[serde(rename = "L")]
+    pub last_trade_id: String,
+    #[serde(rename = "n")]
+    pub trade_count: u64,
+    #[serde(rename = "bo", with = "string_or_float")]
+    pub best_bid_price: Decimal,
+    #[serde(rename = "ao", with = "string_or_float")]
+    pub best_ask_price: Decimal,
+    #[serde(rename = "bq", with = "string_or_float")]
+    pub best_bid_qty: Decimal,
+    #[serde(rename = "aq", with = "string_or_float")]
+    pub best_ask_qty: Decimal,
+    #[serde(rename = "b", with = "string_or_float")]
+    pub bid_iv: Decimal,
+    #[serde(rename = "a", with = "string_or_float")]
+    pub ask_iv: Decimal,
+    #[serde(rename = "d", with = "string_or_float")]
+    pub delta: Decimal,
+    #[serde(rename = "t", with = "string_or_float")]
+    pub theta: Decimal,
+    #[serde(rename = "g", with = "string_or_float")]
+    pub gamma: Decimal,
+    #[serde(rename = "v", with = "string_or_float")]
+    pub vega: Decimal,
+    #[serde(rename = "vo", with = "string_or_float")]
+    pub mark_iv: Decimal,
+    #[serde(rename = "mp", with = "string_or_float")]
+    pub mark_price: Decimal,
+    #[serde(rename = "hl", with = "string_or_float")]
+    pub high_price_limit: Decimal,
+    #[serde(rename = "ll", with = "string_or_float")]
+    pub low_price_limit: Decimal,
+    /// Estimated exercise price, only meaningful in the last hour before expiry
+    #[serde(rename = "eep", with = "string_or_float")]
+    pub estimated_exercise_price: Decimal,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct MarkPriceEvent {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "mp", with = "string_or_float")]
+    pub mark_price: Decimal,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct IndexEvent {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    /// Underlying, such as "BTCUSDT"
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "p", with = "string_or_float")]
+    pub index_price: Decimal,
+}
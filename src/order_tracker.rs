@@ -0,0 +1,499 @@
+//! Order lifecycle tracking.
+//!
+//! [`OrderTracker`] keeps the state of spot orders from the three sources that report it: the
+//! [`Transaction`] returned when placing an order, `executionReport` events ([`OrderUpdate`]) from
+//! the user stream and periodic reconciliation against the open orders of the REST API.
+//!
+//! Fills are accumulated from the cumulative quantities reported by the exchange, so events that
+//! are received twice are ignored. When the cumulative quantity of an event does not follow from the
+//! known state, some events were missed and an [`OrderEvent::Gap`] is emitted.
+
+use std::collections::HashMap;
+
+use rust_decimal::Decimal;
+
+use crate::account::{Account, OrderStatusRequest};
+use crate::errors::*;
+use crate::rest_model::{ExecutionType, Order, OrderSide, OrderStatus, OrderType, Transaction};
+use crate::ws_model::OrderUpdate;
+
+/// State of a tracked order
+#[derive(Debug, Clone)]
+pub struct TrackedOrder {
+    pub symbol: String,
+    pub order_id: u64,
+    pub client_order_id: String,
+    pub side: OrderSide,
+    pub order_type: OrderType,
+    pub price: Decimal,
+    pub orig_qty: Decimal,
+    pub status: OrderStatus,
+    /// Every status the order went through, in order
+    pub transitions: Vec<OrderStatus>,
+    pub executed_qty: Decimal,
+    pub cumulative_quote_qty: Decimal,
+    /// Commissions paid per asset, only complete if no fill was missed
+    pub commissions: HashMap<String, Decimal>,
+    /// Transaction time of the last update applied, in milliseconds. Placements, execution reports
+    /// (`T`) and REST queries (`updateTime`) are ordered on this single clock
+    pub last_update_time: u64,
+}
+
+impl TrackedOrder {
+    /// Average fill price, None if nothing was filled
+    pub fn avg_price(&self) -> Option<Decimal> {
+        if self.executed_qty.is_zero() {
+            None
+        } else {
+            Some(self.cumulative_quote_qty / self.executed_qty)
+        }
+    }
+
+    pub fn remaining_qty(&self) -> Decimal { (self.orig_qty - self.executed_qty).max(Decimal::ZERO) }
+
+    /// Whether the order can no longer change
+    pub fn is_final(&self) -> bool { is_final(&self.status) }
+
+    fn set_status(&mut self, status: &OrderStatus, events: &mut Vec<OrderEvent>) {
+        if &self.status != status {
+            let from = std::mem::replace(&mut self.status, status.clone());
+            self.transitions.push(status.clone());
+            events.push(OrderEvent::StatusChanged { from });
+        }
+    }
+
+    /// Moves the cumulative quantities forward, reporting the quantity filled without being seen
+    fn catch_up(&mut self, executed_qty: Decimal, cumulative_quote_qty: Decimal, events: &mut Vec<OrderEvent>) {
+        if executed_qty > self.executed_qty {
+            events.push(OrderEvent::Gap {
+                missed_qty: executed_qty - self.executed_qty,
+            });
+            self.executed_qty = executed_qty;
+            self.cumulative_quote_qty = cumulative_quote_qty;
+        }
+    }
+}
+
+/// A change applied to a tracked order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrderEvent {
+    /// The order is tracked for the first time
+    New,
+    /// The order status changed
+    StatusChanged { from: OrderStatus },
+    /// The order was filled
+    Fill { qty: Decimal, price: Decimal },
+    /// Fills were missed, the cumulative quantities were corrected but commissions may be incomplete
+    Gap { missed_qty: Decimal },
+    /// The order is no longer open on the exchange but its final status is unknown
+    Missing,
+}
+
+/// Whether the order status can no longer change
+pub fn is_final(status: &OrderStatus) -> bool {
+    matches!(
+        status,
+        OrderStatus::Filled
+            | OrderStatus::Canceled
+            | OrderStatus::Rejected
+            | OrderStatus::Expired
+            | OrderStatus::ExpiredInMatch
+    )
+}
+
+type OrderKey = (String, u64);
+type OrderHandler<'a> = Box<dyn FnMut(&OrderEvent, &TrackedOrder) + 'a + Send>;
+
+/// Tracks spot orders by order id and client order id
+#[derive(Default)]
+pub struct OrderTracker<'a> {
+    orders: HashMap<OrderKey, TrackedOrder>,
+    client_ids: HashMap<String, OrderKey>,
+    handler: Option<OrderHandler<'a>>,
+}
+
+impl<'a> OrderTracker<'a> {
+    pub fn new() -> Self { Self::default() }
+
+    /// Calls the handler for every change applied to an order
+    pub fn with_handler<Callback>(mut self, handler: Callback) -> Self
+    where
+        Callback: FnMut(&OrderEvent, &TrackedOrder) + 'a + Send,
+    {
+        self.handler = Some(Box::new(handler));
+        self
+    }
+
+    pub fn get(&self, symbol: &str, order_id: u64) -> Option<&TrackedOrder> {
+        self.orders.get(&(symbol.to_string(), order_id))
+    }
+
+    pub fn get_by_client_id(&self, client_order_id: &str) -> Option<&TrackedOrder> {
+        self.client_ids
+            .get(client_order_id)
+            .and_then(|key| self.orders.get(key))
+    }
+
+    /// Orders which did not reach a final status
+    pub fn open_orders(&self) -> impl Iterator<Item = &TrackedOrder> { self.orders.values().filter(|o| !o.is_final()) }
+
+    pub fn orders(&self) -> impl Iterator<Item = &TrackedOrder> { self.orders.values() }
+
+    /// Stops tracking orders which reached a final status, returning them
+    pub fn remove_final(&mut self) -> Vec<TrackedOrder> {
+        let keys: Vec<OrderKey> = self
+            .orders
+            .iter()
+            .filter(|(_, o)| o.is_final())
+            .map(|(k, _)| k.clone())
+            .collect();
+        let removed: Vec<TrackedOrder> = keys.iter().filter_map(|k| self.orders.remove(k)).collect();
+        self.client_ids.retain(|_, key| !keys.contains(key));
+        removed
+    }
+
+    /// Applies the response of a placed order
+    pub fn apply_transaction(&mut self, transaction: &Transaction) {
+        let key = (transaction.symbol.clone(), transaction.order_id);
+        let mut events = vec![];
+        match self.orders.get_mut(&key) {
+            Some(order) => {
+                if transaction.executed_qty > order.executed_qty {
+                    order.executed_qty = transaction.executed_qty;
+                    order.cumulative_quote_qty = transaction.cummulative_quote_qty;
+                    order.commissions = fill_commissions(transaction);
+                }
+                if transaction.transact_time >= order.last_update_time && !order.is_final() {
+                    order.set_status(&transaction.status, &mut events);
+                    order.last_update_time = transaction.transact_time;
+                }
+            }
+            None => {
+                let order = TrackedOrder {
+                    symbol: transaction.symbol.clone(),
+                    order_id: transaction.order_id,
+                    client_order_id: transaction.client_order_id.clone(),
+                    side: transaction.side.clone(),
+                    order_type: transaction.order_type.clone(),
+                    price: transaction.price,
+                    orig_qty: transaction.orig_qty,
+                    status: transaction.status.clone(),
+                    transitions: vec![transaction.status.clone()],
+                    executed_qty: transaction.executed_qty,
+                    cumulative_quote_qty: transaction.cummulative_quote_qty,
+                    commissions: fill_commissions(transaction),
+                    last_update_time: transaction.transact_time,
+                };
+                self.track(order);
+                events.push(OrderEvent::New);
+            }
+        }
+        self.notify(&key, events);
+    }
+
+    /// Applies an `executionReport` event from the user stream
+    pub fn apply_update(&mut self, update: &OrderUpdate) {
+        let key = (update.symbol.clone(), update.order_id);
+        let mut events = vec![];
+        if !self.orders.contains_key(&key) {
+            // Cancellations carry the client order id of the cancel request, the order's own is in `C`
+            let client_order_id = update
+                .origin_client_id
+                .clone()
+                .filter(|id| !id.is_empty())
+                .or_else(|| update.client_order_id.clone())
+                .unwrap_or_default();
+            self.track(TrackedOrder {
+                symbol: update.symbol.clone(),
+                order_id: update.order_id,
+                client_order_id,
+                side: update.side.clone(),
+                order_type: update.order_type.clone(),
+                price: update.price,
+                orig_qty: update.qty,
+                status: OrderStatus::New,
+                transitions: vec![OrderStatus::New],
+                executed_qty: Decimal::ZERO,
+                cumulative_quote_qty: Decimal::ZERO,
+                commissions: HashMap::new(),
+                last_update_time: update.order_creation_time,
+            });
+            events.push(OrderEvent::New);
+        }
+        let Some(order) = self.orders.get_mut(&key) else {
+            return;
+        };
+        if order.is_final() || update.trade_order_time < order.last_update_time {
+            self.notify(&key, events);
+            return;
+        }
+        if let ExecutionType::Trade = update.execution_type {
+            let previous_qty = update.cumulative_filled_qty - update.qty_last_executed;
+            let previous_quote_qty =
+                update.cumulative_quote_asset_transacted_qty - update.last_quote_asset_transacted_qty;
+            order.catch_up(previous_qty, previous_quote_qty, &mut events);
+            if update.cumulative_filled_qty > order.executed_qty {
+                order.executed_qty = update.cumulative_filled_qty;
+                order.cumulative_quote_qty = update.cumulative_quote_asset_transacted_qty;
+                if let Some(asset) = &update.commission_asset {
+                    *order.commissions.entry(asset.clone()).or_default() += update.commission;
+                }
+                events.push(OrderEvent::Fill {
+                    qty: update.qty_last_executed,
+                    price: update.last_executed_price,
+                });
+            }
+        } else {
+            order.catch_up(
+                update.cumulative_filled_qty,
+                update.cumulative_quote_asset_transacted_qty,
+                &mut events,
+            );
+        }
+        order.set_status(&update.current_order_status, &mut events);
+        order.last_update_time = update.trade_order_time;
+        self.notify(&key, events);
+    }
+
+    /// Applies an order queried from the REST API, such as with `Account::order_status`
+    pub fn apply_order(&mut self, rest_order: &Order) {
+        let key = (rest_order.symbol.clone(), rest_order.order_id);
+        let mut events = vec![];
+        match self.orders.get_mut(&key) {
+            Some(order) => {
+                order.catch_up(rest_order.executed_qty, rest_order.cummulative_quote_qty, &mut events);
+                if rest_order.update_time >= order.last_update_time && !order.is_final() {
+                    order.set_status(&rest_order.status, &mut events);
+                    order.last_update_time = rest_order.update_time;
+                }
+            }
+            None => {
+                self.track(TrackedOrder {
+                    symbol: rest_order.symbol.clone(),
+                    order_id: rest_order.order_id,
+                    client_order_id: rest_order.client_order_id.clone(),
+                    side: rest_order.side.clone(),
+                    order_type: rest_order.order_type.clone(),
+                    price: rest_order.price,
+                    orig_qty: rest_order.orig_qty,
+                    status: rest_order.status.clone(),
+                    transitions: vec![rest_order.status.clone()],
+                    executed_qty: rest_order.executed_qty,
+                    cumulative_quote_qty: rest_order.cummulative_quote_qty,
+                    commissions: HashMap::new(),
+                    last_update_time: rest_order.update_time,
+                });
+                events.push(OrderEvent::New);
+            }
+        }
+        self.notify(&key, events);
+    }
+
+    /// Reconciles the tracked orders of a symbol with its open orders on the exchange.
+    ///
+    /// Returns the ids of orders the tracker believes open which are not open anymore, their final
+    /// status can be queried and applied with [`OrderTracker::apply_order`].
+    pub fn reconcile_open_orders(&mut self, symbol: &str, open_orders: &[Order]) -> Vec<u64> {
+        for order in open_orders {
+            self.apply_order(order);
+        }
+        let missing: Vec<u64> = self
+            .open_orders()
+            .filter(|o| o.symbol == symbol && !open_orders.iter().any(|open| open.order_id == o.order_id))
+            .map(|o| o.order_id)
+            .collect();
+        for order_id in &missing {
+            self.notify(&(symbol.to_string(), *order_id), vec![OrderEvent::Missing]);
+        }
+        missing
+    }
+
+    /// Fetches the open orders of a symbol and reconciles them, querying the status of the orders
+    /// which were closed without the tracker being notified
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, account::*, config::*, order_tracker::*};
+    /// let account: Account = Binance::new_with_env(&Config::testnet());
+    /// let mut tracker = OrderTracker::new().with_handler(|event, order| println!("{:?} {:?}", event, order));
+    /// let synced = tokio_test::block_on(tracker.sync(&account, "BTCUSDT"));
+    /// assert!(synced.is_ok(), "{:?}", synced);
+    /// ```
+    pub async fn sync(&mut self, account: &Account, symbol: &str) -> Result<()> {
+        let open_orders = account.get_open_orders(symbol).await?;
+        for order_id in self.reconcile_open_orders(symbol, &open_orders) {
+            let order = account
+                .order_status(OrderStatusRequest {
+                    symbol: symbol.to_string(),
+                    order_id: Some(order_id),
+                    ..OrderStatusRequest::default()
+                })
+                .await?;
+            self.apply_order(&order);
+        }
+        Ok(())
+    }
+
+    fn track(&mut self, order: TrackedOrder) {
+        let key = (order.symbol.clone(), order.order_id);
+        if !order.client_order_id.is_empty() {
+            self.client_ids.insert(order.client_order_id.clone(), key.clone());
+        }
+        self.orders.insert(key, order);
+    }
+
+    fn notify(&mut self, key: &OrderKey, events: Vec<OrderEvent>) {
+        if let (Some(handler), Some(order)) = (self.handler.as_mut(), self.orders.get(key)) {
+            for event in &events {
+                handler(event, order);
+            }
+        }
+    }
+}
+
+fn fill_commissions(transaction: &Transaction) -> HashMap<String, Decimal> {
+    let mut commissions: HashMap<String, Decimal> = HashMap::new();
+    for fill in &transaction.fills {
+        *commissions.entry(fill.commission_asset.clone()).or_default() += fill.commission;
+    }
+    commissions
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rust_decimal_macros::dec;
+    use std::sync::{Arc, Mutex};
+
+    fn transaction(status: &str, executed_qty: &str, transact_time: u64) -> Transaction {
+        serde_json::from_str(&format!(
+            r#"{{"symbol":"BTCUSDT","orderId":1,"clientOrderId":"bot-1","transactTime":{transact_time},
+            "price":"20000","origQty":"1","executedQty":"{executed_qty}","cummulativeQuoteQty":"0",
+            "status":"{status}","timeInForce":"GTC","type":"LIMIT","side":"BUY","fills":[]}}"#
+        ))
+        .unwrap()
+    }
+
+    fn update(execution_type: &str, status: &str, last_qty: &str, cumulative_qty: &str, time: u64) -> OrderUpdate {
+        serde_json::from_str(&format!(
+            r#"{{"e":"executionReport","E":{event_time},"s":"BTCUSDT","c":"bot-1","S":"BUY","o":"LIMIT",
+            "f":"GTC","q":"1","p":"20000","P":"0","F":"0","g":-1,"C":"","x":"{execution_type}",
+            "X":"{status}","r":"NONE","i":1,"l":"{last_qty}","z":"{cumulative_qty}","L":"20000",
+            "n":"0.001","N":"BNB","T":{time},"t":1,"I":1,"w":true,"m":false,"M":false,"O":1000,
+            "Z":"{quote_qty}","Y":"{last_quote_qty}","Q":"0"}}"#,
+            // Events are sent a while after the transaction, on another clock
+            event_time = time + 5000,
+            quote_qty = cumulative_qty.parse::<Decimal>().unwrap() * dec!(20000),
+            last_quote_qty = last_qty.parse::<Decimal>().unwrap() * dec!(20000),
+        ))
+        .unwrap()
+    }
+
+    fn order(order_id: u64, status: &str, executed_qty: &str, update_time: u64) -> Order {
+        serde_json::from_str(&format!(
+            r#"{{"symbol":"BTCUSDT","orderId":{order_id},"orderListId":-1,"clientOrderId":"bot-{order_id}",
+            "price":"20000","origQty":"1","executedQty":"{executed_qty}","cummulativeQuoteQty":"0",
+            "status":"{status}","timeInForce":"GTC","type":"LIMIT","side":"BUY","stopPrice":"0",
+            "icebergQty":"0","time":1000,"updateTime":{update_time},"isWorking":true,"origQuoteOrderQty":"0"}}"#
+        ))
+        .unwrap()
+    }
+
+    fn recording_tracker<'a>(events: Arc<Mutex<Vec<OrderEvent>>>) -> OrderTracker<'a> {
+        OrderTracker::new()
+            .with_handler(move |event: &OrderEvent, _: &TrackedOrder| events.lock().unwrap().push(event.clone()))
+    }
+
+    #[test]
+    fn fills_from_placement_and_execution_reports() {
+        let events = Arc::new(Mutex::new(vec![]));
+        let mut tracker = recording_tracker(events.clone());
+        tracker.apply_transaction(&transaction("NEW", "0", 1000));
+        tracker.apply_update(&update("NEW", "NEW", "0", "0", 1000));
+        tracker.apply_update(&update("TRADE", "PARTIALLY_FILLED", "0.4", "0.4", 1010));
+        // Received twice
+        tracker.apply_update(&update("TRADE", "PARTIALLY_FILLED", "0.4", "0.4", 1010));
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                OrderEvent::New,
+                OrderEvent::Fill {
+                    qty: dec!(0.4),
+                    price: dec!(20000)
+                },
+                OrderEvent::StatusChanged { from: OrderStatus::New },
+            ]
+        );
+
+        // The fill of 0.3 in between was missed
+        events.lock().unwrap().clear();
+        tracker.apply_update(&update("TRADE", "FILLED", "0.3", "1", 1030));
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                OrderEvent::Gap { missed_qty: dec!(0.3) },
+                OrderEvent::Fill {
+                    qty: dec!(0.3),
+                    price: dec!(20000)
+                },
+                OrderEvent::StatusChanged {
+                    from: OrderStatus::PartiallyFilled
+                },
+            ]
+        );
+        let order = tracker.get_by_client_id("bot-1").unwrap();
+        assert!(order.is_final());
+        assert_eq!(order.executed_qty, dec!(1));
+        assert_eq!(order.avg_price(), Some(dec!(20000)));
+        assert_eq!(order.commissions.get("BNB"), Some(&dec!(0.002)));
+        assert_eq!(
+            order.transitions,
+            vec![OrderStatus::New, OrderStatus::PartiallyFilled, OrderStatus::Filled]
+        );
+    }
+
+    #[test]
+    fn updates_are_ordered_by_transaction_time() {
+        let mut tracker = OrderTracker::new();
+        tracker.apply_update(&update("NEW", "NEW", "0", "0", 1000));
+        tracker.apply_transaction(&transaction("PARTIALLY_FILLED", "0.5", 1020));
+        let tracked = tracker.get("BTCUSDT", 1).unwrap();
+        assert_eq!(tracked.status, OrderStatus::PartiallyFilled);
+        assert_eq!(tracked.last_update_time, 1020);
+
+        // Older than the placement response, although its event time is later
+        tracker.apply_update(&update("NEW", "NEW", "0", "0", 1010));
+        assert_eq!(tracker.get("BTCUSDT", 1).unwrap().status, OrderStatus::PartiallyFilled);
+        tracker.apply_order(&order(1, "NEW", "0", 1015));
+        assert_eq!(tracker.get("BTCUSDT", 1).unwrap().status, OrderStatus::PartiallyFilled);
+        tracker.apply_order(&order(1, "CANCELED", "0.5", 1040));
+        let tracked = tracker.get("BTCUSDT", 1).unwrap();
+        assert_eq!(tracked.status, OrderStatus::Canceled);
+        assert_eq!(tracked.last_update_time, 1040);
+    }
+
+    #[test]
+    fn reconciles_open_orders() {
+        let events = Arc::new(Mutex::new(vec![]));
+        let mut tracker = recording_tracker(events.clone());
+        tracker.apply_order(&order(1, "NEW", "0", 1000));
+        tracker.apply_order(&order(2, "NEW", "0", 1000));
+        events.lock().unwrap().clear();
+
+        let missing = tracker.reconcile_open_orders("BTCUSDT", &[order(2, "PARTIALLY_FILLED", "0.2", 1010)]);
+        assert_eq!(missing, vec![1]);
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                OrderEvent::Gap { missed_qty: dec!(0.2) },
+                OrderEvent::StatusChanged { from: OrderStatus::New },
+                OrderEvent::Missing,
+            ]
+        );
+
+        tracker.apply_order(&order(1, "CANCELED", "0", 1020));
+        let removed = tracker.remove_final();
+        assert_eq!(removed.len(), 1);
+        assert!(tracker.get_by_client_id("bot-1").is_none());
+        assert_eq!(tracker.open_orders().count(), 1);
+    }
+}
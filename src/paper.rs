@@ -0,0 +1,1283 @@
+//! Paper trading.
+//!
+//! [`PaperAccount`] exposes the trading methods of [`crate::account::Account`] but matches orders
+//! against a local order book fed with market data, such as `depthUpdate` and `trade` events from
+//! live or replayed streams, instead of sending them to the exchange.
+//!
+//! Orders are checked against the symbol filters and rejected with the same errors as the exchange.
+//! Every change produces the `executionReport` and `outboundAccountPosition` events the user stream
+//! would send, which can be drained with [`PaperAccount::take_events`].
+//!
+//! Taker orders match the levels of the local book. Resting orders are filled as makers once the
+//! market trades through their price, or when the opposite side of the book crosses it. Trailing
+//! stops are not simulated.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, MutexGuard};
+
+use rust_decimal::prelude::*;
+
+use crate::account::{OrderCancellation, OrderRequest, OrderStatusRequest};
+use crate::errors::*;
+use crate::rest_model::{
+    AccountInformation, AccountType, Balance, ExecutionType, Fill, Filters, Order, OrderBook, OrderCanceled, OrderSide,
+    OrderStatus, OrderType, Symbol, TestResponse, TimeInForce, TradeHistory, Transaction,
+};
+use crate::util::get_timestamp;
+use crate::ws_model::{
+    AccountPositionUpdate, DepthOrderBookEvent, EventBalance, OrderUpdate, TradeEvent, WebsocketEvent,
+};
+
+/// Commission rates, 0.001 is 0.1%
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fees {
+    pub maker: Decimal,
+    pub taker: Decimal,
+}
+
+/// The default spot rates of 0.1%
+impl Default for Fees {
+    fn default() -> Self {
+        Self {
+            maker: Decimal::new(1, 3),
+            taker: Decimal::new(1, 3),
+        }
+    }
+}
+
+/// Simulated spot account
+pub struct PaperAccount {
+    state: Mutex<PaperState>,
+}
+
+impl PaperAccount {
+    /// Creates an account trading the symbols, typically from `General::exchange_info`
+    pub fn new(symbols: Vec<Symbol>) -> Self {
+        Self {
+            state: Mutex::new(PaperState {
+                symbols: symbols.into_iter().map(|s| (s.symbol.clone(), s)).collect(),
+                fees: Fees::default(),
+                balances: HashMap::new(),
+                books: HashMap::new(),
+                orders: BTreeMap::new(),
+                trades: HashMap::new(),
+                next_order_id: 1,
+                next_trade_id: 1,
+                time: 0,
+                events: vec![],
+            }),
+        }
+    }
+
+    pub fn with_fees(self, fees: Fees) -> Self {
+        self.state().fees = fees;
+        self
+    }
+
+    /// Credits an asset to the account
+    pub fn deposit<S: Into<String>>(&self, asset: S, amount: Decimal) {
+        let mut state = self.state();
+        let asset = asset.into();
+        state.balances.entry(asset.clone()).or_default().free += amount;
+        state.emit_positions(&[asset]);
+    }
+
+    /// Events the user stream would have sent since the last call
+    pub fn take_events(&self) -> Vec<WebsocketEvent> { std::mem::take(&mut self.state().events) }
+
+    /// Replaces the book of a symbol with a snapshot, such as from `Market::get_depth`
+    pub fn apply_order_book(&self, symbol: &str, book: &OrderBook) {
+        let mut state = self.state();
+        let local = state.books.entry(symbol.to_string()).or_default();
+        local.bids = book.bids.iter().map(|l| (l.price, l.qty)).collect();
+        local.asks = book.asks.iter().map(|l| (l.price, l.qty)).collect();
+        state.match_resting(symbol);
+    }
+
+    /// Applies a diff of the book, filling the resting orders it crosses
+    pub fn apply_depth(&self, event: &DepthOrderBookEvent) {
+        let mut state = self.state();
+        state.advance_time(event.event_time);
+        let local = state.books.entry(event.symbol.clone()).or_default();
+        for level in &event.bids {
+            set_level(&mut local.bids, level.price, level.qty);
+        }
+        for level in &event.asks {
+            set_level(&mut local.asks, level.price, level.qty);
+        }
+        state.match_resting(&event.symbol);
+    }
+
+    /// Applies a trade, triggering stop orders and filling the resting orders it trades through
+    pub fn apply_trade(&self, event: &TradeEvent) -> Result<()> {
+        let price = parse_decimal(&event.price)?;
+        let qty = parse_decimal(&event.qty)?;
+        let mut state = self.state();
+        state.advance_time(event.trade_order_time);
+        state.on_trade(&event.symbol, price, qty);
+        Ok(())
+    }
+
+    /// Applies the market data events, ignoring the others
+    pub fn apply_event(&self, event: &WebsocketEvent) -> Result<()> {
+        match event {
+            WebsocketEvent::DepthOrderBook(depth) => self.apply_depth(depth),
+            WebsocketEvent::Trade(trade) => self.apply_trade(trade)?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Place an order, see `Account::place_order`
+    pub async fn place_order(&self, order: OrderRequest) -> Result<Transaction> { self.state().place(order) }
+
+    /// Validate an order without placing it, see `Account::place_test_order`
+    pub async fn place_test_order(&self, order: OrderRequest) -> Result<TestResponse> {
+        self.state().check(&order)?;
+        Ok(TestResponse {})
+    }
+
+    /// Cancel an order, see `Account::cancel_order`
+    pub async fn cancel_order(&self, o: OrderCancellation) -> Result<OrderCanceled> { self.state().cancel(o) }
+
+    /// Cancel all open orders of a symbol, see `Account::cancel_all_open_orders`
+    pub async fn cancel_all_open_orders<S>(&self, symbol: S) -> Result<Vec<Order>>
+    where
+        S: AsRef<str>,
+    {
+        let mut state = self.state();
+        let order_ids: Vec<u64> = state
+            .orders
+            .values()
+            .filter(|o| o.symbol == symbol.as_ref() && !o.is_final())
+            .map(|o| o.order_id)
+            .collect();
+        let mut canceled = vec![];
+        for order_id in order_ids {
+            state.cancel(OrderCancellation {
+                symbol: symbol.as_ref().to_string(),
+                order_id: Some(order_id),
+                ..OrderCancellation::default()
+            })?;
+            canceled.push(state.orders[&order_id].to_order());
+        }
+        Ok(canceled)
+    }
+
+    /// Open orders of a symbol, see `Account::get_open_orders`
+    pub async fn get_open_orders<S>(&self, symbol: S) -> Result<Vec<Order>>
+    where
+        S: AsRef<str>,
+    {
+        Ok(self
+            .state()
+            .orders
+            .values()
+            .filter(|o| o.symbol == symbol.as_ref() && !o.is_final())
+            .map(PaperOrder::to_order)
+            .collect())
+    }
+
+    /// Open orders of all symbols, see `Account::get_all_open_orders`
+    pub async fn get_all_open_orders(&self) -> Result<Vec<Order>> {
+        Ok(self
+            .state()
+            .orders
+            .values()
+            .filter(|o| !o.is_final())
+            .map(PaperOrder::to_order)
+            .collect())
+    }
+
+    /// Status of an order, see `Account::order_status`
+    pub async fn order_status(&self, osr: OrderStatusRequest) -> Result<Order> {
+        let state = self.state();
+        state
+            .find(&osr.symbol, osr.order_id, osr.orig_client_order_id.as_deref())
+            .map(|id| state.orders[&id].to_order())
+            .ok_or_else(|| binance_error(UNKNOWN_ORDER, "Order does not exist."))
+    }
+
+    /// Account balances, see `Account::get_account`
+    pub async fn get_account(&self) -> Result<AccountInformation> {
+        let state = self.state();
+        let bips = |rate: Decimal| (rate * Decimal::from(10000)).to_f32().unwrap_or_default();
+        let mut balances: Vec<Balance> = state
+            .balances
+            .iter()
+            .map(|(asset, b)| Balance {
+                asset: asset.clone(),
+                free: b.free,
+                locked: b.locked,
+            })
+            .collect();
+        balances.sort_by(|a, b| a.asset.cmp(&b.asset));
+        Ok(AccountInformation {
+            maker_commission: bips(state.fees.maker),
+            taker_commission: bips(state.fees.taker),
+            buyer_commission: 0.0,
+            seller_commission: 0.0,
+            can_trade: true,
+            can_withdraw: false,
+            can_deposit: false,
+            account_type: AccountType::Spot,
+            balances,
+            permissions: vec![AccountType::Spot],
+            update_time: state.now() as i64,
+        })
+    }
+
+    /// Balance of an asset, see `Account::get_balance`
+    pub async fn get_balance<S>(&self, asset: S) -> Result<Balance>
+    where
+        S: AsRef<str>,
+    {
+        let state = self.state();
+        let balance = state.balances.get(asset.as_ref()).cloned().unwrap_or_default();
+        Ok(Balance {
+            asset: asset.as_ref().to_string(),
+            free: balance.free,
+            locked: balance.locked,
+        })
+    }
+
+    /// Trades of a symbol, see `Account::trade_history`
+    pub async fn trade_history<S>(&self, symbol: S) -> Result<Vec<TradeHistory>>
+    where
+        S: AsRef<str>,
+    {
+        Ok(self.state().trades.get(symbol.as_ref()).cloned().unwrap_or_default())
+    }
+
+    fn state(&self) -> MutexGuard<'_, PaperState> { self.state.lock().unwrap_or_else(|e| e.into_inner()) }
+}
+
+const INVALID_PARAMETER: i32 = -1013;
+const NEW_ORDER_REJECTED: i32 = -2010;
+const CANCEL_REJECTED: i32 = -2011;
+const UNKNOWN_ORDER: i32 = -2013;
+
+fn binance_error(code: i32, msg: &str) -> Error {
+    Error::BinanceError {
+        response: BinanceContentError::new(code, msg),
+    }
+}
+
+fn filter_failure(filter: &str) -> Error { binance_error(INVALID_PARAMETER, &format!("Filter failure: {filter}")) }
+
+fn parse_decimal(value: &str) -> Result<Decimal> {
+    Decimal::from_str(value).map_err(|e| Error::Msg(format!("invalid decimal {value}: {e}")))
+}
+
+fn set_level(levels: &mut BTreeMap<Decimal, Decimal>, price: Decimal, qty: Decimal) {
+    if qty.is_zero() {
+        levels.remove(&price);
+    } else {
+        levels.insert(price, qty);
+    }
+}
+
+fn floor_to_step(qty: Decimal, step: Decimal) -> Decimal {
+    if step.is_zero() {
+        qty
+    } else {
+        (qty / step).floor() * step
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct PaperBalance {
+    free: Decimal,
+    locked: Decimal,
+}
+
+#[derive(Debug, Default)]
+struct LocalBook {
+    bids: BTreeMap<Decimal, Decimal>,
+    asks: BTreeMap<Decimal, Decimal>,
+    last_price: Option<Decimal>,
+}
+
+impl LocalBook {
+    /// Best level a taker on this side can match, within the limit price
+    fn best_for(&self, side: &OrderSide, limit: Option<Decimal>) -> Option<(Decimal, Decimal)> {
+        match side {
+            OrderSide::Buy => self
+                .asks
+                .iter()
+                .next()
+                .filter(|(price, _)| !matches!(limit, Some(l) if **price > l)),
+            OrderSide::Sell => self
+                .bids
+                .iter()
+                .next_back()
+                .filter(|(price, _)| !matches!(limit, Some(l) if **price < l)),
+        }
+        .map(|(p, q)| (*p, *q))
+    }
+
+    fn consume(&mut self, side: &OrderSide, price: Decimal, qty: Decimal) {
+        let levels = match side {
+            OrderSide::Buy => &mut self.asks,
+            OrderSide::Sell => &mut self.bids,
+        };
+        if let Some(level) = levels.get_mut(&price) {
+            *level -= qty;
+            if *level <= Decimal::ZERO {
+                levels.remove(&price);
+            }
+        }
+    }
+
+    /// Quantity a taker on this side can match within the limit price
+    fn depth_for(&self, side: &OrderSide, limit: Decimal) -> Decimal {
+        match side {
+            OrderSide::Buy => self.asks.range(..=limit).map(|(_, q)| *q).sum(),
+            OrderSide::Sell => self.bids.range(limit..).map(|(_, q)| *q).sum(),
+        }
+    }
+
+    /// Quote cost of buying the quantity as a taker, the quantity beyond the book is priced at its
+    /// last level or at the reference price when the book is empty
+    fn buy_cost(&self, qty: Decimal) -> Option<Decimal> {
+        let mut remaining = qty;
+        let mut cost = Decimal::ZERO;
+        let mut last_level = None;
+        for (price, level_qty) in &self.asks {
+            if remaining.is_zero() {
+                break;
+            }
+            let matched = remaining.min(*level_qty);
+            cost += matched * price;
+            remaining -= matched;
+            last_level = Some(*price);
+        }
+        let price = last_level.or_else(|| self.reference_price())?;
+        Some(cost + remaining * price)
+    }
+
+    fn reference_price(&self) -> Option<Decimal> {
+        self.last_price.or_else(|| {
+            let bid = self.bids.keys().next_back()?;
+            let ask = self.asks.keys().next()?;
+            Some((bid + ask) / Decimal::TWO)
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PaperOrder {
+    symbol: String,
+    order_id: u64,
+    client_order_id: String,
+    side: OrderSide,
+    order_type: OrderType,
+    time_in_force: TimeInForce,
+    /// Zero for market orders by quote quantity until they are filled
+    qty: Decimal,
+    quote_order_qty: Decimal,
+    price: Decimal,
+    stop_price: Decimal,
+    status: OrderStatus,
+    executed_qty: Decimal,
+    cumulative_quote_qty: Decimal,
+    /// Whether the order is working, stop orders only work once triggered
+    working: bool,
+    /// Amount still locked, in quote asset for buys and base asset for sells
+    locked: Decimal,
+    time: u64,
+    update_time: u64,
+    fills: Vec<Fill>,
+}
+
+impl PaperOrder {
+    fn is_final(&self) -> bool { !matches!(self.status, OrderStatus::New | OrderStatus::PartiallyFilled) }
+
+    fn is_market(&self) -> bool {
+        matches!(
+            self.order_type,
+            OrderType::Market | OrderType::StopLoss | OrderType::TakeProfit
+        )
+    }
+
+    /// Whether the last price triggers the stop order
+    fn triggers(&self, last_price: Decimal) -> bool {
+        match (&self.order_type, &self.side) {
+            (OrderType::StopLoss | OrderType::StopLossLimit, OrderSide::Buy)
+            | (OrderType::TakeProfit | OrderType::TakeProfitLimit, OrderSide::Sell) => last_price >= self.stop_price,
+            (OrderType::StopLoss | OrderType::StopLossLimit, OrderSide::Sell)
+            | (OrderType::TakeProfit | OrderType::TakeProfitLimit, OrderSide::Buy) => last_price <= self.stop_price,
+            _ => false,
+        }
+    }
+
+    /// Quantity left to fill at this price
+    fn remaining_at(&self, price: Decimal, step: Decimal) -> Decimal {
+        if self.qty.is_zero() {
+            floor_to_step((self.quote_order_qty - self.cumulative_quote_qty) / price, step)
+        } else {
+            self.qty - self.executed_qty
+        }
+    }
+
+    fn to_order(&self) -> Order {
+        Order {
+            symbol: self.symbol.clone(),
+            order_id: self.order_id,
+            order_list_id: -1,
+            client_order_id: self.client_order_id.clone(),
+            price: self.price,
+            orig_qty: self.qty,
+            executed_qty: self.executed_qty,
+            cummulative_quote_qty: self.cumulative_quote_qty,
+            status: self.status.clone(),
+            time_in_force: self.time_in_force.clone(),
+            order_type: self.order_type.clone(),
+            side: self.side.clone(),
+            stop_price: self.stop_price,
+            iceberg_qty: Decimal::ZERO,
+            time: self.time,
+            update_time: self.update_time,
+            is_working: self.working,
+            orig_quote_order_qty: self.quote_order_qty,
+        }
+    }
+
+    fn to_transaction(&self) -> Transaction {
+        Transaction {
+            symbol: self.symbol.clone(),
+            order_id: self.order_id,
+            client_order_id: self.client_order_id.clone(),
+            transact_time: self.time,
+            price: self.price,
+            orig_qty: self.qty,
+            executed_qty: self.executed_qty,
+            cummulative_quote_qty: self.cumulative_quote_qty,
+            status: self.status.clone(),
+            time_in_force: self.time_in_force.clone(),
+            order_type: self.order_type.clone(),
+            side: self.side.clone(),
+            fills: self.fills.clone(),
+        }
+    }
+}
+
+/// A fill, as reported in the execution report
+struct Execution {
+    qty: Decimal,
+    price: Decimal,
+    commission: Decimal,
+    commission_asset: String,
+    trade_id: i64,
+    is_maker: bool,
+}
+
+struct PaperState {
+    symbols: HashMap<String, Symbol>,
+    fees: Fees,
+    balances: HashMap<String, PaperBalance>,
+    books: HashMap<String, LocalBook>,
+    orders: BTreeMap<u64, PaperOrder>,
+    trades: HashMap<String, Vec<TradeHistory>>,
+    next_order_id: u64,
+    next_trade_id: u64,
+    /// Time of the latest market data, in milliseconds
+    time: u64,
+    events: Vec<WebsocketEvent>,
+}
+
+impl PaperState {
+    fn advance_time(&mut self, time: u64) {
+        self.time = self.time.max(time);
+    }
+
+    fn now(&self) -> u64 {
+        if self.time > 0 {
+            self.time
+        } else {
+            get_timestamp().unwrap_or_default()
+        }
+    }
+
+    fn symbol(&self, symbol: &str) -> Result<&Symbol> {
+        self.symbols
+            .get(symbol)
+            .ok_or_else(|| Error::UnknownSymbol(symbol.to_string()))
+    }
+
+    fn step_size(&self, symbol: &str) -> Decimal {
+        match self.symbols.get(symbol).and_then(Symbol::lot_size) {
+            Some(Filters::LotSize { step_size, .. }) => step_size,
+            _ => Decimal::ZERO,
+        }
+    }
+
+    fn assets(&self, symbol: &str) -> (String, String) {
+        self.symbols
+            .get(symbol)
+            .map(|s| (s.base_asset.clone(), s.quote_asset.clone()))
+            .unwrap_or_default()
+    }
+
+    fn free(&self, asset: &str) -> Decimal { self.balances.get(asset).map(|b| b.free).unwrap_or_default() }
+
+    fn find(&self, symbol: &str, order_id: Option<u64>, client_order_id: Option<&str>) -> Option<u64> {
+        self.orders
+            .values()
+            .find(|o| {
+                o.symbol == symbol
+                    && (order_id == Some(o.order_id) || client_order_id == Some(o.client_order_id.as_str()))
+            })
+            .map(|o| o.order_id)
+    }
+
+    /// Validates an order the way the exchange would
+    fn check(&self, order: &OrderRequest) -> Result<()> {
+        order.valid()?;
+        if order.trailing_delta.is_some() {
+            return Err(Error::InvalidOrderError {
+                msg: "Trailing stops are not simulated by the paper account".to_string(),
+            });
+        }
+        let symbol = self.symbol(&order.symbol)?;
+        if !symbol.order_types.contains(&order.order_type) {
+            return Err(binance_error(
+                INVALID_PARAMETER,
+                "Order type not supported for this symbol.",
+            ));
+        }
+        let reference_price = order
+            .price
+            .filter(|_| order.order_type.requires_price())
+            .or_else(|| self.books.get(&order.symbol).and_then(LocalBook::reference_price));
+        let is_market = matches!(
+            order.order_type,
+            OrderType::Market | OrderType::StopLoss | OrderType::TakeProfit
+        );
+        for filter in &symbol.filters {
+            match filter {
+                Filters::PriceFilter {
+                    min_price,
+                    max_price,
+                    tick_size,
+                } => {
+                    for price in [order.price, order.stop_price].into_iter().flatten() {
+                        if (!min_price.is_zero() && price < *min_price)
+                            || (!max_price.is_zero() && price > *max_price)
+                            || (!tick_size.is_zero() && !((price - min_price) % tick_size).is_zero())
+                        {
+                            return Err(filter_failure("PRICE_FILTER"));
+                        }
+                    }
+                }
+                Filters::LotSize {
+                    min_qty,
+                    max_qty,
+                    step_size,
+                } if !is_market => check_lot(order.quantity, *min_qty, *max_qty, *step_size, "LOT_SIZE")?,
+                Filters::MarketLotSize {
+                    min_qty,
+                    max_qty,
+                    step_size,
+                } if is_market => check_lot(order.quantity, *min_qty, *max_qty, *step_size, "MARKET_LOT_SIZE")?,
+                Filters::MinNotional {
+                    min_notional,
+                    apply_to_market,
+                    ..
+                } if !is_market || *apply_to_market => {
+                    if let Some(notional) = notional(order, reference_price) {
+                        if notional < *min_notional {
+                            return Err(filter_failure("MIN_NOTIONAL"));
+                        }
+                    }
+                }
+                Filters::Notional {
+                    min_notional,
+                    apply_min_to_market,
+                    max_notional,
+                    apply_max_to_market,
+                    ..
+                } => {
+                    if let Some(notional) = notional(order, reference_price) {
+                        if (!is_market || *apply_min_to_market) && notional < *min_notional {
+                            return Err(filter_failure("NOTIONAL"));
+                        }
+                        if (!is_market || *apply_max_to_market) && !max_notional.is_zero() && notional > *max_notional {
+                            return Err(filter_failure("NOTIONAL"));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn place(&mut self, request: OrderRequest) -> Result<Transaction> {
+        self.check(&request)?;
+        let (base_asset, quote_asset) = self.assets(&request.symbol);
+        let now = self.now();
+        let order_id = self.next_order_id;
+        let mut order = PaperOrder {
+            symbol: request.symbol.clone(),
+            order_id,
+            client_order_id: request
+                .new_client_order_id
+                .clone()
+                .unwrap_or_else(|| format!("paper-{order_id}")),
+            side: request.side.clone(),
+            order_type: request.order_type.clone(),
+            time_in_force: request.time_in_force.clone().unwrap_or(TimeInForce::GTC),
+            qty: request.quantity.unwrap_or_default(),
+            quote_order_qty: request.quote_order_qty.unwrap_or_default(),
+            price: request.price.unwrap_or_default(),
+            stop_price: request.stop_price.unwrap_or_default(),
+            status: OrderStatus::New,
+            executed_qty: Decimal::ZERO,
+            cumulative_quote_qty: Decimal::ZERO,
+            working: !request.order_type.requires_stop_price(),
+            locked: Decimal::ZERO,
+            time: now,
+            update_time: now,
+            fills: vec![],
+        };
+        let book = self.books.entry(order.symbol.clone()).or_default();
+        if order.order_type == OrderType::LimitMaker && book.best_for(&order.side, Some(order.price)).is_some() {
+            return Err(binance_error(
+                NEW_ORDER_REJECTED,
+                "Order would immediately match and take.",
+            ));
+        }
+        if let (false, Some(last_price)) = (order.working, book.last_price) {
+            if order.triggers(last_price) {
+                return Err(binance_error(
+                    NEW_ORDER_REJECTED,
+                    "Stop price would trigger immediately.",
+                ));
+            }
+        }
+
+        // Funds are locked for orders which can rest on the book, market orders use the free balance
+        let (lock_asset, lock) = match order.side {
+            OrderSide::Buy if !order.is_market() => (&quote_asset, order.qty * order.price),
+            OrderSide::Buy => (&quote_asset, Decimal::ZERO),
+            OrderSide::Sell => (&base_asset, order.qty),
+        };
+        // Market buys by base quantity must afford their estimated cost, stops at their stop price
+        let required = match (&order.side, order.is_market()) {
+            (OrderSide::Buy, true) if order.qty.is_zero() => order.quote_order_qty,
+            (OrderSide::Buy, true) if order.working => self
+                .books
+                .get(&order.symbol)
+                .and_then(|book| book.buy_cost(order.qty))
+                .unwrap_or_default(),
+            (OrderSide::Buy, true) => order.qty * order.stop_price,
+            _ => lock,
+        };
+        if self.free(lock_asset) < required || (order.is_market() && self.free(lock_asset).is_zero()) {
+            return Err(binance_error(
+                NEW_ORDER_REJECTED,
+                "Account has insufficient balance for requested action.",
+            ));
+        }
+        let balance = self.balances.entry(lock_asset.clone()).or_default();
+        balance.free -= lock;
+        balance.locked += lock;
+        order.locked = lock;
+        self.next_order_id += 1;
+        self.emit_order(&order, ExecutionType::New, None);
+
+        if order.working {
+            self.work(&mut order);
+        }
+        self.emit_positions(&[base_asset, quote_asset]);
+        let transaction = order.to_transaction();
+        order.fills.clear();
+        self.orders.insert(order.order_id, order);
+        Ok(transaction)
+    }
+
+    /// Matches a working order as a taker, then finalizes or rests it
+    fn work(&mut self, order: &mut PaperOrder) {
+        if order.is_market() {
+            self.take(order, None, false);
+            if order.status != OrderStatus::Filled {
+                order.qty = order.qty.max(order.executed_qty);
+                self.finish(order, OrderStatus::Expired);
+            }
+            return;
+        }
+        let book = self.books.entry(order.symbol.clone()).or_default();
+        if order.time_in_force == TimeInForce::FOK && book.depth_for(&order.side, order.price) < order.qty {
+            self.finish(order, OrderStatus::Expired);
+            return;
+        }
+        self.take(order, Some(order.price), false);
+        if order.status != OrderStatus::Filled && matches!(order.time_in_force, TimeInForce::IOC | TimeInForce::FOK) {
+            self.finish(order, OrderStatus::Expired);
+        }
+    }
+
+    /// Matches an order against the book until it is filled, the book has no level within the
+    /// limit price or the account cannot pay for more. Makers are filled at their own price.
+    fn take(&mut self, order: &mut PaperOrder, limit: Option<Decimal>, is_maker: bool) {
+        let step = self.step_size(&order.symbol);
+        let (base_asset, quote_asset) = self.assets(&order.symbol);
+        loop {
+            let Some((level_price, level_qty)) = self
+                .books
+                .get(&order.symbol)
+                .and_then(|book| book.best_for(&order.side, limit))
+            else {
+                return;
+            };
+            let price = if is_maker { order.price } else { level_price };
+            let remaining = order.remaining_at(price, step);
+            if remaining <= Decimal::ZERO {
+                return;
+            }
+            let affordable = match (&order.side, order.locked.is_zero()) {
+                (OrderSide::Buy, true) => floor_to_step(self.free(&quote_asset) / price, step),
+                (OrderSide::Sell, true) => self.free(&base_asset),
+                _ => remaining,
+            };
+            let qty = remaining.min(level_qty).min(affordable);
+            if qty <= Decimal::ZERO {
+                return;
+            }
+            if let Some(book) = self.books.get_mut(&order.symbol) {
+                book.consume(&order.side, level_price, qty);
+            }
+            self.fill(order, price, qty, is_maker);
+        }
+    }
+
+    fn fill(&mut self, order: &mut PaperOrder, price: Decimal, qty: Decimal, is_maker: bool) {
+        let (base_asset, quote_asset) = self.assets(&order.symbol);
+        let rate = if is_maker { self.fees.maker } else { self.fees.taker };
+        let quote_qty = price * qty;
+        let (commission, commission_asset) = match order.side {
+            OrderSide::Buy => {
+                let release = order.locked.min(qty * order.price);
+                order.locked -= release;
+                let quote = self.balances.entry(quote_asset.clone()).or_default();
+                quote.locked -= release;
+                quote.free += release - quote_qty;
+                let commission = qty * rate;
+                self.balances.entry(base_asset.clone()).or_default().free += qty - commission;
+                (commission, base_asset)
+            }
+            OrderSide::Sell => {
+                let release = order.locked.min(qty);
+                order.locked -= release;
+                let base = self.balances.entry(base_asset.clone()).or_default();
+                base.locked -= release;
+                base.free -= qty - release;
+                let commission = quote_qty * rate;
+                self.balances.entry(quote_asset.clone()).or_default().free += quote_qty - commission;
+                (commission, quote_asset)
+            }
+        };
+        let trade_id = self.next_trade_id;
+        self.next_trade_id += 1;
+        order.executed_qty += qty;
+        order.cumulative_quote_qty += quote_qty;
+        order.status = OrderStatus::PartiallyFilled;
+        order.update_time = self.now();
+        order.fills.push(Fill {
+            price,
+            qty,
+            commission,
+            commission_asset: commission_asset.clone(),
+        });
+        self.trades.entry(order.symbol.clone()).or_default().push(TradeHistory {
+            id: trade_id,
+            price,
+            qty,
+            commission: commission.to_string(),
+            commission_asset: commission_asset.clone(),
+            time: order.update_time,
+            is_buyer: order.side == OrderSide::Buy,
+            is_maker,
+            is_best_match: true,
+        });
+        if let Some(book) = self.books.get_mut(&order.symbol) {
+            book.last_price = Some(price);
+        }
+        let execution = Execution {
+            qty,
+            price,
+            commission,
+            commission_asset,
+            trade_id: trade_id as i64,
+            is_maker,
+        };
+        let step = self.step_size(&order.symbol);
+        if order.remaining_at(price, step) <= Decimal::ZERO {
+            // Market orders by quote quantity report the quantity they filled
+            order.qty = order.qty.max(order.executed_qty);
+            order.status = OrderStatus::Filled;
+            self.release(order);
+        }
+        self.emit_order(order, ExecutionType::Trade, Some(execution));
+    }
+
+    /// Moves an order to a final status, releasing its locked funds
+    fn finish(&mut self, order: &mut PaperOrder, status: OrderStatus) {
+        if order.status == status {
+            return;
+        }
+        let execution_type = match status {
+            OrderStatus::Canceled => ExecutionType::Canceled,
+            OrderStatus::Filled => ExecutionType::Trade,
+            _ => ExecutionType::Expired,
+        };
+        order.status = status;
+        order.update_time = self.now();
+        self.release(order);
+        if !matches!(execution_type, ExecutionType::Trade) {
+            self.emit_order(order, execution_type, None);
+        }
+    }
+
+    fn release(&mut self, order: &mut PaperOrder) {
+        if order.locked.is_zero() {
+            return;
+        }
+        let (base_asset, quote_asset) = self.assets(&order.symbol);
+        let asset = match order.side {
+            OrderSide::Buy => quote_asset,
+            OrderSide::Sell => base_asset,
+        };
+        let balance = self.balances.entry(asset).or_default();
+        balance.locked -= order.locked;
+        balance.free += order.locked;
+        order.locked = Decimal::ZERO;
+    }
+
+    fn cancel(&mut self, o: OrderCancellation) -> Result<OrderCanceled> {
+        let order_id = self
+            .find(&o.symbol, o.order_id, o.orig_client_order_id.as_deref())
+            .filter(|id| !self.orders[id].is_final())
+            .ok_or_else(|| binance_error(CANCEL_REJECTED, "Unknown order sent."))?;
+        let Some(mut order) = self.orders.remove(&order_id) else {
+            return Err(binance_error(CANCEL_REJECTED, "Unknown order sent."));
+        };
+        self.finish(&mut order, OrderStatus::Canceled);
+        let (base_asset, quote_asset) = self.assets(&order.symbol);
+        self.emit_positions(&[base_asset, quote_asset]);
+        let canceled = OrderCanceled {
+            symbol: order.symbol.clone(),
+            orig_client_order_id: order.client_order_id.clone(),
+            order_id,
+            client_order_id: o
+                .new_client_order_id
+                .unwrap_or_else(|| format!("paper-cancel-{order_id}")),
+        };
+        self.orders.insert(order_id, order);
+        Ok(canceled)
+    }
+
+    /// Triggers the stop orders and fills the resting orders the trade went through
+    fn on_trade(&mut self, symbol: &str, price: Decimal, qty: Decimal) {
+        self.books.entry(symbol.to_string()).or_default().last_price = Some(price);
+        let (base_asset, quote_asset) = self.assets(symbol);
+        let triggered: Vec<u64> = self
+            .orders
+            .values()
+            .filter(|o| o.symbol == symbol && !o.is_final() && !o.working && o.triggers(price))
+            .map(|o| o.order_id)
+            .collect();
+        for order_id in triggered {
+            if let Some(mut order) = self.orders.remove(&order_id) {
+                order.working = true;
+                self.work(&mut order);
+                self.orders.insert(order_id, order);
+            }
+        }
+
+        // Resting orders fill at their price when the market trades through it, best prices first
+        let mut through: Vec<&PaperOrder> = self
+            .orders
+            .values()
+            .filter(|o| o.symbol == symbol && !o.is_final() && o.working && !o.is_market())
+            .filter(|o| match o.side {
+                OrderSide::Buy => o.price > price,
+                OrderSide::Sell => o.price < price,
+            })
+            .collect();
+        through.sort_by(|a, b| match a.side {
+            OrderSide::Buy => b.price.cmp(&a.price),
+            OrderSide::Sell => a.price.cmp(&b.price),
+        });
+        let through: Vec<u64> = through.iter().map(|o| o.order_id).collect();
+        let step = self.step_size(symbol);
+        let mut traded = qty;
+        for order_id in through {
+            if traded <= Decimal::ZERO {
+                break;
+            }
+            if let Some(mut order) = self.orders.remove(&order_id) {
+                let fill_qty = order.remaining_at(order.price, step).min(traded);
+                if fill_qty > Decimal::ZERO {
+                    traded -= fill_qty;
+                    let price = order.price;
+                    self.fill(&mut order, price, fill_qty, true);
+                }
+                self.orders.insert(order_id, order);
+            }
+        }
+        self.emit_positions(&[base_asset, quote_asset]);
+    }
+
+    /// Fills the resting orders crossed by the opposite side of the book
+    fn match_resting(&mut self, symbol: &str) {
+        let resting: Vec<u64> = self
+            .orders
+            .values()
+            .filter(|o| o.symbol == symbol && !o.is_final() && o.working && !o.is_market())
+            .map(|o| o.order_id)
+            .collect();
+        let mut filled = false;
+        for order_id in resting {
+            if let Some(mut order) = self.orders.remove(&order_id) {
+                let executed_qty = order.executed_qty;
+                let price = order.price;
+                self.take(&mut order, Some(price), true);
+                filled |= order.executed_qty > executed_qty;
+                self.orders.insert(order_id, order);
+            }
+        }
+        if filled {
+            let (base_asset, quote_asset) = self.assets(symbol);
+            self.emit_positions(&[base_asset, quote_asset]);
+        }
+    }
+
+    fn emit_order(&mut self, order: &PaperOrder, execution_type: ExecutionType, execution: Option<Execution>) {
+        let now = self.now();
+        let execution = execution.unwrap_or(Execution {
+            qty: Decimal::ZERO,
+            price: Decimal::ZERO,
+            commission: Decimal::ZERO,
+            commission_asset: String::new(),
+            trade_id: -1,
+            is_maker: false,
+        });
+        let update = OrderUpdate {
+            event_time: now,
+            symbol: order.symbol.clone(),
+            client_order_id: Some(order.client_order_id.clone()),
+            side: order.side.clone(),
+            order_type: order.order_type.clone(),
+            time_in_force: order.time_in_force.clone(),
+            qty: order.qty,
+            price: order.price,
+            stop_price: order.stop_price,
+            iceberg_qty: Decimal::ZERO,
+            order_list_id: -1,
+            origin_client_id: Some(String::new()),
+            execution_type,
+            current_order_status: order.status.clone(),
+            order_reject_reason: "NONE".to_string(),
+            order_id: order.order_id,
+            qty_last_executed: execution.qty,
+            cumulative_filled_qty: order.executed_qty,
+            last_executed_price: execution.price,
+            commission: execution.commission,
+            commission_asset: Some(execution.commission_asset).filter(|a| !a.is_empty()),
+            trade_order_time: now,
+            trade_id: execution.trade_id,
+            i_ignore: 0,
+            is_order_on_the_book: order.working && !order.is_final() && !order.is_market(),
+            is_buyer_maker: execution.is_maker,
+            m_ignore: false,
+            order_creation_time: order.time,
+            cumulative_quote_asset_transacted_qty: order.cumulative_quote_qty,
+            last_quote_asset_transacted_qty: execution.qty * execution.price,
+            quote_order_qty: order.quote_order_qty,
+            trailing_delta: None,
+            trailing_time: None,
+            strategy_id: None,
+            strategy_type: None,
+            self_trade_prevention_mode: None,
+            prevented_match_id: None,
+            prevented_qty: None,
+            last_prevented_qty: None,
+            trade_group_id: None,
+            counter_order_id: None,
+            counter_symbol: None,
+            working_time: order.working.then_some(order.update_time),
+        };
+        self.events.push(WebsocketEvent::OrderUpdate(Box::new(update)));
+    }
+
+    fn emit_positions(&mut self, assets: &[String]) {
+        let now = self.now();
+        let balances = assets
+            .iter()
+            .filter(|asset| !asset.is_empty())
+            .map(|asset| {
+                let balance = self.balances.get(asset).cloned().unwrap_or_default();
+                EventBalance {
+                    asset: asset.clone(),
+                    free: balance.free,
+                    locked: balance.locked,
+                }
+            })
+            .collect();
+        self.events
+            .push(WebsocketEvent::AccountPositionUpdate(Box::new(AccountPositionUpdate {
+                event_time: now,
+                last_update_time: now,
+                balances,
+            })));
+    }
+}
+
+fn check_lot(qty: Option<Decimal>, min_qty: Decimal, max_qty: Decimal, step_size: Decimal, filter: &str) -> Result<()> {
+    let Some(qty) = qty else {
+        return Ok(());
+    };
+    if (!min_qty.is_zero() && qty < min_qty)
+        || (!max_qty.is_zero() && qty > max_qty)
+        || (!step_size.is_zero() && !((qty - min_qty) % step_size).is_zero())
+    {
+        return Err(filter_failure(filter));
+    }
+    Ok(())
+}
+
+fn notional(order: &OrderRequest, reference_price: Option<Decimal>) -> Option<Decimal> {
+    match (order.quantity, order.quote_order_qty) {
+        (Some(qty), _) => reference_price.map(|price| qty * price),
+        (None, quote_order_qty) => quote_order_qty,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    const SYMBOL: &str = "BTCUSDT";
+
+    fn account() -> PaperAccount {
+        let symbol: Symbol = serde_json::from_str(
+            r#"{
+                "symbol": "BTCUSDT", "status": "TRADING", "baseAsset": "BTC", "baseAssetPrecision": 8,
+                "quoteAsset": "USDT", "quotePrecision": 8, "quoteAssetPrecision": 8,
+                "baseCommissionPrecision": 8, "quoteCommissionPrecision": 8,
+                "orderTypes": ["LIMIT", "LIMIT_MAKER", "MARKET", "STOP_LOSS", "STOP_LOSS_LIMIT"],
+                "icebergAllowed": true, "ocoAllowed": true, "quoteOrderQtyMarketAllowed": true,
+                "isSpotTradingAllowed": true, "isMarginTradingAllowed": false,
+                "filters": [
+                    {"filterType": "PRICE_FILTER", "minPrice": "0.01", "maxPrice": "1000000.00", "tickSize": "0.01"},
+                    {"filterType": "LOT_SIZE", "minQty": "0.001", "maxQty": "9000.000", "stepSize": "0.001"}
+                ],
+                "permissions": ["SPOT"],
+                "defaultSelfTradePreventionMode": "NONE"
+            }"#,
+        )
+        .unwrap();
+        PaperAccount::new(vec![symbol])
+    }
+
+    fn book(bids: &str, asks: &str) -> OrderBook {
+        serde_json::from_str(&format!(r#"{{"lastUpdateId": 1, "bids": [{bids}], "asks": [{asks}]}}"#)).unwrap()
+    }
+
+    fn trade(time: u64, price: &str, qty: &str) -> TradeEvent {
+        serde_json::from_str(&format!(
+            r#"{{"e": "trade", "E": {time}, "s": "BTCUSDT", "t": {time}, "p": "{price}", "q": "{qty}", "b": 1, "a": 2, "T": {time}, "m": true, "M": true}}"#
+        ))
+        .unwrap()
+    }
+
+    fn depth(time: u64, asks: &str) -> DepthOrderBookEvent {
+        serde_json::from_str(&format!(
+            r#"{{"e": "depthUpdate", "E": {time}, "s": "BTCUSDT", "U": {time}, "u": {time}, "b": [], "a": [{asks}]}}"#
+        ))
+        .unwrap()
+    }
+
+    fn balance(account: &PaperAccount, asset: &str) -> (Decimal, Decimal) {
+        let balance = tokio_test::block_on(account.get_balance(asset)).unwrap();
+        (balance.free, balance.locked)
+    }
+
+    fn status(account: &PaperAccount, order_id: u64) -> Order {
+        tokio_test::block_on(account.order_status(OrderStatusRequest {
+            symbol: SYMBOL.to_string(),
+            order_id: Some(order_id),
+            ..OrderStatusRequest::default()
+        }))
+        .unwrap()
+    }
+
+    fn cancel(account: &PaperAccount, order_id: u64) -> Result<OrderCanceled> {
+        tokio_test::block_on(account.cancel_order(OrderCancellation {
+            symbol: SYMBOL.to_string(),
+            order_id: Some(order_id),
+            ..OrderCancellation::default()
+        }))
+    }
+
+    fn is_rejected(result: Result<Transaction>) -> bool {
+        matches!(result, Err(Error::BinanceError { response }) if response.code == NEW_ORDER_REJECTED)
+    }
+
+    #[test]
+    fn resting_orders_lock_funds_until_canceled() {
+        let account = account();
+        account.deposit("USDT", dec!(1000));
+        account.apply_order_book(SYMBOL, &book(r#"["99", "1"]"#, r#"["101", "1"]"#));
+
+        let order = OrderRequest::limit(SYMBOL, OrderSide::Buy, dec!(2), dec!(100));
+        let transaction = tokio_test::block_on(account.place_order(order)).unwrap();
+        assert_eq!(transaction.status, OrderStatus::New);
+        assert_eq!(balance(&account, "USDT"), (dec!(800), dec!(200)));
+        let too_large = OrderRequest::limit(SYMBOL, OrderSide::Buy, dec!(9), dec!(100));
+        assert!(is_rejected(tokio_test::block_on(account.place_order(too_large))));
+
+        cancel(&account, transaction.order_id).unwrap();
+        assert_eq!(status(&account, transaction.order_id).status, OrderStatus::Canceled);
+        assert_eq!(balance(&account, "USDT"), (dec!(1000), dec!(0)));
+        assert!(cancel(&account, transaction.order_id).is_err());
+        assert!(tokio_test::block_on(account.get_open_orders(SYMBOL))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn limit_orders_take_then_fill_as_makers() {
+        let account = account().with_fees(Fees {
+            maker: dec!(0),
+            taker: dec!(0.001),
+        });
+        account.deposit("USDT", dec!(1000));
+        account.apply_order_book(SYMBOL, &book(r#"["99", "1"]"#, r#"["100", "1"], ["101", "1"]"#));
+
+        let order = OrderRequest::limit(SYMBOL, OrderSide::Buy, dec!(3), dec!(100.5));
+        let transaction = tokio_test::block_on(account.place_order(order)).unwrap();
+        let order_id = transaction.order_id;
+        assert_eq!(transaction.status, OrderStatus::PartiallyFilled);
+        assert_eq!(transaction.executed_qty, dec!(1));
+        assert_eq!(transaction.fills[0].price, dec!(100));
+        assert_eq!(transaction.fills[0].commission, dec!(0.001));
+        // The taker fill paid 100 of the 100.5 locked for it
+        assert_eq!(balance(&account, "USDT"), (dec!(699), dec!(201)));
+        assert_eq!(balance(&account, "BTC"), (dec!(0.999), dec!(0)));
+
+        // A trade below the resting price fills it at its own price, up to the traded quantity
+        account.apply_trade(&trade(2, "100", "0.5")).unwrap();
+        let order = status(&account, order_id);
+        assert_eq!(order.status, OrderStatus::PartiallyFilled);
+        assert_eq!(order.executed_qty, dec!(1.5));
+
+        // An ask crossing the resting price fills the rest
+        account.apply_depth(&depth(3, r#"["100.4", "5"]"#));
+        let order = status(&account, order_id);
+        assert_eq!(order.status, OrderStatus::Filled);
+        assert_eq!(order.cummulative_quote_qty, dec!(100) + dec!(2) * dec!(100.5));
+        assert_eq!(balance(&account, "USDT"), (dec!(699), dec!(0)));
+        assert_eq!(balance(&account, "BTC"), (dec!(2.999), dec!(0)));
+        let trades = tokio_test::block_on(account.trade_history(SYMBOL)).unwrap();
+        assert_eq!(trades.len(), 3);
+        assert!(!trades[0].is_maker && trades[1].is_maker && trades[2].is_maker);
+    }
+
+    #[test]
+    fn limit_makers_and_fill_or_kill_orders_do_not_rest_when_crossing() {
+        let account = account();
+        account.deposit("USDT", dec!(1000));
+        account.apply_order_book(SYMBOL, &book(r#"["99", "1"]"#, r#"["100", "1"]"#));
+
+        let maker = OrderRequest::limit_maker(SYMBOL, OrderSide::Buy, dec!(1), dec!(100));
+        assert!(is_rejected(tokio_test::block_on(account.place_order(maker))));
+        let fok = OrderRequest {
+            time_in_force: Some(TimeInForce::FOK),
+            ..OrderRequest::limit(SYMBOL, OrderSide::Buy, dec!(2), dec!(100))
+        };
+        let transaction = tokio_test::block_on(account.place_order(fok)).unwrap();
+        assert_eq!(transaction.status, OrderStatus::Expired);
+        assert_eq!(transaction.executed_qty, dec!(0));
+        assert_eq!(balance(&account, "USDT"), (dec!(1000), dec!(0)));
+    }
+
+    #[test]
+    fn market_buys_must_afford_their_estimated_cost() {
+        let account = account().with_fees(Fees {
+            maker: dec!(0),
+            taker: dec!(0),
+        });
+        account.deposit("USDT", dec!(120));
+        account.apply_order_book(SYMBOL, &book(r#"["99", "1"]"#, r#"["100", "1"], ["101", "1"]"#));
+
+        // 1 at 100 and 0.5 at 101
+        let order = OrderRequest::market(SYMBOL, OrderSide::Buy, dec!(1.5));
+        assert!(is_rejected(tokio_test::block_on(account.place_order(order.clone()))));
+        // Beyond the book, the rest is priced at the last level
+        let beyond = OrderRequest::market(SYMBOL, OrderSide::Buy, dec!(4));
+        account.deposit("USDT", dec!(200));
+        assert!(is_rejected(tokio_test::block_on(account.place_order(beyond))));
+
+        let transaction = tokio_test::block_on(account.place_order(order)).unwrap();
+        assert_eq!(transaction.status, OrderStatus::Filled);
+        assert_eq!(transaction.cummulative_quote_qty, dec!(150.5));
+        assert_eq!(transaction.fills.len(), 2);
+        assert_eq!(balance(&account, "USDT"), (dec!(169.5), dec!(0)));
+        assert_eq!(balance(&account, "BTC"), (dec!(1.5), dec!(0)));
+
+        // Market orders by quote quantity report the base quantity they filled
+        let by_quote = OrderRequest::market_quote(SYMBOL, OrderSide::Buy, dec!(50.5));
+        let transaction = tokio_test::block_on(account.place_order(by_quote)).unwrap();
+        assert_eq!(transaction.status, OrderStatus::Filled);
+        assert_eq!(transaction.orig_qty, dec!(0.5));
+    }
+
+    #[test]
+    fn market_orders_expire_when_the_book_runs_out() {
+        let account = account();
+        account.deposit("BTC", dec!(2));
+        account.apply_order_book(SYMBOL, &book(r#"["99", "1"]"#, r#"["101", "1"]"#));
+
+        let order = OrderRequest::market(SYMBOL, OrderSide::Sell, dec!(2));
+        let transaction = tokio_test::block_on(account.place_order(order)).unwrap();
+        assert_eq!(transaction.status, OrderStatus::Expired);
+        assert_eq!(transaction.executed_qty, dec!(1));
+        assert_eq!(balance(&account, "BTC"), (dec!(1), dec!(0)));
+        assert_eq!(balance(&account, "USDT"), (dec!(99) - dec!(0.099), dec!(0)));
+    }
+
+    #[test]
+    fn stop_orders_work_once_triggered() {
+        let account = account();
+        account.deposit("BTC", dec!(1));
+        account.apply_order_book(SYMBOL, &book(r#"["94", "2"]"#, r#"["101", "1"]"#));
+        account.apply_trade(&trade(1, "100", "1")).unwrap();
+
+        let immediate = OrderRequest::stop_loss(SYMBOL, OrderSide::Sell, dec!(1), dec!(101));
+        assert!(is_rejected(tokio_test::block_on(account.place_order(immediate))));
+        let order = OrderRequest::stop_loss(SYMBOL, OrderSide::Sell, dec!(1), dec!(95));
+        let transaction = tokio_test::block_on(account.place_order(order)).unwrap();
+        assert_eq!(transaction.status, OrderStatus::New);
+        assert!(!status(&account, transaction.order_id).is_working);
+        assert_eq!(balance(&account, "BTC"), (dec!(0), dec!(1)));
+
+        account.apply_trade(&trade(2, "96", "1")).unwrap();
+        assert_eq!(status(&account, transaction.order_id).status, OrderStatus::New);
+        account.apply_trade(&trade(3, "95", "1")).unwrap();
+        let order = status(&account, transaction.order_id);
+        assert_eq!(order.status, OrderStatus::Filled);
+        assert_eq!(order.cummulative_quote_qty, dec!(94));
+        assert_eq!(balance(&account, "BTC"), (dec!(0), dec!(0)));
+
+        let events = account.take_events();
+        let fills = events
+            .iter()
+            .filter(|e| matches!(e, WebsocketEvent::OrderUpdate(u) if matches!(u.execution_type, ExecutionType::Trade)))
+            .count();
+        assert_eq!(fills, 1);
+    }
+}
@@ -0,0 +1,635 @@
+//! Profit and loss accounting over spot trade history.
+//!
+//! A [`SymbolLedger`] replays the trades of a symbol from [`Account::trade_history_paginated`]
+//! and matches sells against the lots acquired by buys, first in first out, last in first out or
+//! at average cost. Commissions are included in the cost basis of buys and deducted from the
+//! proceeds of sells. Commissions paid in a third asset, typically BNB, are converted with the prices given by
+//! [`SymbolLedger::set_commission_price`], and kept apart while no price is known. Setting a price
+//! after the trades were applied replays them, so that the PnL never silently leaves out a
+//! commission which can be converted.
+//!
+//! [`Portfolio`] groups the ledgers of several symbols, reports realized and unrealized PnL against
+//! the latest prices and exports the tax lots as CSV.
+
+use std::collections::{HashMap, VecDeque};
+use std::io::Write;
+use std::str::FromStr;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use rust_decimal::Decimal;
+
+use crate::account::Account;
+use crate::errors::*;
+use crate::market::Market;
+use crate::rest_model::{Prices, TradeHistory};
+
+/// How sells are matched against the lots acquired by buys
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CostBasisMethod {
+    /// The oldest lots are sold first
+    #[default]
+    Fifo,
+    /// The newest lots are sold first
+    Lifo,
+    /// All lots are merged at their average cost
+    AverageCost,
+}
+
+/// Quantity acquired by a buy and not sold yet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaxLot {
+    /// None for the merged lot of the average cost method
+    pub trade_id: Option<u64>,
+    pub acquired_time: u64,
+    pub qty: Decimal,
+    /// Total cost in quote asset, commissions included
+    pub cost: Decimal,
+}
+
+impl TaxLot {
+    pub fn unit_cost(&self) -> Decimal {
+        if self.qty.is_zero() {
+            Decimal::ZERO
+        } else {
+            self.cost / self.qty
+        }
+    }
+}
+
+/// Quantity of a lot closed by a sell
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disposal {
+    pub symbol: String,
+    /// None when the lot was merged at average cost or the sell exceeded the known holdings
+    pub buy_trade_id: Option<u64>,
+    pub sell_trade_id: u64,
+    /// None when the sell exceeded the known holdings
+    pub acquired_time: Option<u64>,
+    pub disposed_time: u64,
+    pub qty: Decimal,
+    /// Cost in quote asset, commissions included
+    pub cost_basis: Decimal,
+    /// Proceeds in quote asset, net of commissions
+    pub proceeds: Decimal,
+}
+
+impl Disposal {
+    pub fn realized_pnl(&self) -> Decimal { self.proceeds - self.cost_basis }
+}
+
+/// Realized and unrealized PnL of a symbol, in its quote asset
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PnlReport {
+    pub symbol: String,
+    pub quote_asset: String,
+    pub held_qty: Decimal,
+    pub open_cost: Decimal,
+    /// None when no price is known for the symbol
+    pub market_value: Option<Decimal>,
+    pub realized_pnl: Decimal,
+    pub unrealized_pnl: Option<Decimal>,
+    /// Commissions paid per asset
+    pub commissions: HashMap<String, Decimal>,
+    /// Commissions which could not be converted to the quote asset and are not in the PnL
+    pub unconverted_commissions: HashMap<String, Decimal>,
+}
+
+/// Cost basis accounting of a single symbol
+#[derive(Debug, Clone)]
+pub struct SymbolLedger {
+    pub symbol: String,
+    pub base_asset: String,
+    pub quote_asset: String,
+    method: CostBasisMethod,
+    lots: VecDeque<TaxLot>,
+    disposals: Vec<Disposal>,
+    commissions: HashMap<String, Decimal>,
+    unconverted_commissions: HashMap<String, Decimal>,
+    commission_prices: HashMap<String, Decimal>,
+    /// Applied trades with their parsed commission, replayed when a commission price changes
+    applied: Vec<(TradeHistory, Decimal)>,
+    last_trade_id: Option<u64>,
+}
+
+impl SymbolLedger {
+    pub fn new<S: Into<String>>(symbol: S, base_asset: S, quote_asset: S, method: CostBasisMethod) -> Self {
+        Self {
+            symbol: symbol.into(),
+            base_asset: base_asset.into(),
+            quote_asset: quote_asset.into(),
+            method,
+            lots: VecDeque::new(),
+            disposals: vec![],
+            commissions: HashMap::new(),
+            unconverted_commissions: HashMap::new(),
+            commission_prices: HashMap::new(),
+            applied: vec![],
+            last_trade_id: None,
+        }
+    }
+
+    /// Sets the price in quote asset used to convert commissions paid in another asset, such as BNB.
+    /// The trades already applied are replayed if they paid commissions in this asset
+    pub fn set_commission_price<S: Into<String>>(&mut self, asset: S, price: Decimal) {
+        let asset = asset.into();
+        let changed = self.commission_prices.insert(asset.clone(), price) != Some(price);
+        if changed && asset != self.base_asset && asset != self.quote_asset && self.commissions.contains_key(&asset) {
+            self.replay();
+        }
+    }
+
+    /// Applies trades in chronological order, trades already applied are skipped
+    pub fn apply_trades(&mut self, trades: &[TradeHistory]) -> Result<()> {
+        let mut trades: Vec<&TradeHistory> = trades.iter().collect();
+        trades.sort_by_key(|t| (t.time, t.id));
+        for trade in trades {
+            if self.last_trade_id < Some(trade.id) {
+                self.apply_trade(trade)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies a single trade
+    pub fn apply_trade(&mut self, trade: &TradeHistory) -> Result<()> {
+        let commission = Decimal::from_str(&trade.commission).map_err(|e| {
+            Error::Msg(format!(
+                "invalid commission {} of trade {}: {}",
+                trade.commission, trade.id, e
+            ))
+        })?;
+        self.book(trade, commission);
+        self.applied.push((trade.clone(), commission));
+        Ok(())
+    }
+
+    /// Lots still held
+    pub fn open_lots(&self) -> impl Iterator<Item = &TaxLot> { self.lots.iter() }
+
+    /// Lots closed by sells
+    pub fn disposals(&self) -> &[Disposal] { &self.disposals }
+
+    pub fn held_qty(&self) -> Decimal { self.lots.iter().map(|l| l.qty).sum() }
+
+    pub fn open_cost(&self) -> Decimal { self.lots.iter().map(|l| l.cost).sum() }
+
+    pub fn realized_pnl(&self) -> Decimal { self.disposals.iter().map(Disposal::realized_pnl).sum() }
+
+    pub fn unrealized_pnl(&self, price: Decimal) -> Decimal { self.held_qty() * price - self.open_cost() }
+
+    /// Commissions which could not be converted to the quote asset and are not in the PnL
+    pub fn unconverted_commissions(&self) -> &HashMap<String, Decimal> { &self.unconverted_commissions }
+
+    /// Reports the PnL, marking the held quantity at the price if known
+    pub fn report(&self, price: Option<Decimal>) -> PnlReport {
+        PnlReport {
+            symbol: self.symbol.clone(),
+            quote_asset: self.quote_asset.clone(),
+            held_qty: self.held_qty(),
+            open_cost: self.open_cost(),
+            market_value: price.map(|p| self.held_qty() * p),
+            realized_pnl: self.realized_pnl(),
+            unrealized_pnl: price.map(|p| self.unrealized_pnl(p)),
+            commissions: self.commissions.clone(),
+            unconverted_commissions: self.unconverted_commissions.clone(),
+        }
+    }
+
+    fn book(&mut self, trade: &TradeHistory, commission: Decimal) {
+        *self.commissions.entry(trade.commission_asset.clone()).or_default() += commission;
+        let notional = trade.qty * trade.price;
+        if trade.is_buyer {
+            let (qty, cost) = if trade.commission_asset == self.base_asset {
+                (trade.qty - commission, notional)
+            } else {
+                (
+                    trade.qty,
+                    notional + self.commission_in_quote(&trade.commission_asset, commission),
+                )
+            };
+            self.acquire(TaxLot {
+                trade_id: Some(trade.id),
+                acquired_time: trade.time,
+                qty,
+                cost,
+            });
+        } else {
+            let (qty, proceeds) = if trade.commission_asset == self.base_asset {
+                (trade.qty + commission, notional)
+            } else {
+                (
+                    trade.qty,
+                    notional - self.commission_in_quote(&trade.commission_asset, commission),
+                )
+            };
+            self.dispose(trade, qty, proceeds);
+        }
+        self.last_trade_id = Some(trade.id);
+    }
+
+    /// Books the applied trades again from an empty ledger
+    fn replay(&mut self) {
+        self.lots.clear();
+        self.disposals.clear();
+        self.commissions.clear();
+        self.unconverted_commissions.clear();
+        for (trade, commission) in std::mem::take(&mut self.applied) {
+            self.book(&trade, commission);
+            self.applied.push((trade, commission));
+        }
+    }
+
+    fn commission_in_quote(&mut self, asset: &str, commission: Decimal) -> Decimal {
+        if asset == self.quote_asset {
+            return commission;
+        }
+        match self.commission_prices.get(asset) {
+            Some(price) => commission * price,
+            None => {
+                *self.unconverted_commissions.entry(asset.to_string()).or_default() += commission;
+                Decimal::ZERO
+            }
+        }
+    }
+
+    fn acquire(&mut self, lot: TaxLot) {
+        match (self.method, self.lots.front_mut()) {
+            (CostBasisMethod::AverageCost, Some(merged)) => {
+                merged.qty += lot.qty;
+                merged.cost += lot.cost;
+            }
+            (CostBasisMethod::AverageCost, None) => self.lots.push_back(TaxLot { trade_id: None, ..lot }),
+            _ => self.lots.push_back(lot),
+        }
+    }
+
+    fn dispose(&mut self, trade: &TradeHistory, qty: Decimal, proceeds: Decimal) {
+        let mut remaining = qty;
+        while remaining > Decimal::ZERO {
+            let lot = match self.method {
+                CostBasisMethod::Lifo => self.lots.back_mut(),
+                _ => self.lots.front_mut(),
+            };
+            let Some(lot) = lot else {
+                break;
+            };
+            let taken = remaining.min(lot.qty);
+            let cost_basis = if taken == lot.qty {
+                lot.cost
+            } else {
+                lot.cost * taken / lot.qty
+            };
+            lot.qty -= taken;
+            lot.cost -= cost_basis;
+            let disposal = Disposal {
+                symbol: self.symbol.clone(),
+                buy_trade_id: lot.trade_id,
+                sell_trade_id: trade.id,
+                acquired_time: Some(lot.acquired_time),
+                disposed_time: trade.time,
+                qty: taken,
+                cost_basis,
+                proceeds: proceeds * taken / qty,
+            };
+            if lot.qty.is_zero() {
+                match self.method {
+                    CostBasisMethod::Lifo => self.lots.pop_back(),
+                    _ => self.lots.pop_front(),
+                };
+            }
+            self.disposals.push(disposal);
+            remaining -= taken;
+        }
+        if remaining > Decimal::ZERO {
+            // The history does not cover the acquisition of what was sold
+            self.disposals.push(Disposal {
+                symbol: self.symbol.clone(),
+                buy_trade_id: None,
+                sell_trade_id: trade.id,
+                acquired_time: None,
+                disposed_time: trade.time,
+                qty: remaining,
+                cost_basis: Decimal::ZERO,
+                proceeds: proceeds * remaining / qty,
+            });
+        }
+    }
+}
+
+/// PnL accounting over several symbols
+#[derive(Debug, Clone, Default)]
+pub struct Portfolio {
+    method: CostBasisMethod,
+    ledgers: HashMap<String, SymbolLedger>,
+}
+
+impl Portfolio {
+    pub fn new(method: CostBasisMethod) -> Self {
+        Self {
+            method,
+            ledgers: HashMap::new(),
+        }
+    }
+
+    /// Returns the ledger of a symbol, creating it if needed
+    pub fn ledger_mut(&mut self, symbol: &str, base_asset: &str, quote_asset: &str) -> &mut SymbolLedger {
+        let method = self.method;
+        self.ledgers
+            .entry(symbol.to_string())
+            .or_insert_with(|| SymbolLedger::new(symbol, base_asset, quote_asset, method))
+    }
+
+    pub fn ledger(&self, symbol: &str) -> Option<&SymbolLedger> { self.ledgers.get(symbol) }
+
+    pub fn ledgers(&self) -> impl Iterator<Item = &SymbolLedger> { self.ledgers.values() }
+
+    /// Applies the trades of a symbol
+    pub fn apply_trades(
+        &mut self,
+        symbol: &str,
+        base_asset: &str,
+        quote_asset: &str,
+        trades: &[TradeHistory],
+    ) -> Result<()> {
+        self.ledger_mut(symbol, base_asset, quote_asset).apply_trades(trades)
+    }
+
+    /// Fetches and applies the whole trade history of a symbol
+    pub async fn load_trades(
+        &mut self,
+        account: &Account,
+        symbol: &str,
+        base_asset: &str,
+        quote_asset: &str,
+    ) -> Result<()> {
+        let trades = account.trade_history_paginated(symbol).await?;
+        self.apply_trades(symbol, base_asset, quote_asset, &trades)
+    }
+
+    /// Reports the PnL of every symbol against the prices
+    pub fn report(&self, prices: &Prices) -> Vec<PnlReport> {
+        let Prices::AllPrices(prices) = prices;
+        let prices: HashMap<&str, Decimal> = prices.iter().map(|p| (p.symbol.as_str(), p.price)).collect();
+        let mut reports: Vec<PnlReport> = self
+            .ledgers
+            .values()
+            .map(|ledger| ledger.report(prices.get(ledger.symbol.as_str()).copied()))
+            .collect();
+        reports.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        reports
+    }
+
+    /// Reports the PnL of every symbol against the latest prices
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, account::*, config::*, market::*, pnl::*};
+    /// let account: Account = Binance::new_with_env(&Config::testnet());
+    /// let market: Market = Binance::new_with_env(&Config::testnet());
+    /// let mut portfolio = Portfolio::new(CostBasisMethod::Fifo);
+    /// tokio_test::block_on(portfolio.load_trades(&account, "BTCUSDT", "BTC", "USDT")).unwrap();
+    /// let reports = tokio_test::block_on(portfolio.report_with_market(&market));
+    /// assert!(reports.is_ok(), "{:?}", reports);
+    /// ```
+    pub async fn report_with_market(&self, market: &Market) -> Result<Vec<PnlReport>> {
+        let prices = market.get_all_prices().await?;
+        Ok(self.report(&prices))
+    }
+
+    /// Writes the closed lots of every symbol as CSV, in disposal order
+    pub fn write_disposals_csv<W: Write>(&self, mut writer: W) -> Result<()> {
+        writeln!(
+            writer,
+            "symbol,buy_trade_id,sell_trade_id,acquired_time,disposed_time,qty,cost_basis,proceeds,realized_pnl"
+        )?;
+        let mut disposals: Vec<&Disposal> = self.ledgers.values().flat_map(|l| l.disposals.iter()).collect();
+        disposals.sort_by(|a, b| (a.disposed_time, &a.symbol).cmp(&(b.disposed_time, &b.symbol)));
+        for d in disposals {
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{},{},{}",
+                d.symbol,
+                optional(d.buy_trade_id),
+                d.sell_trade_id,
+                d.acquired_time.map(format_time).unwrap_or_default(),
+                format_time(d.disposed_time),
+                d.qty.normalize(),
+                d.cost_basis.normalize(),
+                d.proceeds.normalize(),
+                d.realized_pnl().normalize()
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Writes the open lots of every symbol as CSV
+    pub fn write_open_lots_csv<W: Write>(&self, mut writer: W) -> Result<()> {
+        writeln!(writer, "symbol,trade_id,acquired_time,qty,cost,unit_cost")?;
+        let mut symbols: Vec<&String> = self.ledgers.keys().collect();
+        symbols.sort();
+        for symbol in symbols {
+            for lot in self.ledgers[symbol].open_lots() {
+                writeln!(
+                    writer,
+                    "{},{},{},{},{},{}",
+                    symbol,
+                    optional(lot.trade_id),
+                    format_time(lot.acquired_time),
+                    lot.qty.normalize(),
+                    lot.cost.normalize(),
+                    lot.unit_cost().normalize()
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn optional(value: Option<u64>) -> String { value.map(|v| v.to_string()).unwrap_or_default() }
+
+fn format_time(millis: u64) -> String {
+    DateTime::<Utc>::from_timestamp_millis(millis as i64)
+        .map(|t| t.to_rfc3339_opts(SecondsFormat::Millis, true))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    const T0: u64 = 1_700_000_000_000;
+
+    fn trade(id: u64, is_buyer: bool, qty: Decimal, price: Decimal, commission: &str, asset: &str) -> TradeHistory {
+        TradeHistory {
+            id,
+            price,
+            qty,
+            commission: commission.to_string(),
+            commission_asset: asset.to_string(),
+            time: T0 + id * 1000,
+            is_buyer,
+            is_maker: false,
+            is_best_match: true,
+        }
+    }
+
+    fn ledger(method: CostBasisMethod) -> SymbolLedger {
+        let mut ledger = SymbolLedger::new("BTCUSDT", "BTC", "USDT", method);
+        ledger
+            .apply_trades(&[
+                trade(3, false, dec!(1), dec!(300), "0", "USDT"),
+                trade(1, true, dec!(1), dec!(100), "0", "USDT"),
+                trade(2, true, dec!(1), dec!(200), "0", "USDT"),
+            ])
+            .unwrap();
+        ledger
+    }
+
+    #[test]
+    fn fifo() {
+        let ledger = ledger(CostBasisMethod::Fifo);
+        assert_eq!(ledger.disposals()[0].buy_trade_id, Some(1));
+        assert_eq!(ledger.realized_pnl(), dec!(200));
+        let lots: Vec<&TaxLot> = ledger.open_lots().collect();
+        assert_eq!(lots[0].trade_id, Some(2));
+        assert_eq!(ledger.unrealized_pnl(dec!(250)), dec!(50));
+    }
+
+    #[test]
+    fn lifo() {
+        let ledger = ledger(CostBasisMethod::Lifo);
+        assert_eq!(ledger.disposals()[0].buy_trade_id, Some(2));
+        assert_eq!(ledger.realized_pnl(), dec!(100));
+        assert_eq!(ledger.open_lots().next().unwrap().trade_id, Some(1));
+        assert_eq!(ledger.unrealized_pnl(dec!(250)), dec!(150));
+    }
+
+    #[test]
+    fn average_cost() {
+        let mut ledger = ledger(CostBasisMethod::AverageCost);
+        assert_eq!(ledger.disposals()[0].buy_trade_id, None);
+        assert_eq!(ledger.realized_pnl(), dec!(150));
+        let lot = ledger.open_lots().next().unwrap();
+        assert_eq!((lot.trade_id, lot.qty, lot.unit_cost()), (None, dec!(1), dec!(150)));
+
+        // Selling more than the known holdings
+        ledger
+            .apply_trade(&trade(4, false, dec!(2), dec!(300), "0", "USDT"))
+            .unwrap();
+        let unknown = ledger.disposals().last().unwrap();
+        assert_eq!((unknown.acquired_time, unknown.qty), (None, dec!(1)));
+        assert_eq!(unknown.cost_basis, Decimal::ZERO);
+        assert_eq!(ledger.held_qty(), Decimal::ZERO);
+        // Already applied
+        ledger
+            .apply_trades(&[trade(4, false, dec!(2), dec!(300), "0", "USDT")])
+            .unwrap();
+        assert_eq!(ledger.disposals().len(), 3);
+    }
+
+    #[test]
+    fn commissions_in_base_and_quote_assets() {
+        let mut ledger = SymbolLedger::new("BTCUSDT", "BTC", "USDT", CostBasisMethod::Fifo);
+        ledger
+            .apply_trade(&trade(1, true, dec!(1), dec!(100), "0.001", "BTC"))
+            .unwrap();
+        assert_eq!(ledger.held_qty(), dec!(0.999));
+        assert_eq!(ledger.open_cost(), dec!(100));
+        ledger
+            .apply_trade(&trade(2, false, dec!(0.5), dec!(200), "0.1", "USDT"))
+            .unwrap();
+        assert_eq!(ledger.disposals()[0].proceeds, dec!(99.9));
+        assert_eq!(ledger.held_qty(), dec!(0.499));
+        let report = ledger.report(None);
+        assert_eq!(report.commissions.get("BTC"), Some(&dec!(0.001)));
+        assert_eq!(report.commissions.get("USDT"), Some(&dec!(0.1)));
+        assert!(report.unconverted_commissions.is_empty());
+        assert!(ledger
+            .apply_trade(&trade(3, true, dec!(1), dec!(100), "abc", "BTC"))
+            .is_err());
+    }
+
+    #[test]
+    fn third_asset_commissions_are_repriced() {
+        let mut ledger = SymbolLedger::new("BTCUSDT", "BTC", "USDT", CostBasisMethod::Fifo);
+        ledger
+            .apply_trades(&[
+                trade(1, true, dec!(1), dec!(100), "0.01", "BNB"),
+                trade(2, false, dec!(1), dec!(200), "0.01", "BNB"),
+            ])
+            .unwrap();
+        assert_eq!(ledger.realized_pnl(), dec!(100));
+        assert_eq!(ledger.unconverted_commissions().get("BNB"), Some(&dec!(0.02)));
+
+        ledger.set_commission_price("BNB", dec!(300));
+        assert_eq!(ledger.realized_pnl(), dec!(94));
+        assert!(ledger.unconverted_commissions().is_empty());
+        assert_eq!(ledger.report(None).commissions.get("BNB"), Some(&dec!(0.02)));
+
+        ledger
+            .apply_trade(&trade(3, true, dec!(1), dec!(100), "0.01", "BNB"))
+            .unwrap();
+        assert_eq!(ledger.open_cost(), dec!(103));
+    }
+
+    #[test]
+    fn csv_export() {
+        let mut portfolio = Portfolio::new(CostBasisMethod::Fifo);
+        portfolio
+            .apply_trades(
+                "BTCUSDT",
+                "BTC",
+                "USDT",
+                &[
+                    trade(1, true, dec!(1), dec!(100), "0", "USDT"),
+                    trade(2, true, dec!(1), dec!(200), "0", "USDT"),
+                    trade(3, false, dec!(1.5), dec!(300), "0", "USDT"),
+                ],
+            )
+            .unwrap();
+        let mut disposals = vec![];
+        portfolio.write_disposals_csv(&mut disposals).unwrap();
+        assert_eq!(
+            String::from_utf8(disposals).unwrap(),
+            "symbol,buy_trade_id,sell_trade_id,acquired_time,disposed_time,qty,cost_basis,proceeds,realized_pnl\n\
+             BTCUSDT,1,3,2023-11-14T22:13:21.000Z,2023-11-14T22:13:23.000Z,1,100,300,200\n\
+             BTCUSDT,2,3,2023-11-14T22:13:22.000Z,2023-11-14T22:13:23.000Z,0.5,100,150,50\n"
+        );
+        let mut open_lots = vec![];
+        portfolio.write_open_lots_csv(&mut open_lots).unwrap();
+        assert_eq!(
+            String::from_utf8(open_lots).unwrap(),
+            "symbol,trade_id,acquired_time,qty,cost,unit_cost\n\
+             BTCUSDT,2,2023-11-14T22:13:22.000Z,0.5,100,200\n"
+        );
+    }
+
+    #[test]
+    fn paginated_history_keeps_the_cost_of_early_buys() {
+        let mut pages = vec![
+            vec![trade(3, false, dec!(1.5), dec!(300), "0", "USDT")],
+            vec![
+                trade(1, true, dec!(1), dec!(100), "0", "USDT"),
+                trade(2, true, dec!(1), dec!(200), "0", "USDT"),
+            ],
+        ];
+        let mut requested = vec![];
+        let trades = tokio_test::block_on(crate::account::paginate_trades(2, |from_id| {
+            requested.push(from_id);
+            let page = pages.pop().unwrap_or_default();
+            async move { Ok(page) }
+        }))
+        .unwrap();
+        assert_eq!(requested, vec![0, 3]);
+
+        let mut ledger = SymbolLedger::new("BTCUSDT", "BTC", "USDT", CostBasisMethod::Fifo);
+        ledger.apply_trades(&trades).unwrap();
+        let cost_basis: Vec<(Option<u64>, Decimal)> = ledger
+            .disposals()
+            .iter()
+            .map(|d| (d.buy_trade_id, d.cost_basis))
+            .collect();
+        assert_eq!(cost_basis, vec![(Some(1), dec!(100)), (Some(2), dec!(100))]);
+        assert_eq!(ledger.realized_pnl(), dec!(250));
+    }
+}
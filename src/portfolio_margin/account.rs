@@ -0,0 +1,263 @@
+use super::rest_model::{
+    AccountInformation, Balance, FuturesOrder, MarginOrderCancellationResult, MarginOrderResult, MarginOrderState,
+    MessageResponse, StatusResponse,
+};
+use crate::account::OrderCancellation;
+use crate::client::Client;
+use crate::errors::*;
+use crate::futures::account::OrderRequest;
+use crate::rest_model::{MarginOrder, PairQuery};
+
+static PAPI_V1_UM_ORDER: &str = "/papi/v1/um/order";
+static PAPI_V1_UM_OPEN_ORDERS: &str = "/papi/v1/um/openOrders";
+static PAPI_V1_UM_ALL_OPEN_ORDERS: &str = "/papi/v1/um/allOpenOrders";
+static PAPI_V1_CM_ORDER: &str = "/papi/v1/cm/order";
+static PAPI_V1_CM_OPEN_ORDERS: &str = "/papi/v1/cm/openOrders";
+static PAPI_V1_CM_ALL_OPEN_ORDERS: &str = "/papi/v1/cm/allOpenOrders";
+static PAPI_V1_MARGIN_ORDER: &str = "/papi/v1/margin/order";
+static PAPI_V1_MARGIN_OPEN_ORDERS: &str = "/papi/v1/margin/openOrders";
+static PAPI_V1_BALANCE: &str = "/papi/v1/balance";
+static PAPI_V1_ACCOUNT: &str = "/papi/v1/account";
+static PAPI_V1_AUTO_COLLECTION: &str = "/papi/v1/auto-collection";
+static PAPI_V1_ASSET_COLLECTION: &str = "/papi/v1/asset-collection";
+static PAPI_V1_REPAY_FUTURES_NEGATIVE_BALANCE: &str = "/papi/v1/repay-futures-negative-balance";
+
+/// This struct acts as a gateway for all portfolio margin endpoints.
+/// Preferably use the trait [`crate::api::Binance`] to get an instance.
+#[derive(Clone)]
+pub struct PortfolioMargin {
+    pub client: Client,
+    pub recv_window: u64,
+}
+
+#[derive(Serialize)]
+struct AssetQuery {
+    pub asset: String,
+}
+
+impl PortfolioMargin {
+    /// Place a UM futures order
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, config::*, futures::account::OrderRequest, portfolio_margin::account::*, rest_model::OrderSide};
+    /// use rust_decimal_macros::dec;
+    /// let account: PortfolioMargin = Binance::new_with_env(&Config::default());
+    /// let order = OrderRequest::limit("BTCUSDT", OrderSide::Buy, dec!(0.001), dec!(20000));
+    /// let placed = tokio_test::block_on(account.place_um_order(order));
+    /// assert!(placed.is_ok(), "{:?}", placed);
+    /// ```
+    pub async fn place_um_order(&self, order: OrderRequest) -> Result<FuturesOrder> {
+        order.valid()?;
+        self.client
+            .post_signed_p(PAPI_V1_UM_ORDER, order, self.recv_window)
+            .await
+    }
+
+    /// Cancel a UM futures order
+    pub async fn cancel_um_order(&self, o: OrderCancellation) -> Result<FuturesOrder> {
+        let recv_window = o.recv_window.unwrap_or(self.recv_window);
+        self.client.delete_signed_p(PAPI_V1_UM_ORDER, &o, recv_window).await
+    }
+
+    /// Cancel all open UM futures orders on this symbol
+    pub async fn cancel_all_um_open_orders<S>(&self, symbol: S) -> Result<StatusResponse>
+    where
+        S: Into<String>,
+    {
+        self.client
+            .delete_signed_p(
+                PAPI_V1_UM_ALL_OPEN_ORDERS,
+                PairQuery { symbol: symbol.into() },
+                self.recv_window,
+            )
+            .await
+    }
+
+    /// Get the open UM futures orders on this symbol
+    pub async fn um_open_orders<S>(&self, symbol: S) -> Result<Vec<FuturesOrder>>
+    where
+        S: Into<String>,
+    {
+        self.client
+            .get_signed_p(
+                PAPI_V1_UM_OPEN_ORDERS,
+                Some(PairQuery { symbol: symbol.into() }),
+                self.recv_window,
+            )
+            .await
+    }
+
+    /// Place a CM futures order, the quantity is in contracts
+    pub async fn place_cm_order(&self, order: OrderRequest) -> Result<FuturesOrder> {
+        order.valid()?;
+        self.client
+            .post_signed_p(PAPI_V1_CM_ORDER, order, self.recv_window)
+            .await
+    }
+
+    /// Cancel a CM futures order
+    pub async fn cancel_cm_order(&self, o: OrderCancellation) -> Result<FuturesOrder> {
+        let recv_window = o.recv_window.unwrap_or(self.recv_window);
+        self.client.delete_signed_p(PAPI_V1_CM_ORDER, &o, recv_window).await
+    }
+
+    /// Cancel all open CM futures orders on this symbol
+    pub async fn cancel_all_cm_open_orders<S>(&self, symbol: S) -> Result<StatusResponse>
+    where
+        S: Into<String>,
+    {
+        self.client
+            .delete_signed_p(
+                PAPI_V1_CM_ALL_OPEN_ORDERS,
+                PairQuery { symbol: symbol.into() },
+                self.recv_window,
+            )
+            .await
+    }
+
+    /// Get the open CM futures orders on this symbol
+    pub async fn cm_open_orders<S>(&self, symbol: S) -> Result<Vec<FuturesOrder>>
+    where
+        S: Into<String>,
+    {
+        self.client
+            .get_signed_p(
+                PAPI_V1_CM_OPEN_ORDERS,
+                Some(PairQuery { symbol: symbol.into() }),
+                self.recv_window,
+            )
+            .await
+    }
+
+    /// Place a cross margin order, isolated margin is not available to portfolio margin accounts
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, config::*, portfolio_margin::account::*, rest_model::*};
+    /// use rust_decimal_macros::dec;
+    /// let account: PortfolioMargin = Binance::new_with_env(&Config::default());
+    /// let order = MarginOrder::limit("BTCUSDT", OrderSide::Buy, dec!(0.001), dec!(20000));
+    /// let placed = tokio_test::block_on(account.place_margin_order(order));
+    /// assert!(placed.is_ok(), "{:?}", placed);
+    /// ```
+    pub async fn place_margin_order(&self, margin_order: MarginOrder) -> Result<MarginOrderResult> {
+        margin_order.valid()?;
+        self.client
+            .post_signed_p(PAPI_V1_MARGIN_ORDER, margin_order, self.recv_window)
+            .await
+    }
+
+    /// Cancel a cross margin order
+    pub async fn cancel_margin_order(&self, o: OrderCancellation) -> Result<MarginOrderCancellationResult> {
+        let recv_window = o.recv_window.unwrap_or(self.recv_window);
+        self.client.delete_signed_p(PAPI_V1_MARGIN_ORDER, &o, recv_window).await
+    }
+
+    /// Get the open cross margin orders on this symbol
+    pub async fn margin_open_orders<S>(&self, symbol: S) -> Result<Vec<MarginOrderState>>
+    where
+        S: Into<String>,
+    {
+        self.client
+            .get_signed_p(
+                PAPI_V1_MARGIN_OPEN_ORDERS,
+                Some(PairQuery { symbol: symbol.into() }),
+                self.recv_window,
+            )
+            .await
+    }
+
+    /// Unified balances of all assets
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, config::*, portfolio_margin::account::*};
+    /// let account: PortfolioMargin = Binance::new_with_env(&Config::default());
+    /// let balances = tokio_test::block_on(account.balances());
+    /// assert!(balances.is_ok(), "{:?}", balances);
+    /// ```
+    pub async fn balances(&self) -> Result<Vec<Balance>> {
+        self.client
+            .get_signed_p(PAPI_V1_BALANCE, Option::<String>::None, self.recv_window)
+            .await
+    }
+
+    /// Unified balance of an asset
+    pub async fn balance<S>(&self, asset: S) -> Result<Balance>
+    where
+        S: Into<String>,
+    {
+        self.client
+            .get_signed_p(
+                PAPI_V1_BALANCE,
+                Some(AssetQuery { asset: asset.into() }),
+                self.recv_window,
+            )
+            .await
+    }
+
+    /// Unified maintenance margin ratio, equity and margins of the account
+    pub async fn account_information(&self) -> Result<AccountInformation> {
+        self.client
+            .get_signed_p(PAPI_V1_ACCOUNT, Option::<String>::None, self.recv_window)
+            .await
+    }
+
+    /// Collect the assets of the UM and CM wallets into the margin wallet
+    pub async fn auto_collection(&self) -> Result<MessageResponse> {
+        self.client
+            .post_signed_p(PAPI_V1_AUTO_COLLECTION, Option::<String>::None, self.recv_window)
+            .await
+    }
+
+    /// Collect an asset of the UM and CM wallets into the margin wallet
+    pub async fn asset_collection<S>(&self, asset: S) -> Result<MessageResponse>
+    where
+        S: Into<String>,
+    {
+        self.client
+            .post_signed_p(
+                PAPI_V1_ASSET_COLLECTION,
+                AssetQuery { asset: asset.into() },
+                self.recv_window,
+            )
+            .await
+    }
+
+    /// Repay the negative balances of the UM and CM wallets from the margin wallet
+    pub async fn repay_futures_negative_balance(&self) -> Result<MessageResponse> {
+        self.client
+            .post_signed_p(
+                PAPI_V1_REPAY_FUTURES_NEGATIVE_BALANCE,
+                Option::<String>::None,
+                self.recv_window,
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::api::Binance;
+    use crate::rest_model::OrderSide;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn orders_are_validated_before_sending() {
+        // The client has no credentials, so only a rejection by `valid` can come back as an order error
+        let account: PortfolioMargin = Binance::new(None, None);
+        let close_position = || OrderRequest {
+            close_position: Some(true),
+            ..OrderRequest::stop_market("BTCUSDT", OrderSide::Sell, dec!(0.01), dec!(19000))
+        };
+        let result = tokio_test::block_on(account.place_um_order(close_position()));
+        assert!(matches!(result, Err(Error::InvalidOrderError { .. })), "{result:?}");
+        let result = tokio_test::block_on(account.place_cm_order(close_position()));
+        assert!(matches!(result, Err(Error::InvalidOrderError { .. })), "{result:?}");
+        let missing_price = MarginOrder {
+            price: None,
+            ..MarginOrder::limit("BTCUSDT", OrderSide::Buy, dec!(1), dec!(20000))
+        };
+        let result = tokio_test::block_on(account.place_margin_order(missing_price));
+        assert!(matches!(result, Err(Error::InvalidOrderError { .. })), "{result:?}");
+    }
+}
@@ -0,0 +1,11 @@
+//! Portfolio margin accounts, which trade USDⓈ-M futures (UM), coin-margined futures (CM) and cross
+//! margin through `/papi` with a unified margin balance.
+//!
+//! Futures orders are built with [`crate::futures::account::OrderRequest`] and margin orders with
+//! [`crate::rest_model::MarginOrder`].
+
+pub mod account;
+pub mod rest_model;
+pub mod userstream;
+pub mod websockets;
+pub mod ws_model;
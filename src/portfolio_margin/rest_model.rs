@@ -0,0 +1,186 @@
+pub use crate::futures::rest_model::{OrderType, PositionSide, StatusResponse};
+use crate::rest_model::string_or_float_opt;
+pub use crate::rest_model::{
+    string_or_float, MarginOrderCancellationResult, MarginOrderResult, MarginOrderState, OrderSide, OrderStatus,
+    TimeInForce,
+};
+use rust_decimal::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A UM or CM futures order, as returned by queries, placements and cancellations
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FuturesOrder {
+    pub order_id: u64,
+    pub symbol: String,
+    /// Only for CM orders
+    #[serde(default)]
+    pub pair: Option<String>,
+    pub status: OrderStatus,
+    pub client_order_id: String,
+    #[serde(with = "string_or_float")]
+    pub price: Decimal,
+    #[serde(default, with = "string_or_float")]
+    pub avg_price: Decimal,
+    /// Quantity in contracts for CM orders
+    #[serde(with = "string_or_float")]
+    pub orig_qty: Decimal,
+    #[serde(with = "string_or_float")]
+    pub executed_qty: Decimal,
+    /// Executed quantity in quote asset, only for UM orders
+    #[serde(default, with = "string_or_float_opt")]
+    pub cum_quote: Option<Decimal>,
+    /// Executed quantity in base asset, only for CM orders
+    #[serde(default, with = "string_or_float_opt")]
+    pub cum_base: Option<Decimal>,
+    pub time_in_force: TimeInForce,
+    #[serde(rename = "type")]
+    pub order_type: OrderType,
+    #[serde(default)]
+    pub orig_type: Option<OrderType>,
+    pub reduce_only: bool,
+    pub side: OrderSide,
+    pub position_side: PositionSide,
+    /// Creation time, not included in placement and cancellation responses
+    #[serde(default)]
+    pub time: u64,
+    pub update_time: u64,
+}
+
+/// Balance of an asset across the cross margin, UM and CM wallets
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Balance {
+    pub asset: String,
+    #[serde(with = "string_or_float")]
+    pub total_wallet_balance: Decimal,
+    #[serde(with = "string_or_float")]
+    pub cross_margin_asset: Decimal,
+    #[serde(with = "string_or_float")]
+    pub cross_margin_borrowed: Decimal,
+    #[serde(with = "string_or_float")]
+    pub cross_margin_free: Decimal,
+    #[serde(with = "string_or_float")]
+    pub cross_margin_interest: Decimal,
+    #[serde(with = "string_or_float")]
+    pub cross_margin_locked: Decimal,
+    #[serde(with = "string_or_float")]
+    pub um_wallet_balance: Decimal,
+    #[serde(rename = "umUnrealizedPNL", with = "string_or_float")]
+    pub um_unrealized_pnl: Decimal,
+    #[serde(with = "string_or_float")]
+    pub cm_wallet_balance: Decimal,
+    #[serde(rename = "cmUnrealizedPNL", with = "string_or_float")]
+    pub cm_unrealized_pnl: Decimal,
+    /// Negative futures balance, which can be repaid from the margin wallet
+    #[serde(with = "string_or_float")]
+    pub negative_balance: Decimal,
+    pub update_time: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AccountStatus {
+    Normal,
+    MarginCall,
+    SupplyMargin,
+    ReduceOnly,
+    ActiveLiquidation,
+    ForceLiquidation,
+    Bankrupted,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountInformation {
+    /// Unified maintenance margin ratio
+    #[serde(rename = "uniMMR", with = "string_or_float")]
+    pub uni_mmr: Decimal,
+    /// Equity in USD
+    #[serde(with = "string_or_float")]
+    pub account_equity: Decimal,
+    /// Equity in USD, without collateral rates
+    #[serde(with = "string_or_float")]
+    pub actual_equity: Decimal,
+    #[serde(with = "string_or_float")]
+    pub account_initial_margin: Decimal,
+    #[serde(with = "string_or_float")]
+    pub account_maint_margin: Decimal,
+    pub account_status: AccountStatus,
+    /// Maximum amount in USD which can be transferred out
+    #[serde(with = "string_or_float")]
+    pub virtual_max_withdraw_amount: Decimal,
+    #[serde(default, with = "string_or_float_opt")]
+    pub total_available_balance: Option<Decimal>,
+    #[serde(default, with = "string_or_float_opt")]
+    pub total_margin_open_loss: Option<Decimal>,
+    pub update_time: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MessageResponse {
+    pub msg: String,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn futures_order_serde() {
+        let um: FuturesOrder = serde_json::from_str(
+            r#"{"clientOrderId": "testOrder", "cumQty": "0", "cumQuote": "0", "executedQty": "0", "orderId": 22542179,
+                "avgPrice": "0.00000", "origQty": "10", "price": "0", "reduceOnly": false, "side": "BUY",
+                "positionSide": "SHORT", "status": "NEW", "symbol": "BTCUSDT", "timeInForce": "GTD", "type": "MARKET",
+                "selfTradePreventionMode": "NONE", "goodTillDate": 1693207680000, "updateTime": 1566818724722,
+                "priceMatch": "NONE"}"#,
+        )
+        .unwrap();
+        assert_eq!(um.cum_quote, Some(dec!(0)));
+        assert_eq!(um.cum_base, None);
+        assert_eq!(um.pair, None);
+        assert_eq!(um.time, 0);
+
+        let cm: FuturesOrder = serde_json::from_str(
+            r#"{"avgPrice": "0.0", "clientOrderId": "abc", "cumBase": "0", "executedQty": "0", "orderId": 1917641,
+                "origQty": "0.40", "origType": "LIMIT", "price": "0", "reduceOnly": false, "side": "BUY",
+                "positionSide": "SHORT", "status": "NEW", "symbol": "BTCUSD_200925", "pair": "BTCUSD",
+                "time": 1579276756075, "timeInForce": "GTC", "type": "LIMIT", "updateTime": 1579276756075}"#,
+        )
+        .unwrap();
+        assert_eq!(cm.pair.as_deref(), Some("BTCUSD"));
+        assert_eq!(cm.cum_base, Some(dec!(0)));
+        assert_eq!(cm.cum_quote, None);
+        assert_eq!(cm.position_side, PositionSide::Short);
+    }
+
+    #[test]
+    fn balance_and_account_serde() {
+        let balance: Balance = serde_json::from_str(
+            r#"{"asset": "USDT", "totalWalletBalance": "122607.35137903", "crossMarginAsset": "92.27530794",
+                "crossMarginBorrowed": "10.00000000", "crossMarginFree": "100.00000000",
+                "crossMarginInterest": "0.72469206", "crossMarginLocked": "3.00000000",
+                "umWalletBalance": "0.00000000", "umUnrealizedPNL": "23.72469206", "cmWalletBalance": "23.72469206",
+                "cmUnrealizedPNL": "0.00000000", "updateTime": 1617939110373, "negativeBalance": "0"}"#,
+        )
+        .unwrap();
+        assert_eq!(balance.um_unrealized_pnl, dec!(23.72469206));
+        assert_eq!(balance.cross_margin_borrowed, dec!(10));
+
+        let account: AccountInformation = serde_json::from_str(
+            r#"{"uniMMR": "5167.92171923", "accountEquity": "122607.35137903", "actualEquity": "73.47428058",
+                "accountInitialMargin": "23.72469206", "accountMaintMargin": "23.72469206",
+                "accountStatus": "NORMAL", "virtualMaxWithdrawAmount": "1627523.32459208",
+                "totalAvailableBalance": "", "totalMarginOpenLoss": "", "updateTime": 1657707212154}"#,
+        )
+        .unwrap();
+        assert_eq!(account.uni_mmr, dec!(5167.92171923));
+        assert_eq!(account.account_status, AccountStatus::Normal);
+        assert_eq!(account.total_available_balance, None);
+        let status: AccountStatus = serde_json::from_str("\"SOME_NEW_STATUS\"").unwrap();
+        assert_eq!(status, AccountStatus::Other);
+    }
+}
@@ -0,0 +1,27 @@
+//! User data stream of portfolio margin accounts, on which UM, CM and cross margin events arrive.
+
+pub use crate::websockets::WebSockets;
+
+use crate::config::Config;
+use crate::errors::*;
+
+/// New websocket holder connecting to the portfolio margin endpoint of the configuration
+/// # Examples
+/// ```rust,no_run
+/// use binance::{portfolio_margin::{websockets::*, ws_model::WebsocketEvent}, config::*};
+/// use std::sync::atomic::AtomicBool;
+/// let mut ws = new_with_options(|event: WebsocketEvent| { println!("{event:?}"); Ok(()) }, Config::default());
+/// tokio_test::block_on(ws.connect("my_listen_key")).unwrap();
+/// tokio_test::block_on(ws.event_loop(&AtomicBool::new(true))).unwrap();
+/// ```
+pub fn new_with_options<'a, WE, Callback>(handler: Callback, conf: Config) -> WebSockets<'a, WE>
+where
+    WE: serde::de::DeserializeOwned,
+    Callback: FnMut(WE) -> Result<()> + 'a + Send,
+{
+    let conf = Config {
+        ws_endpoint: conf.portfolio_margin_ws_endpoint.clone(),
+        ..conf
+    };
+    WebSockets::new_with_options(handler, conf)
+}
@@ -0,0 +1,153 @@
+use super::rest_model::AccountStatus;
+use crate::futures::rest_model::{OrderType, PositionSide};
+pub use crate::futures::ws_model::{Balance, ReasonType};
+use crate::rest_model::{string_or_float, string_or_float_opt, ExecutionType, OrderSide, OrderStatus, TimeInForce};
+pub use crate::ws_model::{AccountPositionUpdate, BalanceUpdate, OrderUpdate};
+use rust_decimal::Decimal;
+
+/// Events of the portfolio margin user data stream, futures events name the futures market they
+/// come from and cross margin events have the same format as on the spot stream
+#[derive(Debug, Deserialize)]
+#[serde(tag = "e")]
+pub enum WebsocketEvent {
+    #[serde(rename = "ACCOUNT_UPDATE")]
+    AccountUpdate(Box<AccountUpdate>),
+    #[serde(rename = "ORDER_TRADE_UPDATE")]
+    OrderTradeUpdate(Box<OrderTradeUpdate>),
+    #[serde(rename = "executionReport")]
+    MarginOrderUpdate(Box<OrderUpdate>),
+    #[serde(rename = "outboundAccountPosition")]
+    MarginAccountPositionUpdate(Box<AccountPositionUpdate>),
+    #[serde(rename = "balanceUpdate")]
+    MarginBalanceUpdate(Box<BalanceUpdate>),
+    #[serde(rename = "riskLevelChange")]
+    RiskLevelChange(Box<RiskLevelChange>),
+}
+
+/// Futures market of a futures event
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum BusinessUnit {
+    Um,
+    Cm,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AccountUpdate {
+    #[serde(rename = "fs")]
+    pub business_unit: BusinessUnit,
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+    #[serde(rename = "a")]
+    pub account: Account,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Account {
+    #[serde(rename = "m")]
+    pub reason_type: ReasonType,
+    #[serde(rename = "B")]
+    pub balances: Vec<Balance>,
+    #[serde(rename = "P")]
+    pub positions: Vec<Position>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Position {
+    #[serde(rename = "s")]
+    pub symbol: String,
+    /// Position in contracts for CM positions, negative when short
+    #[serde(rename = "pa", with = "string_or_float")]
+    pub position_amount: Decimal,
+    #[serde(rename = "ep", with = "string_or_float")]
+    pub entry_price: Decimal,
+    #[serde(default, rename = "bep", with = "string_or_float_opt")]
+    pub breakeven_price: Option<Decimal>,
+    #[serde(rename = "cr", with = "string_or_float")]
+    pub accumulated_realized: Decimal,
+    #[serde(rename = "up", with = "string_or_float")]
+    pub unrealized_profit: Decimal,
+    #[serde(rename = "ps")]
+    pub position_side: PositionSide,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct OrderTradeUpdate {
+    #[serde(rename = "fs")]
+    pub business_unit: BusinessUnit,
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+    #[serde(rename = "o")]
+    pub order: Order,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Order {
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "c")]
+    pub client_order_id: String,
+    #[serde(rename = "S")]
+    pub side: OrderSide,
+    #[serde(rename = "o")]
+    pub order_type: OrderType,
+    #[serde(rename = "f")]
+    pub time_in_force: TimeInForce,
+    #[serde(rename = "q", with = "string_or_float")]
+    pub quantity: Decimal,
+    #[serde(rename = "p", with = "string_or_float")]
+    pub price: Decimal,
+    #[serde(rename = "ap", with = "string_or_float")]
+    pub average_price: Decimal,
+    #[serde(rename = "x")]
+    pub execution_type: ExecutionType,
+    #[serde(rename = "X")]
+    pub order_status: OrderStatus,
+    #[serde(rename = "i")]
+    pub order_id: u64,
+    #[serde(rename = "l", with = "string_or_float")]
+    pub order_last_filled_quantity: Decimal,
+    #[serde(rename = "z", with = "string_or_float")]
+    pub order_filled_accumulated_quantity: Decimal,
+    #[serde(rename = "L", with = "string_or_float")]
+    pub last_filled_price: Decimal,
+    #[serde(default, rename = "n", with = "string_or_float_opt")]
+    pub commission: Option<Decimal>,
+    #[serde(rename = "N")]
+    pub commission_asset: Option<String>,
+    #[serde(rename = "T")]
+    pub order_trade_time: u64,
+    #[serde(rename = "t")]
+    pub trade_id: u64,
+    #[serde(rename = "m")]
+    pub is_maker: bool,
+    #[serde(rename = "R")]
+    pub is_reduce: bool,
+    #[serde(rename = "ps")]
+    pub position_side: PositionSide,
+    #[serde(rename = "rp", with = "string_or_float")]
+    pub realized_profit: Decimal,
+}
+
+/// Change of the risk level of the account, based on its unified maintenance margin ratio
+#[derive(Clone, Debug, Deserialize)]
+pub struct RiskLevelChange {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "u", with = "string_or_float")]
+    pub uni_mmr: Decimal,
+    #[serde(rename = "s")]
+    pub account_status: AccountStatus,
+    /// Equity in USD
+    #[serde(rename = "eq", with = "string_or_float")]
+    pub account_equity: Decimal,
+    /// Equity in USD, without collateral rates
+    #[serde(rename = "ae", with = "string_or_float")]
+    pub actual_equity: Decimal,
+    #[serde(rename = "m", with = "string_or_float")]
+    pub maint_margin: Decimal,
+}
@@ -37,8 +37,20 @@ pub struct Symbol {
     pub quote_order_qty_market_allowed: bool,
     pub is_spot_trading_allowed: bool,
     pub is_margin_trading_allowed: bool,
+    #[serde(default)]
+    pub allow_trailing_stop: bool,
+    #[serde(default)]
+    pub cancel_replace_allowed: bool,
+    #[serde(default)]
+    pub oto_allowed: bool,
     pub filters: Vec<Filters>,
     pub permissions: Vec<SymbolPermission>,
+    /// Sets of permissions, holding any one complete set is enough to trade the symbol
+    #[serde(default)]
+    pub permission_sets: Vec<Vec<SymbolPermission>>,
+    pub default_self_trade_prevention_mode: Option<SelfTradePreventionMode>,
+    #[serde(default)]
+    pub allowed_self_trade_prevention_modes: Vec<SelfTradePreventionMode>,
 }
 
 impl Symbol {
@@ -55,6 +67,20 @@ impl Symbol {
             .find(|filter| matches!(filter, Filters::MarketLotSize { .. }))
             .cloned()
     }
+
+    pub fn notional(&self) -> Option<Filters> {
+        self.filters
+            .iter()
+            .find(|filter| matches!(filter, Filters::Notional { .. }))
+            .cloned()
+    }
+
+    pub fn trailing_delta(&self) -> Option<Filters> {
+        self.filters
+            .iter()
+            .find(|filter| matches!(filter, Filters::TrailingDelta { .. }))
+            .cloned()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -79,6 +105,19 @@ pub enum Filters {
         multiplier_down: Decimal,
         avg_price_mins: u64,
     },
+    #[serde(rename = "PERCENT_PRICE_BY_SIDE")]
+    #[serde(rename_all = "camelCase")]
+    PercentPriceBySide {
+        #[serde(with = "string_or_float")]
+        bid_multiplier_up: Decimal,
+        #[serde(with = "string_or_float")]
+        bid_multiplier_down: Decimal,
+        #[serde(with = "string_or_float")]
+        ask_multiplier_up: Decimal,
+        #[serde(with = "string_or_float")]
+        ask_multiplier_down: Decimal,
+        avg_price_mins: u64,
+    },
     #[serde(rename = "LOT_SIZE")]
     #[serde(rename_all = "camelCase")]
     LotSize {
@@ -107,6 +146,17 @@ pub enum Filters {
         apply_to_market: bool,
         avg_price_mins: u64,
     },
+    #[serde(rename = "NOTIONAL")]
+    #[serde(rename_all = "camelCase")]
+    Notional {
+        #[serde(with = "string_or_float")]
+        min_notional: Decimal,
+        apply_min_to_market: bool,
+        #[serde(with = "string_or_float")]
+        max_notional: Decimal,
+        apply_max_to_market: bool,
+        avg_price_mins: u64,
+    },
     #[serde(rename = "ICEBERG_PARTS")]
     #[serde(rename_all = "camelCase")]
     IcebergParts { limit: u16 },
@@ -125,12 +175,24 @@ pub enum Filters {
         #[serde(with = "string_or_float")]
         max_position: Decimal,
     },
+    #[serde(rename = "TRAILING_DELTA")]
+    #[serde(rename_all = "camelCase")]
+    TrailingDelta {
+        min_trailing_above_delta: u32,
+        max_trailing_above_delta: u32,
+        min_trailing_below_delta: u32,
+        max_trailing_below_delta: u32,
+    },
     #[serde(rename = "EXCHANGE_MAX_NUM_ORDERS")]
     #[serde(rename_all = "camelCase")]
     ExchangeMaxNumOrders { max_num_orders: u16 },
-    #[serde(rename = "EXCHANGE_MAX_ALGO_ORDERS")]
+    #[serde(rename = "EXCHANGE_MAX_NUM_ALGO_ORDERS", alias = "EXCHANGE_MAX_ALGO_ORDERS")]
     #[serde(rename_all = "camelCase")]
     ExchangeMaxNumAlgoOrders { max_num_algo_orders: u16 },
+    #[serde(rename = "EXCHANGE_MAX_NUM_ICEBERG_ORDERS")]
+    #[serde(rename_all = "camelCase")]
+    ExchangeMaxNumIcebergOrders { max_num_iceberg_orders: u16 },
+    /// Any filter type not modeled above, so that new filters never break parsing
     #[serde(other)]
     Others,
 }
@@ -265,6 +327,128 @@ pub struct Transaction {
     pub fills: Vec<Fill>,
 }
 
+/// Fill of an order placed through Smart Order Routing
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SorFill {
+    pub match_type: String,
+    #[serde(with = "string_or_float")]
+    pub price: Decimal,
+    #[serde(with = "string_or_float")]
+    pub qty: Decimal,
+    #[serde(with = "string_or_float")]
+    pub commission: Decimal,
+    pub commission_asset: String,
+    pub trade_id: i64,
+    pub alloc_id: u64,
+}
+
+/// Response to an order placed through Smart Order Routing (endpoint /api/v3/sor/order).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SorTransaction {
+    pub symbol: String,
+    pub order_id: u64,
+    pub order_list_id: i64,
+    pub client_order_id: String,
+    pub transact_time: u64,
+    #[serde(with = "string_or_float")]
+    pub price: Decimal,
+    #[serde(with = "string_or_float")]
+    pub orig_qty: Decimal,
+    #[serde(with = "string_or_float")]
+    pub executed_qty: Decimal,
+    #[serde(with = "string_or_float")]
+    pub cummulative_quote_qty: Decimal,
+    pub status: OrderStatus,
+    pub time_in_force: TimeInForce,
+    #[serde(rename = "type")]
+    pub order_type: OrderType,
+    pub side: OrderSide,
+    pub working_time: u64,
+    #[serde(default)]
+    pub fills: Vec<SorFill>,
+    pub working_floor: String,
+    pub self_trade_prevention_mode: SelfTradePreventionMode,
+    pub used_sor: bool,
+}
+
+/// Trade resulting from a Smart Order Routing allocation
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Allocation {
+    pub symbol: String,
+    pub allocation_id: u64,
+    pub allocation_type: String,
+    pub order_id: u64,
+    pub order_list_id: i64,
+    #[serde(with = "string_or_float")]
+    pub price: Decimal,
+    #[serde(with = "string_or_float")]
+    pub qty: Decimal,
+    #[serde(with = "string_or_float")]
+    pub quote_qty: Decimal,
+    #[serde(with = "string_or_float")]
+    pub commission: Decimal,
+    pub commission_asset: String,
+    pub time: u64,
+    pub is_buyer: bool,
+    pub is_maker: bool,
+    pub is_allocator: bool,
+}
+
+/// State of an order after its quantity was amended
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AmendedOrder {
+    pub symbol: String,
+    pub order_id: u64,
+    pub order_list_id: i64,
+    pub orig_client_order_id: String,
+    pub client_order_id: String,
+    #[serde(with = "string_or_float")]
+    pub price: Decimal,
+    #[serde(with = "string_or_float")]
+    pub qty: Decimal,
+    #[serde(with = "string_or_float")]
+    pub executed_qty: Decimal,
+    #[serde(with = "string_or_float")]
+    pub prevented_qty: Decimal,
+    #[serde(with = "string_or_float")]
+    pub quote_order_qty: Decimal,
+    #[serde(with = "string_or_float")]
+    pub cumulative_quote_qty: Decimal,
+    pub status: OrderStatus,
+    pub time_in_force: TimeInForce,
+    #[serde(rename = "type")]
+    pub order_type: OrderType,
+    pub side: OrderSide,
+    pub working_time: u64,
+    pub self_trade_prevention_mode: SelfTradePreventionMode,
+}
+
+/// Response to an order amendment (endpoint /api/v3/order/amend/keepPriority).
+/// list_status is only set when the amended order is part of an order list
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderAmendment {
+    pub transact_time: u64,
+    pub execution_id: u64,
+    pub amended_order: AmendedOrder,
+    pub list_status: Option<AmendedOrderList>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AmendedOrderList {
+    pub order_list_id: u64,
+    pub contingency_type: ContingencyType,
+    pub list_order_status: OCOOrderStatus,
+    pub list_client_order_id: Option<String>,
+    pub symbol: String,
+    pub orders: Vec<OCOOrderDetail>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionId {
@@ -375,6 +559,24 @@ pub struct TradeHistory {
     pub is_best_match: bool,
 }
 
+/// Order that expired because of self-trade prevention
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PreventedMatch {
+    pub symbol: String,
+    pub prevented_match_id: u64,
+    pub taker_order_id: u64,
+    pub maker_symbol: Option<String>,
+    pub maker_order_id: u64,
+    pub trade_group_id: i64,
+    pub self_trade_prevention_mode: SelfTradePreventionMode,
+    #[serde(with = "string_or_float")]
+    pub price: Decimal,
+    #[serde(with = "string_or_float")]
+    pub maker_prevented_quantity: Decimal,
+    pub transact_time: u64,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct PriceStats {
@@ -477,6 +679,8 @@ pub enum TimeInForce {
     FOK,
     /// Good till expired
     GTX,
+    /// Good till date, futures only
+    GTD,
     #[serde(other)]
     Other,
 }
@@ -561,6 +765,135 @@ impl Default for OrderType {
     }
 }
 
+impl OrderType {
+    /// Orders of this type rest in the book at a limit price
+    pub fn requires_price(&self) -> bool {
+        matches!(
+            self,
+            Self::Limit | Self::LimitMaker | Self::StopLossLimit | Self::TakeProfitLimit
+        )
+    }
+
+    /// Orders of this type are triggered by a stop price or a trailing delta
+    pub fn requires_stop_price(&self) -> bool {
+        matches!(
+            self,
+            Self::StopLoss | Self::StopLossLimit | Self::TakeProfit | Self::TakeProfitLimit
+        )
+    }
+
+    /// Orders of this type must be sent with a time in force
+    pub fn requires_time_in_force(&self) -> bool {
+        matches!(self, Self::Limit | Self::StopLossLimit | Self::TakeProfitLimit)
+    }
+}
+
+/// Type of a spot or margin order with the parameters it requires, the limit orders are good till
+/// canceled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderSpec {
+    Limit {
+        qty: Decimal,
+        price: Decimal,
+    },
+    /// Rejected if it would immediately match as a taker
+    LimitMaker {
+        qty: Decimal,
+        price: Decimal,
+    },
+    /// For a quantity of the base asset
+    Market {
+        qty: Decimal,
+    },
+    /// Spending (when buying) or receiving (when selling) a quantity of the quote asset
+    MarketQuote {
+        quote_qty: Decimal,
+    },
+    /// Executes a MARKET order once the stop price is reached
+    StopLoss {
+        qty: Decimal,
+        stop_price: Decimal,
+    },
+    /// Places a LIMIT order once the stop price is reached
+    StopLossLimit {
+        qty: Decimal,
+        price: Decimal,
+        stop_price: Decimal,
+    },
+    /// Executes a MARKET order once the stop price is reached
+    TakeProfit {
+        qty: Decimal,
+        stop_price: Decimal,
+    },
+    /// Places a LIMIT order once the stop price is reached
+    TakeProfitLimit {
+        qty: Decimal,
+        price: Decimal,
+        stop_price: Decimal,
+    },
+}
+
+impl OrderSpec {
+    pub fn order_type(&self) -> OrderType {
+        match self {
+            Self::Limit { .. } => OrderType::Limit,
+            Self::LimitMaker { .. } => OrderType::LimitMaker,
+            Self::Market { .. } | Self::MarketQuote { .. } => OrderType::Market,
+            Self::StopLoss { .. } => OrderType::StopLoss,
+            Self::StopLossLimit { .. } => OrderType::StopLossLimit,
+            Self::TakeProfit { .. } => OrderType::TakeProfit,
+            Self::TakeProfitLimit { .. } => OrderType::TakeProfitLimit,
+        }
+    }
+
+    /// Quantity of the base asset, None when the quantity is in the quote asset
+    pub fn quantity(&self) -> Option<Decimal> {
+        match *self {
+            Self::Limit { qty, .. }
+            | Self::LimitMaker { qty, .. }
+            | Self::Market { qty }
+            | Self::StopLoss { qty, .. }
+            | Self::StopLossLimit { qty, .. }
+            | Self::TakeProfit { qty, .. }
+            | Self::TakeProfitLimit { qty, .. } => Some(qty),
+            Self::MarketQuote { .. } => None,
+        }
+    }
+
+    pub fn quote_order_qty(&self) -> Option<Decimal> {
+        match *self {
+            Self::MarketQuote { quote_qty } => Some(quote_qty),
+            _ => None,
+        }
+    }
+
+    pub fn price(&self) -> Option<Decimal> {
+        match *self {
+            Self::Limit { price, .. }
+            | Self::LimitMaker { price, .. }
+            | Self::StopLossLimit { price, .. }
+            | Self::TakeProfitLimit { price, .. } => Some(price),
+            _ => None,
+        }
+    }
+
+    pub fn stop_price(&self) -> Option<Decimal> {
+        match *self {
+            Self::StopLoss { stop_price, .. }
+            | Self::StopLossLimit { stop_price, .. }
+            | Self::TakeProfit { stop_price, .. }
+            | Self::TakeProfitLimit { stop_price, .. } => Some(stop_price),
+            _ => None,
+        }
+    }
+
+    /// GTC for the types which require a time in force
+    pub fn time_in_force(&self) -> Option<TimeInForce> {
+        self.order_type().requires_time_in_force().then_some(TimeInForce::GTC)
+    }
+}
+
+/// Margin order
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct MarginOrder {
@@ -586,13 +919,15 @@ pub struct MarginOrder {
     pub side_effect_type: SideEffectType,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Either order_id or orig_client_order_id must be set, only the fields which are set are sent
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct MarginOrderCancellation {
     pub symbol: String,
-    pub order_id: u64,
-    pub orig_client_order_id: String,
-    pub new_client_order_id: String,
+    pub order_id: Option<u64>,
+    pub orig_client_order_id: Option<String>,
+    /// Used to uniquely identify this cancel. Automatically generated by default.
+    pub new_client_order_id: Option<String>,
     pub is_isolated: Option<String>,
 }
 
@@ -712,6 +1047,23 @@ pub struct OCOOrderReport {
     pub iceberg_qty: Option<Decimal>,
 }
 
+/// Spot order list (OCO, OTO or OTOCO)
+/// order_reports are only returned when placing or canceling a list
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderList {
+    pub order_list_id: u64,
+    pub contingency_type: ContingencyType,
+    pub list_status_type: OCOStatus,
+    pub list_order_status: OCOOrderStatus,
+    pub list_client_order_id: Option<String>,
+    pub transaction_time: u128,
+    pub symbol: String,
+    pub orders: Vec<OCOOrderDetail>,
+    #[serde(default)]
+    pub order_reports: Vec<OCOOrderReport>,
+}
+
 /// archived and is_isolated are only applicable to certain endpoints
 /// refer to Binance documentation for full disclosure
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -1219,6 +1571,25 @@ pub enum SymbolStatus {
     Other,
 }
 
+/// Self-trade prevention mode, decides which orders expire when an order would match
+/// against an order of the same account or trade group
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SelfTradePreventionMode {
+    /// No Self-Trade Prevention
+    None,
+    /// Expire taker order when STP trigger
+    ExpireTaker,
+    /// Expire maker order when STP trigger
+    ExpireMaker,
+    /// Expire taker and maker order when STP trigger
+    ExpireBoth,
+    /// Decrease the quantity of both orders by the overlapping quantity
+    Decrement,
+    #[serde(other)]
+    Other,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum SymbolPermission {
@@ -1267,6 +1638,11 @@ pub enum OrderStatus {
     Expired,
     /// The order was canceled by the exchange due to STP trigger. (e.g. an order with EXPIRE_TAKER will match with existing orders on the book with the same account or same tradeGroupId)
     ExpiredInMatch,
+    /// The pending order of an order list, waiting for the working order to be fully filled.
+    PendingNew,
+    /// Any status not modeled above
+    #[serde(other)]
+    Other,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -1274,6 +1650,7 @@ pub enum OrderStatus {
 pub enum OCOStatus {
     Response,
     ExecStarted,
+    Updated,
     AllDone,
 }
 
@@ -1308,6 +1685,7 @@ pub struct MarginOCOOrderQuery {
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum ContingencyType {
     OCO,
+    OTO,
     #[serde(other)]
     Other,
 }
@@ -2021,7 +2399,7 @@ pub(crate) mod string_or_float_opt {
     use std::fmt;
 
     use crate::rest_model::Decimal;
-    use serde::{Deserializer, Serializer};
+    use serde::{de, Deserialize, Deserializer, Serializer};
 
     pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -2038,7 +2416,19 @@ pub(crate) mod string_or_float_opt {
     where
         D: Deserializer<'de>,
     {
-        Ok(Some(crate::rest_model::string_or_float::deserialize(deserializer)?))
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum StringOrFloat {
+            String(String),
+            Float(Decimal),
+        }
+
+        match StringOrFloat::deserialize(deserializer)? {
+            // Some fields are sent as an empty string when they do not apply
+            StringOrFloat::String(s) if s.is_empty() => Ok(None),
+            StringOrFloat::String(s) => s.parse().map(Some).map_err(de::Error::custom),
+            StringOrFloat::Float(i) => Ok(Some(i)),
+        }
     }
 }
 
@@ -2142,7 +2532,8 @@ pub(crate) mod string_or_bool {
 mod test {
     use std::path::PathBuf;
 
-    use crate::rest_model::ExchangeInformation;
+    use crate::rest_model::{ExchangeInformation, Filters, OrderList, OrderStatus};
+    use rust_decimal_macros::dec;
 
     #[test]
     fn exchange_info_serde() {
@@ -2152,4 +2543,75 @@ mod test {
         let result = serde_json::from_str::<ExchangeInformation>(&fc);
         assert!(result.is_ok(), "{result:?}");
     }
+
+    #[test]
+    fn filters_serde() {
+        let fc = r#"[
+            {"filterType":"NOTIONAL","minNotional":"5.00000000","applyMinToMarket":true,"maxNotional":"9000000.00000000","applyMaxToMarket":false,"avgPriceMins":5},
+            {"filterType":"PERCENT_PRICE_BY_SIDE","bidMultiplierUp":"5","bidMultiplierDown":"0.2","askMultiplierUp":"5","askMultiplierDown":"0.2","avgPriceMins":5},
+            {"filterType":"TRAILING_DELTA","minTrailingAboveDelta":10,"maxTrailingAboveDelta":2000,"minTrailingBelowDelta":10,"maxTrailingBelowDelta":2000},
+            {"filterType":"EXCHANGE_MAX_NUM_ICEBERG_ORDERS","maxNumIcebergOrders":10000},
+            {"filterType":"SOME_FUTURE_FILTER","someField":"1.0"}
+        ]"#;
+        let result = serde_json::from_str::<Vec<Filters>>(fc);
+        assert!(result.is_ok(), "{result:?}");
+        assert_eq!(result.unwrap().last(), Some(&Filters::Others));
+    }
+
+    #[test]
+    fn otoco_order_list_serde() {
+        let fc = r#"{
+            "orderListId": 629,
+            "contingencyType": "OTO",
+            "listStatusType": "EXEC_STARTED",
+            "listOrderStatus": "EXECUTING",
+            "listClientOrderId": "GaeJHjZPasPItFj4x7Mqm6",
+            "transactionTime": 1712291372842,
+            "symbol": "LTCBNB",
+            "orders": [
+                {"symbol": "LTCBNB", "orderId": 6, "clientOrderId": "7QxG7BpuDjvo6DXMkn2UAT"},
+                {"symbol": "LTCBNB", "orderId": 7, "clientOrderId": "zqhsgGDEcdhxy2oza2Ljxd"},
+                {"symbol": "LTCBNB", "orderId": 8, "clientOrderId": "jVmvH7bLpU2u2SBmbVCNkR"}
+            ],
+            "orderReports": [
+                {"symbol": "LTCBNB", "orderId": 6, "orderListId": 629, "clientOrderId": "7QxG7BpuDjvo6DXMkn2UAT", "transactTime": 1712291372842, "price": "1.00000000", "origQty": "1.00000000", "executedQty": "0.00000000", "cummulativeQuoteQty": "0.00000000", "status": "NEW", "timeInForce": "GTC", "type": "LIMIT", "side": "BUY", "workingTime": 1712291372842, "selfTradePreventionMode": "NONE"},
+                {"symbol": "LTCBNB", "orderId": 7, "orderListId": 629, "clientOrderId": "zqhsgGDEcdhxy2oza2Ljxd", "transactTime": 1712291372842, "price": "0.00000000", "origQty": "5.00000000", "executedQty": "0.00000000", "cummulativeQuoteQty": "0.00000000", "status": "PENDING_NEW", "timeInForce": "GTC", "type": "STOP_LOSS", "side": "SELL", "stopPrice": "5.00000000", "workingTime": -1, "selfTradePreventionMode": "NONE"},
+                {"symbol": "LTCBNB", "orderId": 8, "orderListId": 629, "clientOrderId": "jVmvH7bLpU2u2SBmbVCNkR", "transactTime": 1712291372842, "price": "5.00000000", "origQty": "5.00000000", "executedQty": "0.00000000", "cummulativeQuoteQty": "0.00000000", "status": "PENDING_NEW", "timeInForce": "GTC", "type": "LIMIT_MAKER", "side": "SELL", "workingTime": -1, "selfTradePreventionMode": "NONE"}
+            ]
+        }"#;
+        let result = serde_json::from_str::<OrderList>(fc);
+        assert!(result.is_ok(), "{result:?}");
+        let statuses: Vec<OrderStatus> = result.unwrap().order_reports.into_iter().map(|r| r.status).collect();
+        assert_eq!(
+            statuses,
+            vec![OrderStatus::New, OrderStatus::PendingNew, OrderStatus::PendingNew]
+        );
+        let status = serde_json::from_str::<OrderStatus>(r#""SOME_FUTURE_STATUS""#);
+        assert_eq!(status.unwrap(), OrderStatus::Other);
+    }
+
+    #[test]
+    fn string_or_float_opt_serde() {
+        #[derive(Debug, Serialize, Deserialize)]
+        struct Optional {
+            #[serde(default, with = "crate::rest_model::string_or_float_opt")]
+            value: Option<rust_decimal::Decimal>,
+        }
+
+        let value = |json: &str| serde_json::from_str::<Optional>(json).map(|o| o.value);
+        assert_eq!(value(r#"{"value": ""}"#).unwrap(), None);
+        assert_eq!(value(r#"{"value": "0.00071003"}"#).unwrap(), Some(dec!(0.00071003)));
+        assert_eq!(value(r#"{"value": 1.5}"#).unwrap(), Some(dec!(1.5)));
+        assert_eq!(value(r#"{"value": 2}"#).unwrap(), Some(dec!(2)));
+        assert_eq!(value(r#"{}"#).unwrap(), None);
+        assert!(value(r#"{"value": "abc"}"#).is_err());
+        assert_eq!(
+            serde_json::to_string(&Optional { value: Some(dec!(1.5)) }).unwrap(),
+            r#"{"value":"1.5"}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&Optional { value: None }).unwrap(),
+            r#"{"value":null}"#
+        );
+    }
 }
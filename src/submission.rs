@@ -0,0 +1,399 @@
+//! Idempotent order submission.
+//!
+//! When placing an order times out or the server answers with a 5xx, the order may or may not have
+//! reached the matching engine. Blindly retrying can create a duplicate order, giving up can leave
+//! an order on the book that the caller does not know about.
+//!
+//! [`OrderSubmitter`] always sends orders with a client order id, and on ambiguous failures queries
+//! the order by that id before deciding whether it has to be sent again.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use crate::account::{Account, OrderRequest, OrderStatusRequest};
+use crate::errors::*;
+use crate::rest_model::{Order, Transaction};
+use crate::util::get_timestamp;
+
+/// Order id is unknown to the exchange
+const UNKNOWN_ORDER: i32 = -2013;
+/// Spot order rejected, the message tells if it is a duplicate
+const NEW_ORDER_REJECTED: i32 = -2010;
+/// Futures client order id already in use
+const DUPLICATED_CLIENT_ORDER_ID: i32 = -4116;
+/// The message bus answered unexpectedly, execution status unknown
+const UNEXPECTED_RESPONSE: i32 = -1006;
+/// The backend timed out, execution status unknown
+const BACKEND_TIMEOUT: i32 = -1007;
+
+/// Maximum length of a client order id
+pub const MAX_CLIENT_ORDER_ID_LEN: usize = 36;
+/// Digits reserved for the sequence of generated client order ids
+const SEQUENCE_DIGITS: usize = 10;
+
+/// Generates client order ids of the form `{prefix}{session}-{sequence}`.
+///
+/// The session defaults to the creation time in base 36, so that ids do not collide across restarts,
+/// and can be fixed with [`ClientOrderIdGenerator::with_session`] for fully deterministic ids.
+/// Binance limits client order ids to 36 characters, generators whose ids could exceed it, with a
+/// sequence of up to 10 digits and the reserved suffix, cannot be created.
+#[derive(Debug)]
+pub struct ClientOrderIdGenerator {
+    prefix: String,
+    session: String,
+    sequence: AtomicU64,
+    suffix_len: usize,
+}
+
+impl ClientOrderIdGenerator {
+    pub fn new<S: Into<String>>(prefix: S) -> Result<Self> { Self::with_session(prefix, to_base36(get_timestamp()?)) }
+
+    pub fn with_session<S: Into<String>, T: Into<String>>(prefix: S, session: T) -> Result<Self> {
+        let ids = Self {
+            prefix: prefix.into(),
+            session: session.into(),
+            sequence: AtomicU64::new(0),
+            suffix_len: 0,
+        };
+        ids.check_len()?;
+        Ok(ids)
+    }
+
+    /// Reserves room for a suffix of up to this length appended to the generated ids by the caller,
+    /// a larger reservation is kept
+    pub fn reserve_suffix(mut self, suffix_len: usize) -> Result<Self> {
+        self.suffix_len = self.suffix_len.max(suffix_len);
+        self.check_len()?;
+        Ok(self)
+    }
+
+    /// Length of the longest id, reserved suffix included
+    pub fn max_len(&self) -> usize { self.prefix.len() + self.session.len() + 1 + SEQUENCE_DIGITS + self.suffix_len }
+
+    /// Returns the next client order id
+    pub fn next_id(&self) -> String {
+        let sequence = self.sequence.fetch_add(1, Ordering::Relaxed);
+        format!("{}{}-{}", self.prefix, self.session, sequence)
+    }
+
+    /// Whether the client order id was generated with this prefix
+    pub fn owns(&self, client_order_id: &str) -> bool { client_order_id.starts_with(&self.prefix) }
+
+    fn check_len(&self) -> Result<()> {
+        if self.max_len() > MAX_CLIENT_ORDER_ID_LEN {
+            return Err(Error::ClientOrderIdTooLong {
+                prefix: self.prefix.clone(),
+                max_len: self.max_len(),
+            });
+        }
+        Ok(())
+    }
+}
+
+fn to_base36(mut n: u64) -> String {
+    const DIGITS: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+    let mut digits = Vec::new();
+    loop {
+        digits.push(DIGITS[(n % 36) as usize]);
+        n /= 36;
+        if n == 0 {
+            break;
+        }
+    }
+    digits.reverse();
+    String::from_utf8(digits).unwrap_or_default()
+}
+
+/// Outcome of an idempotent submission
+#[derive(Debug, Clone)]
+pub enum Submission<P, R> {
+    /// The exchange acknowledged the order
+    Placed(P),
+    /// The order reply was lost, but the order was found by its client order id
+    Recovered(R),
+}
+
+/// Whether the request may or may not have been executed
+pub fn is_ambiguous(error: &Error) -> bool {
+    match error {
+        Error::ReqError(e) => e.is_timeout() || (e.is_request() && !e.is_connect()),
+        Error::InternalServerError | Error::ServiceUnavailable => true,
+        Error::BinanceError { response } => matches!(response.code, UNEXPECTED_RESPONSE | BACKEND_TIMEOUT),
+        Error::Msg(msg) => msg.starts_with("Received response: 5"),
+        _ => false,
+    }
+}
+
+/// Whether the exchange does not know the queried order
+pub fn is_unknown_order(error: &Error) -> bool {
+    matches!(error, Error::BinanceError { response } if response.code == UNKNOWN_ORDER)
+}
+
+/// Whether the exchange rejected the order because its client order id is already in use
+pub fn is_duplicate_order(error: &Error) -> bool {
+    match error {
+        Error::BinanceError { response } => {
+            response.code == DUPLICATED_CLIENT_ORDER_ID
+                || (response.code == NEW_ORDER_REJECTED && response.msg.contains("Duplicate"))
+        }
+        _ => false,
+    }
+}
+
+/// Places orders so that retries never create duplicate orders
+#[derive(Debug)]
+pub struct OrderSubmitter {
+    ids: ClientOrderIdGenerator,
+    max_attempts: u32,
+    retry_delay: Duration,
+}
+
+impl OrderSubmitter {
+    /// A submitter generating client order ids with this prefix, sending an order at most 3 times
+    pub fn new<S: Into<String>>(prefix: S) -> Result<Self> { Ok(Self::with_ids(ClientOrderIdGenerator::new(prefix)?)) }
+
+    pub fn with_ids(ids: ClientOrderIdGenerator) -> Self {
+        Self {
+            ids,
+            max_attempts: 3,
+            retry_delay: Duration::from_millis(500),
+        }
+    }
+
+    /// Sets how many times an order can be sent, including the first attempt
+    pub fn set_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets how long to wait after an ambiguous failure before querying the order
+    pub fn set_retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    pub fn ids(&self) -> &ClientOrderIdGenerator { &self.ids }
+
+    /// Place a spot order, assigning a client order id if it has none
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, account::*, config::*, rest_model::*, submission::*};
+    /// use rust_decimal_macros::dec;
+    /// let account: Account = Binance::new_with_env(&Config::testnet());
+    /// let submitter = OrderSubmitter::new("bot1-").unwrap();
+    /// let order = OrderRequest::limit("BTCUSDT", OrderSide::Buy, dec!(0.001), dec!(30000));
+    /// let submission = tokio_test::block_on(submitter.place_order(&account, order));
+    /// assert!(submission.is_ok(), "{:?}", submission);
+    /// ```
+    pub async fn place_order(
+        &self,
+        account: &Account,
+        mut order: OrderRequest,
+    ) -> Result<Submission<Transaction, Order>> {
+        let client_order_id = order
+            .new_client_order_id
+            .get_or_insert_with(|| self.ids.next_id())
+            .clone();
+        let symbol = order.symbol.clone();
+        self.submit(
+            || account.place_order(order.clone()),
+            || {
+                account.order_status(OrderStatusRequest {
+                    symbol: symbol.clone(),
+                    orig_client_order_id: Some(client_order_id.clone()),
+                    ..OrderStatusRequest::default()
+                })
+            },
+        )
+        .await
+    }
+
+    /// Place a futures order, assigning a client order id if it has none
+    #[cfg(feature = "futures_api")]
+    pub async fn place_futures_order(
+        &self,
+        account: &crate::futures::account::FuturesAccount,
+        mut order: crate::futures::account::OrderRequest,
+    ) -> Result<Submission<crate::futures::rest_model::Transaction, crate::futures::rest_model::Transaction>> {
+        use crate::futures::account::GetOrderRequest;
+
+        let client_order_id = order
+            .new_client_order_id
+            .get_or_insert_with(|| self.ids.next_id())
+            .clone();
+        let symbol = order.symbol.clone();
+        self.submit(
+            || account.place_order(order.clone()),
+            || {
+                account.get_order(Some(GetOrderRequest {
+                    symbol: symbol.clone(),
+                    order_id: None,
+                    orig_client_order_id: Some(client_order_id.clone()),
+                }))
+            },
+        )
+        .await
+    }
+
+    /// Sends the order until it is acknowledged, resolving ambiguous failures by querying the order
+    /// before sending it again
+    async fn submit<P, R, PF, PFut, RF, RFut>(&self, place: PF, query: RF) -> Result<Submission<P, R>>
+    where
+        PF: Fn() -> PFut,
+        PFut: Future<Output = Result<P>>,
+        RF: Fn() -> RFut,
+        RFut: Future<Output = Result<R>>,
+    {
+        let mut attempt = 1;
+        loop {
+            let error = match place().await {
+                Ok(placed) => return Ok(Submission::Placed(placed)),
+                Err(e) if is_ambiguous(&e) || is_duplicate_order(&e) => e,
+                Err(e) => return Err(e),
+            };
+            tokio::time::sleep(self.retry_delay).await;
+            match self.resolve(&query).await? {
+                Some(found) => return Ok(Submission::Recovered(found)),
+                None if attempt < self.max_attempts => attempt += 1,
+                None => return Err(error),
+            }
+        }
+    }
+
+    /// Queries the order by its client order id, None if the exchange does not know it
+    async fn resolve<R, RF, RFut>(&self, query: &RF) -> Result<Option<R>>
+    where
+        RF: Fn() -> RFut,
+        RFut: Future<Output = Result<R>>,
+    {
+        let mut attempt = 1;
+        loop {
+            match query().await {
+                Ok(found) => return Ok(Some(found)),
+                Err(e) if is_unknown_order(&e) => return Ok(None),
+                Err(e) if is_ambiguous(&e) && attempt < self.max_attempts => {
+                    attempt += 1;
+                    tokio::time::sleep(self.retry_delay).await;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::atomic::AtomicU32;
+
+    fn binance_error(code: i32, msg: &str) -> Error {
+        Error::BinanceError {
+            response: BinanceContentError::new(code, msg),
+        }
+    }
+
+    fn submitter() -> OrderSubmitter {
+        OrderSubmitter::with_ids(ClientOrderIdGenerator::with_session("bot-", "s").unwrap())
+            .set_retry_delay(Duration::ZERO)
+    }
+
+    #[test]
+    fn base36() {
+        assert_eq!(to_base36(0), "0");
+        assert_eq!(to_base36(35), "z");
+        assert_eq!(to_base36(36), "10");
+        assert_eq!(to_base36(1_700_000_000_000), "loyw3v28");
+    }
+
+    #[test]
+    fn generated_ids() {
+        let ids = ClientOrderIdGenerator::with_session("bot-", "abc").unwrap();
+        assert_eq!(ids.next_id(), "bot-abc-0");
+        assert_eq!(ids.next_id(), "bot-abc-1");
+        assert!(ids.owns("bot-abc-7"));
+        assert!(!ids.owns("manual-1"));
+        assert_eq!(ids.max_len(), 18);
+
+        let long_prefix = "a".repeat(20);
+        assert!(matches!(
+            ClientOrderIdGenerator::with_session(long_prefix, "abcdefgh"),
+            Err(Error::ClientOrderIdTooLong { max_len: 39, .. })
+        ));
+        let ids = ClientOrderIdGenerator::with_session("a".repeat(12), "abcdefgh").unwrap();
+        assert_eq!(ids.max_len(), 31);
+        let ids = ids.reserve_suffix(5).unwrap();
+        assert_eq!(ids.max_len(), MAX_CLIENT_ORDER_ID_LEN);
+        assert!(ids.reserve_suffix(2).is_ok());
+        let ids = ClientOrderIdGenerator::with_session("a".repeat(12), "abcdefgh").unwrap();
+        assert!(ids.reserve_suffix(6).is_err());
+    }
+
+    #[test]
+    fn ambiguous_errors() {
+        assert!(is_ambiguous(&Error::InternalServerError));
+        assert!(is_ambiguous(&Error::ServiceUnavailable));
+        assert!(is_ambiguous(&binance_error(
+            BACKEND_TIMEOUT,
+            "Timeout waiting for response"
+        )));
+        assert!(is_ambiguous(&Error::Msg("Received response: 502".to_string())));
+        assert!(!is_ambiguous(&binance_error(-2010, "Account has insufficient balance")));
+        assert!(!is_ambiguous(&Error::Msg("Received response: 404".to_string())));
+        assert!(!is_ambiguous(&Error::InvalidPrice));
+        assert!(is_duplicate_order(&binance_error(
+            NEW_ORDER_REJECTED,
+            "Duplicate order sent."
+        )));
+        assert!(is_unknown_order(&binance_error(UNKNOWN_ORDER, "Order does not exist.")));
+    }
+
+    #[test]
+    fn resubmits_when_the_order_is_not_found() {
+        let placed = AtomicU32::new(0);
+        let queried = AtomicU32::new(0);
+        let submission = tokio_test::block_on(submitter().submit(
+            || async {
+                match placed.fetch_add(1, Ordering::Relaxed) {
+                    0 => Err(Error::ServiceUnavailable),
+                    attempt => Ok(attempt),
+                }
+            },
+            || async {
+                queried.fetch_add(1, Ordering::Relaxed);
+                Err::<u32, _>(binance_error(UNKNOWN_ORDER, "Order does not exist."))
+            },
+        ));
+        assert!(matches!(submission, Ok(Submission::Placed(1))));
+        assert_eq!(placed.load(Ordering::Relaxed), 2);
+        assert_eq!(queried.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn recovers_the_order_found_by_client_id() {
+        let placed = AtomicU32::new(0);
+        let submission = tokio_test::block_on(submitter().submit(
+            || async {
+                placed.fetch_add(1, Ordering::Relaxed);
+                Err::<u32, _>(Error::InternalServerError)
+            },
+            || async { Ok("found") },
+        ));
+        assert!(matches!(submission, Ok(Submission::Recovered("found"))));
+        assert_eq!(placed.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn gives_up_after_max_attempts() {
+        let placed = AtomicU32::new(0);
+        let submission = tokio_test::block_on(submitter().set_max_attempts(2).submit(
+            || async {
+                placed.fetch_add(1, Ordering::Relaxed);
+                Err::<u32, _>(Error::InternalServerError)
+            },
+            || async { Err::<u32, _>(binance_error(UNKNOWN_ORDER, "Order does not exist.")) },
+        ));
+        assert!(matches!(submission, Err(Error::InternalServerError)));
+        assert_eq!(placed.load(Ordering::Relaxed), 2);
+    }
+}
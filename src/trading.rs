@@ -0,0 +1,910 @@
+//! Venue-agnostic order entry.
+//!
+//! Spot, margin and futures accounts take different request and response types for the same
+//! operations. The [`Trading`] trait maps them onto common ones, [`PlaceOrder`], [`CancelOrder`],
+//! [`VenueOrder`] and [`VenueBalance`], so that portfolio level code can be written once and the
+//! venue picked by configuration with [`Venue`]. [`MockTrading`] keeps orders and balances in memory
+//! for tests.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Mutex, MutexGuard};
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::account::{Account, OrderCancellation, OrderRequest};
+use crate::api::Binance;
+use crate::config::Config;
+use crate::errors::*;
+use crate::rest_model::{Order, OrderSide, OrderStatus, OrderType, TimeInForce, Transaction};
+
+#[cfg(feature = "futures_api")]
+use crate::futures::{account::FuturesAccount, rest_model as futures_model};
+#[cfg(feature = "margin_api")]
+use crate::margin::Margin;
+#[cfg(feature = "margin_api")]
+use crate::rest_model::{MarginOrder, MarginOrderCancellation, MarginOrderResult, MarginOrderState};
+
+/// Order types supported by every venue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OrderKind {
+    Market,
+    Limit,
+    /// Market order triggered by a stop price
+    StopMarket,
+    /// Limit order triggered by a stop price
+    StopLimit,
+    /// Market order triggered by a take profit price
+    TakeProfitMarket,
+    /// Limit order triggered by a take profit price
+    TakeProfitLimit,
+    /// A venue specific type, such as `LIMIT_MAKER` or `TRAILING_STOP_MARKET`, which cannot be placed
+    /// through [`Trading`]
+    Other,
+}
+
+impl OrderKind {
+    pub fn requires_price(&self) -> bool { matches!(self, Self::Limit | Self::StopLimit | Self::TakeProfitLimit) }
+
+    pub fn requires_stop_price(&self) -> bool {
+        matches!(
+            self,
+            Self::StopMarket | Self::StopLimit | Self::TakeProfitMarket | Self::TakeProfitLimit
+        )
+    }
+}
+
+/// An order to place on any venue
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaceOrder {
+    pub symbol: String,
+    pub side: OrderSide,
+    pub kind: OrderKind,
+    pub qty: Decimal,
+    pub price: Option<Decimal>,
+    pub stop_price: Option<Decimal>,
+    /// Defaults to `GTC` for orders with a limit price
+    pub time_in_force: Option<TimeInForce>,
+    pub client_order_id: Option<String>,
+    /// Only supported by futures
+    pub reduce_only: bool,
+}
+
+impl PlaceOrder {
+    fn new(symbol: impl Into<String>, side: OrderSide, kind: OrderKind, qty: Decimal) -> Self {
+        Self {
+            symbol: symbol.into(),
+            side,
+            kind,
+            qty,
+            price: None,
+            stop_price: None,
+            time_in_force: None,
+            client_order_id: None,
+            reduce_only: false,
+        }
+    }
+
+    pub fn market(symbol: impl Into<String>, side: OrderSide, qty: Decimal) -> Self {
+        Self::new(symbol, side, OrderKind::Market, qty)
+    }
+
+    pub fn limit(symbol: impl Into<String>, side: OrderSide, qty: Decimal, price: Decimal) -> Self {
+        Self {
+            price: Some(price),
+            ..Self::new(symbol, side, OrderKind::Limit, qty)
+        }
+    }
+
+    pub fn stop_market(symbol: impl Into<String>, side: OrderSide, qty: Decimal, stop_price: Decimal) -> Self {
+        Self {
+            stop_price: Some(stop_price),
+            ..Self::new(symbol, side, OrderKind::StopMarket, qty)
+        }
+    }
+
+    pub fn stop_limit(
+        symbol: impl Into<String>,
+        side: OrderSide,
+        qty: Decimal,
+        price: Decimal,
+        stop_price: Decimal,
+    ) -> Self {
+        Self {
+            price: Some(price),
+            stop_price: Some(stop_price),
+            ..Self::new(symbol, side, OrderKind::StopLimit, qty)
+        }
+    }
+
+    pub fn set_time_in_force(mut self, time_in_force: TimeInForce) -> Self {
+        self.time_in_force = Some(time_in_force);
+        self
+    }
+
+    pub fn set_client_order_id(mut self, client_order_id: impl Into<String>) -> Self {
+        self.client_order_id = Some(client_order_id.into());
+        self
+    }
+
+    pub fn set_reduce_only(mut self, reduce_only: bool) -> Self {
+        self.reduce_only = reduce_only;
+        self
+    }
+
+    /// Checks the fields required by the order kind
+    pub fn valid(&self) -> Result<()> {
+        let error = |msg: &str| {
+            Err(Error::InvalidOrderError {
+                msg: format!("{msg} for a {:?} order on {}", self.kind, self.symbol),
+            })
+        };
+        if self.kind == OrderKind::Other {
+            return error("unsupported order kind");
+        }
+        if self.qty <= Decimal::ZERO {
+            return error("quantity must be positive");
+        }
+        if self.kind.requires_price() != self.price.is_some() {
+            return error(if self.price.is_some() {
+                "unexpected price"
+            } else {
+                "missing price"
+            });
+        }
+        if self.kind.requires_stop_price() != self.stop_price.is_some() {
+            return error(if self.stop_price.is_some() {
+                "unexpected stop price"
+            } else {
+                "missing stop price"
+            });
+        }
+        Ok(())
+    }
+
+    fn time_in_force(&self) -> Option<TimeInForce> {
+        if self.kind.requires_price() {
+            Some(self.time_in_force.clone().unwrap_or(TimeInForce::GTC))
+        } else {
+            None
+        }
+    }
+
+    fn spot_order_type(&self) -> Result<OrderType> {
+        match self.kind {
+            OrderKind::Market => Ok(OrderType::Market),
+            OrderKind::Limit => Ok(OrderType::Limit),
+            OrderKind::StopMarket => Ok(OrderType::StopLoss),
+            OrderKind::StopLimit => Ok(OrderType::StopLossLimit),
+            OrderKind::TakeProfitMarket => Ok(OrderType::TakeProfit),
+            OrderKind::TakeProfitLimit => Ok(OrderType::TakeProfitLimit),
+            OrderKind::Other => Err(Error::InvalidOrderError {
+                msg: "unsupported order kind".to_string(),
+            }),
+        }
+    }
+
+    #[cfg(feature = "futures_api")]
+    fn futures_order_type(&self) -> Result<futures_model::OrderType> {
+        use futures_model::OrderType;
+        match self.kind {
+            OrderKind::Market => Ok(OrderType::Market),
+            OrderKind::Limit => Ok(OrderType::Limit),
+            OrderKind::StopMarket => Ok(OrderType::StopMarket),
+            OrderKind::StopLimit => Ok(OrderType::Stop),
+            OrderKind::TakeProfitMarket => Ok(OrderType::TakeProfitMarket),
+            OrderKind::TakeProfitLimit => Ok(OrderType::TakeProfit),
+            OrderKind::Other => Err(Error::InvalidOrderError {
+                msg: "unsupported order kind".to_string(),
+            }),
+        }
+    }
+
+    fn spot_only(&self) -> Result<()> {
+        if self.reduce_only {
+            return Err(Error::InvalidOrderError {
+                msg: "reduce only orders are only supported by futures".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// An order to cancel, by exchange id or by client id
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CancelOrder {
+    pub symbol: String,
+    pub order_id: Option<u64>,
+    pub client_order_id: Option<String>,
+}
+
+impl CancelOrder {
+    pub fn by_id(symbol: impl Into<String>, order_id: u64) -> Self {
+        Self {
+            symbol: symbol.into(),
+            order_id: Some(order_id),
+            client_order_id: None,
+        }
+    }
+
+    pub fn by_client_id(symbol: impl Into<String>, client_order_id: impl Into<String>) -> Self {
+        Self {
+            symbol: symbol.into(),
+            order_id: None,
+            client_order_id: Some(client_order_id.into()),
+        }
+    }
+
+    fn valid(&self) -> Result<()> {
+        if self.order_id.is_none() && self.client_order_id.is_none() {
+            return Err(Error::InvalidOrderError {
+                msg: "either an order id or a client order id is required to cancel".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// An order as reported by any venue
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VenueOrder {
+    pub symbol: String,
+    pub order_id: u64,
+    pub client_order_id: String,
+    pub side: OrderSide,
+    pub kind: OrderKind,
+    pub price: Decimal,
+    pub orig_qty: Decimal,
+    pub executed_qty: Decimal,
+    pub status: OrderStatus,
+}
+
+impl VenueOrder {
+    pub fn remaining_qty(&self) -> Decimal { self.orig_qty - self.executed_qty }
+
+    pub fn is_open(&self) -> bool { matches!(self.status, OrderStatus::New | OrderStatus::PartiallyFilled) }
+}
+
+/// Balance of an asset on any venue
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VenueBalance {
+    pub asset: String,
+    /// Amount available for new orders
+    pub free: Decimal,
+    /// Amount locked in open orders or, for futures, used as margin
+    pub locked: Decimal,
+}
+
+impl VenueBalance {
+    pub fn total(&self) -> Decimal { self.free + self.locked }
+}
+
+/// Order entry operations common to all venues
+pub trait Trading {
+    /// Places an order
+    fn place(&self, order: PlaceOrder) -> impl Future<Output = Result<VenueOrder>> + Send;
+
+    /// Cancels an order
+    fn cancel(&self, order: CancelOrder) -> impl Future<Output = Result<()>> + Send;
+
+    /// Open orders of a symbol
+    fn open_orders(&self, symbol: &str) -> impl Future<Output = Result<Vec<VenueOrder>>> + Send;
+
+    /// Balances of all assets
+    fn balances(&self) -> impl Future<Output = Result<Vec<VenueBalance>>> + Send;
+}
+
+fn spot_kind(order_type: &OrderType) -> OrderKind {
+    match order_type {
+        OrderType::Market => OrderKind::Market,
+        OrderType::Limit => OrderKind::Limit,
+        OrderType::StopLoss => OrderKind::StopMarket,
+        OrderType::StopLossLimit => OrderKind::StopLimit,
+        OrderType::TakeProfit => OrderKind::TakeProfitMarket,
+        OrderType::TakeProfitLimit => OrderKind::TakeProfitLimit,
+        OrderType::LimitMaker | OrderType::Other => OrderKind::Other,
+    }
+}
+
+impl From<Transaction> for VenueOrder {
+    fn from(transaction: Transaction) -> Self {
+        Self {
+            kind: spot_kind(&transaction.order_type),
+            symbol: transaction.symbol,
+            order_id: transaction.order_id,
+            client_order_id: transaction.client_order_id,
+            side: transaction.side,
+            price: transaction.price,
+            orig_qty: transaction.orig_qty,
+            executed_qty: transaction.executed_qty,
+            status: transaction.status,
+        }
+    }
+}
+
+impl From<Order> for VenueOrder {
+    fn from(order: Order) -> Self {
+        Self {
+            kind: spot_kind(&order.order_type),
+            symbol: order.symbol,
+            order_id: order.order_id,
+            client_order_id: order.client_order_id,
+            side: order.side,
+            price: order.price,
+            orig_qty: order.orig_qty,
+            executed_qty: order.executed_qty,
+            status: order.status,
+        }
+    }
+}
+
+impl Trading for Account {
+    async fn place(&self, order: PlaceOrder) -> Result<VenueOrder> {
+        order.valid()?;
+        order.spot_only()?;
+        let request = OrderRequest {
+            symbol: order.symbol.clone(),
+            side: order.side.clone(),
+            order_type: order.spot_order_type()?,
+            time_in_force: order.time_in_force(),
+            quantity: Some(order.qty),
+            price: order.price,
+            stop_price: order.stop_price,
+            new_client_order_id: order.client_order_id.clone(),
+            ..OrderRequest::default()
+        };
+        self.place_order(request).await.map(VenueOrder::from)
+    }
+
+    async fn cancel(&self, order: CancelOrder) -> Result<()> {
+        order.valid()?;
+        let cancellation = OrderCancellation {
+            symbol: order.symbol,
+            order_id: order.order_id,
+            orig_client_order_id: order.client_order_id,
+            ..OrderCancellation::default()
+        };
+        self.cancel_order(cancellation).await.map(|_| ())
+    }
+
+    async fn open_orders(&self, symbol: &str) -> Result<Vec<VenueOrder>> {
+        let orders = self.get_open_orders(symbol).await?;
+        Ok(orders.into_iter().map(VenueOrder::from).collect())
+    }
+
+    async fn balances(&self) -> Result<Vec<VenueBalance>> {
+        let information = self.get_account().await?;
+        Ok(information
+            .balances
+            .into_iter()
+            .map(|balance| VenueBalance {
+                asset: balance.asset,
+                free: balance.free,
+                locked: balance.locked,
+            })
+            .collect())
+    }
+}
+
+#[cfg(feature = "margin_api")]
+impl From<MarginOrderResult> for VenueOrder {
+    fn from(result: MarginOrderResult) -> Self {
+        Self {
+            kind: spot_kind(&result.order_type),
+            symbol: result.symbol,
+            order_id: result.order_id,
+            client_order_id: result.client_order_id,
+            side: result.side,
+            price: result.price,
+            orig_qty: result.orig_qty,
+            executed_qty: result.executed_qty,
+            status: result.status,
+        }
+    }
+}
+
+#[cfg(feature = "margin_api")]
+impl From<MarginOrderState> for VenueOrder {
+    fn from(state: MarginOrderState) -> Self {
+        Self {
+            kind: spot_kind(&state.order_type),
+            symbol: state.symbol,
+            order_id: state.order_id,
+            client_order_id: state.client_order_id,
+            side: state.side,
+            price: state.price,
+            orig_qty: state.orig_qty,
+            executed_qty: state.executed_qty,
+            status: state.status,
+        }
+    }
+}
+
+/// Only the identifier which is set is sent
+#[cfg(feature = "margin_api")]
+impl From<CancelOrder> for MarginOrderCancellation {
+    fn from(order: CancelOrder) -> Self {
+        Self {
+            symbol: order.symbol,
+            order_id: order.order_id,
+            orig_client_order_id: order.client_order_id,
+            ..Self::default()
+        }
+    }
+}
+
+/// Trades on the cross margin account, without borrowing or repaying
+#[cfg(feature = "margin_api")]
+impl Trading for Margin {
+    async fn place(&self, order: PlaceOrder) -> Result<VenueOrder> {
+        order.valid()?;
+        order.spot_only()?;
+        let request = MarginOrder {
+            symbol: order.symbol.clone(),
+            side: order.side.clone(),
+            order_type: order.spot_order_type()?,
+            quantity: Some(order.qty),
+            quote_order_qty: None,
+            price: order.price,
+            stop_price: order.stop_price,
+            new_client_order_id: order.client_order_id.clone(),
+            iceberg_qty: None,
+            new_order_resp_type: crate::rest_model::OrderResponse::Result,
+            time_in_force: order.time_in_force(),
+            is_isolated: None,
+            side_effect_type: crate::rest_model::SideEffectType::NoSideEffect,
+        };
+        self.new_order(request).await.map(VenueOrder::from)
+    }
+
+    async fn cancel(&self, order: CancelOrder) -> Result<()> {
+        order.valid()?;
+        self.cancel_order(MarginOrderCancellation::from(order))
+            .await
+            .map(|_| ())
+    }
+
+    async fn open_orders(&self, symbol: &str) -> Result<Vec<VenueOrder>> {
+        let orders = Margin::open_orders(self, symbol, None).await?;
+        Ok(orders.into_iter().map(VenueOrder::from).collect())
+    }
+
+    async fn balances(&self) -> Result<Vec<VenueBalance>> {
+        let details = self.details().await?;
+        Ok(details
+            .user_assets
+            .into_iter()
+            .map(|asset| VenueBalance {
+                asset: asset.asset,
+                free: asset.free,
+                locked: asset.locked,
+            })
+            .collect())
+    }
+}
+
+#[cfg(feature = "futures_api")]
+fn futures_kind(order_type: &futures_model::OrderType) -> OrderKind {
+    use futures_model::OrderType;
+    match order_type {
+        OrderType::Market => OrderKind::Market,
+        OrderType::Limit => OrderKind::Limit,
+        OrderType::StopMarket => OrderKind::StopMarket,
+        OrderType::Stop => OrderKind::StopLimit,
+        OrderType::TakeProfitMarket => OrderKind::TakeProfitMarket,
+        OrderType::TakeProfit => OrderKind::TakeProfitLimit,
+        OrderType::TrailingStopMarket => OrderKind::Other,
+    }
+}
+
+#[cfg(feature = "futures_api")]
+impl From<futures_model::Order> for VenueOrder {
+    fn from(order: futures_model::Order) -> Self {
+        Self {
+            kind: futures_kind(&order.orig_type),
+            symbol: order.symbol,
+            order_id: order.order_id,
+            client_order_id: order.client_order_id,
+            side: order.side,
+            price: order.price,
+            orig_qty: order.orig_qty,
+            executed_qty: order.executed_qty,
+            status: order.status,
+        }
+    }
+}
+
+/// Trades perpetual and delivery contracts, [`VenueBalance::locked`] is the part of the wallet
+/// balance which is not available, such as margin of positions and open orders
+#[cfg(feature = "futures_api")]
+impl Trading for FuturesAccount {
+    async fn place(&self, order: PlaceOrder) -> Result<VenueOrder> {
+        order.valid()?;
+        let request = crate::futures::account::OrderRequest {
+            symbol: order.symbol.clone(),
+            side: order.side.clone(),
+            order_type: order.futures_order_type()?,
+            time_in_force: order.time_in_force(),
+            quantity: Some(order.qty),
+            reduce_only: order.reduce_only.then_some(true),
+            price: order.price,
+            stop_price: order.stop_price,
+            new_client_order_id: order.client_order_id.clone(),
+            ..Default::default()
+        };
+        let transaction = self.place_order(request).await?;
+        Ok(VenueOrder {
+            kind: order.kind,
+            // Market orders have no price, they report their average fill price instead
+            price: if transaction.price.is_zero() {
+                transaction.avg_price
+            } else {
+                transaction.price
+            },
+            symbol: transaction.symbol,
+            order_id: transaction.order_id,
+            client_order_id: transaction.client_order_id,
+            side: transaction.side,
+            orig_qty: transaction.orig_qty,
+            executed_qty: transaction.executed_qty,
+            status: transaction.status,
+        })
+    }
+
+    async fn cancel(&self, order: CancelOrder) -> Result<()> {
+        order.valid()?;
+        let cancellation = OrderCancellation {
+            symbol: order.symbol,
+            order_id: order.order_id,
+            orig_client_order_id: order.client_order_id,
+            ..OrderCancellation::default()
+        };
+        self.cancel_order(cancellation).await.map(|_| ())
+    }
+
+    async fn open_orders(&self, symbol: &str) -> Result<Vec<VenueOrder>> {
+        let orders = self.get_open_orders(symbol).await?;
+        Ok(orders.into_iter().map(VenueOrder::from).collect())
+    }
+
+    async fn balances(&self) -> Result<Vec<VenueBalance>> {
+        let balances = self.account_balance().await?;
+        Ok(balances
+            .into_iter()
+            .map(|balance| VenueBalance {
+                asset: balance.asset,
+                free: balance.available_balance,
+                locked: (balance.balance - balance.available_balance).max(Decimal::ZERO),
+            })
+            .collect())
+    }
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    orders: Vec<VenueOrder>,
+    placed: Vec<PlaceOrder>,
+    balances: HashMap<String, VenueBalance>,
+    prices: HashMap<String, Decimal>,
+    next_order_id: u64,
+}
+
+/// In-memory venue for tests
+///
+/// Limit and stop orders stay open until [`MockTrading::fill`] is called, market orders are filled
+/// at once at the price set with [`MockTrading::set_price`]. Balances only change through
+/// [`MockTrading::set_balance`].
+#[derive(Debug, Default)]
+pub struct MockTrading {
+    state: Mutex<MockState>,
+}
+
+impl MockTrading {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn set_balance(&self, asset: &str, free: Decimal, locked: Decimal) {
+        let balance = VenueBalance {
+            asset: asset.to_string(),
+            free,
+            locked,
+        };
+        self.state().balances.insert(asset.to_string(), balance);
+    }
+
+    /// Sets the price market orders of a symbol are filled at
+    pub fn set_price(&self, symbol: &str, price: Decimal) {
+        self.state().prices.insert(symbol.to_string(), price);
+    }
+
+    /// Fills an open order for a quantity, up to its remaining quantity
+    pub fn fill(&self, order_id: u64, qty: Decimal) -> Result<VenueOrder> {
+        let mut state = self.state();
+        let order = state
+            .orders
+            .iter_mut()
+            .find(|o| o.order_id == order_id && o.is_open())
+            .ok_or_else(unknown_order)?;
+        order.executed_qty = (order.executed_qty + qty).min(order.orig_qty);
+        order.status = if order.remaining_qty().is_zero() {
+            OrderStatus::Filled
+        } else {
+            OrderStatus::PartiallyFilled
+        };
+        Ok(order.clone())
+    }
+
+    /// Orders placed so far, in any status
+    pub fn orders(&self) -> Vec<VenueOrder> { self.state().orders.clone() }
+
+    /// Requests received by [`Trading::place`], including the rejected ones
+    pub fn placed(&self) -> Vec<PlaceOrder> { self.state().placed.clone() }
+
+    fn state(&self) -> MutexGuard<'_, MockState> { self.state.lock().unwrap_or_else(|e| e.into_inner()) }
+}
+
+fn unknown_order() -> Error {
+    Error::BinanceError {
+        response: BinanceContentError::new(-2011, "Unknown order sent."),
+    }
+}
+
+impl Trading for MockTrading {
+    async fn place(&self, order: PlaceOrder) -> Result<VenueOrder> {
+        let mut state = self.state();
+        state.placed.push(order.clone());
+        order.valid()?;
+        state.next_order_id += 1;
+        let market = order.kind == OrderKind::Market;
+        let placed = VenueOrder {
+            symbol: order.symbol.clone(),
+            order_id: state.next_order_id,
+            client_order_id: order
+                .client_order_id
+                .unwrap_or_else(|| format!("mock-{}", state.next_order_id)),
+            side: order.side,
+            kind: order.kind,
+            price: match order.price {
+                Some(price) => price,
+                None => state.prices.get(&order.symbol).copied().unwrap_or_default(),
+            },
+            orig_qty: order.qty,
+            executed_qty: if market { order.qty } else { Decimal::ZERO },
+            status: if market { OrderStatus::Filled } else { OrderStatus::New },
+        };
+        state.orders.push(placed.clone());
+        Ok(placed)
+    }
+
+    async fn cancel(&self, order: CancelOrder) -> Result<()> {
+        order.valid()?;
+        let mut state = self.state();
+        let canceled = state
+            .orders
+            .iter_mut()
+            .find(|o| {
+                o.symbol == order.symbol
+                    && o.is_open()
+                    && (order.order_id == Some(o.order_id)
+                        || order.client_order_id.as_deref() == Some(o.client_order_id.as_str()))
+            })
+            .ok_or_else(unknown_order)?;
+        canceled.status = OrderStatus::Canceled;
+        Ok(())
+    }
+
+    async fn open_orders(&self, symbol: &str) -> Result<Vec<VenueOrder>> {
+        let state = self.state();
+        Ok(state
+            .orders
+            .iter()
+            .filter(|o| o.symbol == symbol && o.is_open())
+            .cloned()
+            .collect())
+    }
+
+    async fn balances(&self) -> Result<Vec<VenueBalance>> { Ok(self.state().balances.values().cloned().collect()) }
+}
+
+/// Venue selected by configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VenueKind {
+    Spot,
+    #[cfg(feature = "margin_api")]
+    Margin,
+    #[cfg(feature = "futures_api")]
+    Futures,
+    Mock,
+}
+
+/// Any of the venues implementing [`Trading`]
+/// # Examples
+/// ```rust,no_run
+/// use binance::{config::*, rest_model::OrderSide, trading::*};
+/// use rust_decimal_macros::dec;
+/// let venue = Venue::new_with_env(VenueKind::Spot, &Config::testnet());
+/// let order = PlaceOrder::limit("BTCUSDT", OrderSide::Buy, dec!(0.001), dec!(20000));
+/// let placed = tokio_test::block_on(venue.place(order));
+/// assert!(placed.is_ok(), "{:?}", placed);
+/// ```
+pub enum Venue {
+    Spot(Account),
+    #[cfg(feature = "margin_api")]
+    Margin(Margin),
+    #[cfg(feature = "futures_api")]
+    Futures(FuturesAccount),
+    Mock(MockTrading),
+}
+
+impl Venue {
+    pub fn new_with_config(
+        kind: VenueKind,
+        api_key: Option<String>,
+        secret_key: Option<String>,
+        config: &Config,
+    ) -> Self {
+        match kind {
+            VenueKind::Spot => Self::Spot(Account::new_with_config(api_key, secret_key, config)),
+            #[cfg(feature = "margin_api")]
+            VenueKind::Margin => Self::Margin(Margin::new_with_config(api_key, secret_key, config)),
+            #[cfg(feature = "futures_api")]
+            VenueKind::Futures => Self::Futures(FuturesAccount::new_with_config(api_key, secret_key, config)),
+            VenueKind::Mock => Self::Mock(MockTrading::new()),
+        }
+    }
+
+    /// Creates a venue with the credentials from the `BINANCE_API_KEY` and `BINANCE_API_SECRET_KEY`
+    /// environment variables
+    pub fn new_with_env(kind: VenueKind, config: &Config) -> Self {
+        let api_key = std::env::var("BINANCE_API_KEY").ok();
+        let secret = std::env::var("BINANCE_API_SECRET_KEY").ok();
+        Self::new_with_config(kind, api_key, secret, config)
+    }
+
+    pub fn kind(&self) -> VenueKind {
+        match self {
+            Self::Spot(_) => VenueKind::Spot,
+            #[cfg(feature = "margin_api")]
+            Self::Margin(_) => VenueKind::Margin,
+            #[cfg(feature = "futures_api")]
+            Self::Futures(_) => VenueKind::Futures,
+            Self::Mock(_) => VenueKind::Mock,
+        }
+    }
+}
+
+macro_rules! dispatch {
+    ($venue:expr, $trading:ident => $call:expr) => {
+        match $venue {
+            Venue::Spot($trading) => $call.await,
+            #[cfg(feature = "margin_api")]
+            Venue::Margin($trading) => $call.await,
+            #[cfg(feature = "futures_api")]
+            Venue::Futures($trading) => $call.await,
+            Venue::Mock($trading) => $call.await,
+        }
+    };
+}
+
+impl Trading for Venue {
+    async fn place(&self, order: PlaceOrder) -> Result<VenueOrder> { dispatch!(self, t => t.place(order)) }
+
+    async fn cancel(&self, order: CancelOrder) -> Result<()> { dispatch!(self, t => t.cancel(order)) }
+
+    async fn open_orders(&self, symbol: &str) -> Result<Vec<VenueOrder>> {
+        dispatch!(self, t => Trading::open_orders(t, symbol))
+    }
+
+    async fn balances(&self) -> Result<Vec<VenueBalance>> { dispatch!(self, t => t.balances()) }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn place_order_validation() {
+        assert!(PlaceOrder::market("BTCUSDT", OrderSide::Buy, dec!(1)).valid().is_ok());
+        assert!(
+            PlaceOrder::stop_limit("BTCUSDT", OrderSide::Sell, dec!(1), dec!(90), dec!(95))
+                .valid()
+                .is_ok()
+        );
+        assert!(PlaceOrder::market("BTCUSDT", OrderSide::Buy, dec!(0)).valid().is_err());
+        let missing_price = PlaceOrder {
+            price: None,
+            ..PlaceOrder::limit("BTCUSDT", OrderSide::Buy, dec!(1), dec!(100))
+        };
+        assert!(missing_price.valid().is_err());
+        let unexpected_stop = PlaceOrder {
+            stop_price: Some(dec!(95)),
+            ..PlaceOrder::market("BTCUSDT", OrderSide::Buy, dec!(1))
+        };
+        assert!(unexpected_stop.valid().is_err());
+        let other = PlaceOrder {
+            kind: OrderKind::Other,
+            ..PlaceOrder::market("BTCUSDT", OrderSide::Buy, dec!(1))
+        };
+        assert!(matches!(other.valid(), Err(Error::InvalidOrderError { .. })));
+        assert!(CancelOrder {
+            symbol: "BTCUSDT".to_string(),
+            order_id: None,
+            client_order_id: None,
+        }
+        .valid()
+        .is_err());
+    }
+
+    #[test]
+    fn mock_fills_market_orders_at_the_set_price() {
+        let mock = MockTrading::new();
+        mock.set_price("BTCUSDT", dec!(100));
+        let placed = tokio_test::block_on(mock.place(PlaceOrder::market("BTCUSDT", OrderSide::Buy, dec!(2)))).unwrap();
+        assert_eq!(placed.status, OrderStatus::Filled);
+        assert_eq!(placed.price, dec!(100));
+        assert_eq!(placed.executed_qty, dec!(2));
+        assert_eq!(placed.client_order_id, "mock-1");
+        assert!(tokio_test::block_on(mock.open_orders("BTCUSDT")).unwrap().is_empty());
+
+        let rejected = PlaceOrder::market("BTCUSDT", OrderSide::Buy, dec!(0));
+        assert!(tokio_test::block_on(mock.place(rejected)).is_err());
+        assert_eq!(mock.placed().len(), 2);
+        assert_eq!(mock.orders().len(), 1);
+    }
+
+    #[test]
+    fn mock_keeps_limit_orders_open_until_filled_or_canceled() {
+        let mock = MockTrading::new();
+        let order = PlaceOrder::limit("BTCUSDT", OrderSide::Sell, dec!(3), dec!(110)).set_client_order_id("exit");
+        let placed = tokio_test::block_on(mock.place(order)).unwrap();
+        assert_eq!(placed.status, OrderStatus::New);
+        assert_eq!(
+            tokio_test::block_on(mock.open_orders("BTCUSDT")).unwrap(),
+            vec![placed.clone()]
+        );
+
+        let filled = mock.fill(placed.order_id, dec!(1)).unwrap();
+        assert_eq!(filled.status, OrderStatus::PartiallyFilled);
+        assert_eq!(filled.remaining_qty(), dec!(2));
+        tokio_test::block_on(mock.cancel(CancelOrder::by_client_id("BTCUSDT", "exit"))).unwrap();
+        assert_eq!(mock.orders()[0].status, OrderStatus::Canceled);
+        assert!(tokio_test::block_on(mock.cancel(CancelOrder::by_id("BTCUSDT", placed.order_id))).is_err());
+        assert!(mock.fill(placed.order_id, dec!(1)).is_err());
+
+        let placed =
+            tokio_test::block_on(mock.place(PlaceOrder::limit("BTCUSDT", OrderSide::Buy, dec!(1), dec!(90)))).unwrap();
+        let filled = mock.fill(placed.order_id, dec!(5)).unwrap();
+        assert_eq!(filled.status, OrderStatus::Filled);
+        assert_eq!(filled.executed_qty, dec!(1));
+    }
+
+    #[test]
+    fn mock_balances() {
+        let mock = MockTrading::new();
+        mock.set_balance("USDT", dec!(100), dec!(20));
+        let balances = tokio_test::block_on(mock.balances()).unwrap();
+        assert_eq!(balances.len(), 1);
+        assert_eq!(balances[0].total(), dec!(120));
+        let venue = Venue::Mock(mock);
+        assert_eq!(venue.kind(), VenueKind::Mock);
+        assert_eq!(tokio_test::block_on(venue.balances()).unwrap(), balances);
+    }
+
+    #[cfg(feature = "margin_api")]
+    #[test]
+    fn margin_cancellation_sends_the_given_identifier_only() {
+        let by_client_id = MarginOrderCancellation::from(CancelOrder::by_client_id("BTCUSDT", "exit"));
+        assert_eq!(
+            crate::util::build_request_p(by_client_id).unwrap(),
+            "symbol=BTCUSDT&origClientOrderId=exit"
+        );
+        let by_id = MarginOrderCancellation::from(CancelOrder::by_id("BTCUSDT", 42));
+        assert_eq!(
+            crate::util::build_request_p(by_id).unwrap(),
+            "symbol=BTCUSDT&orderId=42"
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use crate::rest_model::{
-    string_or_float, Asks, Bids, ExecutionType, OrderBook, OrderSide, OrderStatus, OrderType, TimeInForce,
+    string_or_float, string_or_float_opt, Asks, Bids, ExecutionType, OrderBook, OrderSide, OrderStatus, OrderType,
+    SelfTradePreventionMode, TimeInForce,
 };
 use rust_decimal::prelude::*;
 
@@ -476,6 +477,35 @@ pub struct OrderUpdate {
     #[serde(rename = "Q")]
     #[serde(with = "string_or_float")]
     pub quote_order_qty: Decimal,
+    /// Only set for trailing stop orders
+    #[serde(rename = "d")]
+    pub trailing_delta: Option<u32>,
+    /// Only set for trailing stop orders, once activated
+    #[serde(rename = "D")]
+    pub trailing_time: Option<u64>,
+    #[serde(rename = "j")]
+    pub strategy_id: Option<u64>,
+    #[serde(rename = "J")]
+    pub strategy_type: Option<u64>,
+    #[serde(rename = "V")]
+    pub self_trade_prevention_mode: Option<SelfTradePreventionMode>,
+    /// Only set when the order expired because of self-trade prevention
+    #[serde(rename = "v")]
+    pub prevented_match_id: Option<u64>,
+    #[serde(default, rename = "A")]
+    #[serde(with = "string_or_float_opt")]
+    pub prevented_qty: Option<Decimal>,
+    #[serde(default, rename = "B")]
+    #[serde(with = "string_or_float_opt")]
+    pub last_prevented_qty: Option<Decimal>,
+    #[serde(rename = "u")]
+    pub trade_group_id: Option<i64>,
+    #[serde(rename = "U")]
+    pub counter_order_id: Option<u64>,
+    #[serde(rename = "Cs")]
+    pub counter_symbol: Option<String>,
+    #[serde(rename = "W")]
+    pub working_time: Option<u64>,
 }
 
 /// For OCO Events